@@ -8,8 +8,15 @@ pub enum AssetError {
     CheckpointIndexError = 1,
     InsufficientAllowance = 2,
     DaoAlreadyIssuedToken = 3,
-    NotTokenOwner = 4,
-    CanOnlyBeMintedOnce = 5,
     InsufficientBalance = 6,
-    NoCheckpoint = 7
+    NoCheckpoint = 7,
+    AlreadyDelegated = 8,
+    Decommissioned = 9,
+    NotDaoOwner = 10,
+    AlreadyMigrated = 11,
+    SupplyCapExceeded = 12,
+    MintLimitExceeded = 13,
+    InvalidExpirationLedger = 14,
+    InvalidString = 15,
+    InvalidCheckpointRetention = 16,
 }
\ No newline at end of file
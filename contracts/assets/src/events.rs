@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, Bytes, Symbol, symbol_short};
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, Symbol, symbol_short};
 
 pub const ASSET: Symbol = symbol_short!("ASSET");
 
@@ -6,6 +6,13 @@ pub const MINTED: Symbol = symbol_short!("minted");
 pub const OWNER_CHANGED: Symbol = symbol_short!("new_owner");
 pub const CORE_ADDRESS_CHANGED: Symbol = symbol_short!("new_govid");
 pub const TRANSFERRED: Symbol = symbol_short!("transfer");
+pub const DELEGATED: Symbol = symbol_short!("delegate");
+pub const DECOMMISSIONED: Symbol = symbol_short!("decomm");
+pub const UPGRADED: Symbol = symbol_short!("upgraded");
+pub const MIGRATED: Symbol = symbol_short!("migrated");
+pub const BURNED: Symbol = symbol_short!("burned");
+pub const MINT_LIMIT_SET: Symbol = symbol_short!("mlim_set");
+pub const CHECKPOINT_RETENTION_SET: Symbol = symbol_short!("cpret_set");
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -20,10 +27,25 @@ pub struct AssetCreatedEventData {
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AssetMintedEventData {
+    pub minter_id: Address,
+    /// The recipient of the minted assets
     pub owner_id: Address,
     pub amount: i128,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetMintLimitSetEventData {
+    pub period: u32,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetCheckpointRetentionSetEventData {
+    pub ledgers: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AssetNewOwnerEventData {
@@ -36,6 +58,12 @@ pub struct AssetSetGovernanceIDEventData {
     pub core_address: Address,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetDecommissionedEventData {
+    pub core_address: Address,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AssetTransferredEventData {
@@ -43,3 +71,30 @@ pub struct AssetTransferredEventData {
     pub new_owner_id: Address,
     pub amount: i128,
 }
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetBurnedEventData {
+    pub owner_id: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetDelegatedEventData {
+    pub owner_id: Address,
+    pub delegate_id: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetUpgradedEventData {
+    pub wasm_hash: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetMigratedEventData {
+    pub from_version: u32,
+    pub to_version: u32,
+}
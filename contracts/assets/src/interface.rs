@@ -1,6 +1,6 @@
-use soroban_sdk::{Address, Bytes, Env};
+use soroban_sdk::{Address, Bytes, BytesN, Env, String};
 
-use crate::types::Checkpoint;
+use crate::types::{AllowanceValue, Checkpoint, MintLimit};
 
 /// This follows the official specs w/o admin functionalities.
 pub trait AssetTrait {
@@ -10,16 +10,50 @@ pub trait AssetTrait {
     /// - `name`: Name of the DAO
     /// - `core_address`: Contract ID of the governance protocol to use. We'd be thrilled if you choose elio DAO's latest :-)
     /// - `owner`: The owner of this contract
+    /// - `max_supply`: The hard cap of the total supply, 0 for an uncapped supply
     ///
-    fn init(env: Env, symbol: Bytes, name: Bytes, owner: Address, core_address: Address);
+    fn init(env: Env, symbol: Bytes, name: Bytes, owner: Address, core_address: Address, max_supply: i128);
 
-    /// Mints tokens
+    /// Mints tokens, as often as the supply cap and the mint limit allow
     ///
-    /// - `supply`: Total tokens minted on launch
-    /// - `core_address`: Contract ID of the governance protocol to use. We'd be thrilled if you choose elio DAO's latest :-)
-    /// - `owner`: The current owner (must be authed and the current owner, obviously)
+    /// - `minter`: The owner of the DAO or an account with its minter role (must be authed)
+    /// - `to`: The recipient of the minted tokens
+    /// - `amount`: The amount to mint
+    ///
+    fn mint(env: Env, minter: Address, to: Address, amount: i128);
+
+    /// Returns the hard cap of the total supply, 0 if the supply is uncapped.
+    ///
+    fn max_supply(env: Env) -> i128;
+
+    /// Limit the amount that can be minted within each period, e.g. for an inflation schedule
+    ///
+    /// The periods are fixed windows of `period` ledgers, a period of 0 removes the limit.
+    ///
+    /// - `dao_owner`: The current owner of the DAO (must be authed)
+    /// - `period`: The length of a period in ledgers
+    /// - `amount`: The max amount minted within a period
+    ///
+    fn set_mint_limit(env: Env, dao_owner: Address, period: u32, amount: i128);
+
+    /// Returns the mint limit, a period of 0 means there is none.
     ///
-    fn mint(env: Env, owner: Address, supply: i128);
+    fn mint_limit(env: Env) -> MintLimit;
+
+    /// Set the number of ledgers the checkpoints are kept for, older ones are pruned on write
+    ///
+    /// The window has to cover the checkpoint window of the DAO in the votes contract, i.e. its
+    /// proposal duration, finalize window and execution delay, so proposals keep the voting power
+    /// of their creation ledger until they are executed.
+    ///
+    /// - `dao_owner`: The current owner of the DAO (must be authed)
+    /// - `ledgers`: The length of the retention window in ledgers, can't be 0
+    ///
+    fn set_checkpoint_retention(env: Env, dao_owner: Address, ledgers: u32);
+
+    /// Returns the number of ledgers the checkpoints are kept for.
+    ///
+    fn checkpoint_retention(env: Env) -> u32;
 
     /// Get the last recorded historical balance at or before the given ledger sequence number
     /// This is required by the voting protocil. If you roll your own token, this is a must have.
@@ -29,6 +63,34 @@ pub trait AssetTrait {
     ///
     fn get_balance_at(env: Env, id: Address, sequence: u32) -> i128;
 
+    /// Get the votes delegated to an account at or before the given ledger sequence number
+    /// This is the voting power the voting protocol uses, accounts delegate to themselves by default.
+    ///
+    /// - `id`: The address that you want to know the votes of
+    /// - `sequence`: ledger sequence number (aka env.ledger().sequence)
+    ///
+    fn get_votes_at(env: Env, id: Address, sequence: u32) -> i128;
+
+    /// Delegate the votes of an account to another account
+    ///
+    /// - `from`: The account whose votes are delegated (must be authed)
+    /// - `to`: The account receiving the votes, `from` itself to take them back
+    ///
+    fn delegate(env: Env, from: Address, to: Address);
+
+    /// Returns the account the votes of an account are delegated to
+    ///
+    /// - `id`: The delegating address
+    ///
+    fn delegates(env: Env, id: Address) -> Address;
+
+    /// Get the last recorded total supply at or before the given ledger sequence number
+    /// This is required by voting strategies that count the majority of the supply.
+    ///
+    /// - `sequence`: ledger sequence number (aka env.ledger().sequence)
+    ///
+    fn get_total_supply_at(env: Env, sequence: u32) -> i128;
+
     /// Discovery Function: Get the number of checkpoints stored for a given id
     ///
     /// - `id`: The address that you want to know the count of
@@ -48,7 +110,7 @@ pub trait AssetTrait {
 
     /// Change the owner of this token
     ///
-    /// - `owner`: The current owner (must be authed and the current owner, obviously)
+    /// - `owner`: The owner of the DAO in the core (must be authed)
     /// - `new_owner`: The new owner
     ///
     fn set_owner(env: Env, owner: Address, new_owner: Address);
@@ -59,7 +121,7 @@ pub trait AssetTrait {
     /// Change the core address of this token to either a different implementation or to upgrade to
     /// a newer version of elio DAO.
     ///
-    /// - `owner`: The current owner (must be authed and the current owner, obviously)
+    /// - `owner`: The owner of the DAO in the core (must be authed)
     /// - `core_address`: Contract ID of the governance protocol to use. We'd be thrilled if you choose elio DAO's latest :-)
     ///
     fn set_core_address(env: Env, owner: Address, core_address: Address);
@@ -68,6 +130,34 @@ pub trait AssetTrait {
     ///
     fn core_address(env: Env) -> Address;
 
+    /// Replace the wasm of the token, the balances and checkpoints are kept
+    ///
+    /// Call `migrate` afterwards if the new wasm changes the storage layout.
+    ///
+    /// - `new_wasm_hash`: The hash of the installed wasm
+    /// - `dao_owner`: The owner of the DAO in the core (must be authed)
+    ///
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>, dao_owner: Address);
+
+    /// Returns the version of the storage layout.
+    ///
+    fn version(env: Env) -> u32;
+
+    /// Rewrite the storage of an older layout after an upgrade, returns the current version
+    ///
+    /// - `dao_owner`: The owner of the DAO in the core (must be authed)
+    /// - `total_supply`: The total supply of a token of version 0, which didn't keep it, ignored
+    ///   for later versions
+    ///
+    fn migrate(env: Env, dao_owner: Address, total_supply: i128) -> u32;
+
+    /// Freeze the token once the DAO is destroyed. Balances, checkpoints and the core address can
+    /// still be read, transfers, allowances, burning, minting and delegation fail with `Decommissioned`.
+    ///
+    /// Can only be called by the core (must be authed).
+    ///
+    fn decommission(env: Env);
+
     // ----------------------------------------------------------------------------------------
     // Token interface -> Everything starting from here satisfies the soroban token interface
     // ----------------------------------------------------------------------------------------
@@ -76,23 +166,29 @@ pub trait AssetTrait {
     // (usually named `from` here) using all the input arguments, i.e. they have
     // to call  `from.require_auth()`.
 
-    /// Increase the allowance by "amount" for "spender" to transfer/burn from "from".
-    /// Emit event with topics = ["incr_allow", from: Address, spender: Address], data = [amount: i128]
-    fn incr_allow(env: Env, from: Address, spender: Address, amount: i128);
-
-    /// Decrease the allowance by "amount" for "spender" to transfer/burn from "from".
-    /// If "amount" is greater than the current allowance, set the allowance to 0.
-    /// Emit event with topics = ["decr_allow", from: Address, spender: Address], data = [amount: i128]
-    fn decr_allow(env: Env, from: Address, spender: Address, amount: i128);
+    /// Set the allowance to "amount" for "spender" to transfer/burn from "from".
+    /// "expiration_ledger" can't be before the current ledger unless "amount" is 0, the allowance
+    /// reads as 0 after it.
+    /// Emit event with topics = ["approve", from: Address, spender: Address], data = [amount: i128, expiration_ledger: u32]
+    fn approve(env: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32);
 
-    /// Transfer "amount" from "from" to "to.
-    /// Emit event with topics = ["transfer", from: Address, to: Address], data = [amount: i128]
-    fn xfer(env: Env, from: Address, to: Address, amount: i128);
+    /// Transfer "amount" from "from" to "to".
+    /// Emit event with topics = ["transfer", symbol: Bytes], data = [from: Address, to: Address, amount: i128]
+    fn transfer(env: Env, from: Address, to: Address, amount: i128);
 
     /// Transfer "amount" from "from" to "to", consuming the allowance of "spender".
     /// Authorized by spender (`spender.require_auth()`).
-    /// Emit event with topics = ["transfer", from: Address, to: Address], data = [amount: i128]
-    fn xfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128);
+    /// Emit event with topics = ["transfer", symbol: Bytes], data = [from: Address, to: Address, amount: i128]
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128);
+
+    /// Burn "amount" from "from", reducing the total supply.
+    /// Emit event with topics = ["burned", symbol: Bytes], data = [from: Address, amount: i128]
+    fn burn(env: Env, from: Address, amount: i128);
+
+    /// Burn "amount" from "from", consuming the allowance of "spender".
+    /// Authorized by spender (`spender.require_auth()`).
+    /// Emit event with topics = ["burned", symbol: Bytes], data = [from: Address, amount: i128]
+    fn burn_from(env: Env, spender: Address, from: Address, amount: i128);
 
     // --------------------------------------------------------------------------------
     // Read-only Token interface
@@ -107,7 +203,7 @@ pub trait AssetTrait {
     /// Get the spendable balance of "id". This will return the same value as balance()
     /// unless this is called on the Stellar Asset Contract, in which case this can
     /// be less due to reserves/liabilities.
-    fn spendable(env: Env, id: Address) -> i128;
+    fn spendable_balance(env: Env, id: Address) -> i128;
 
     // DAO tokens are always authorized, so this is just returning true for our purposes.
     fn authorized(env: Env, id: Address) -> bool;
@@ -115,6 +211,9 @@ pub trait AssetTrait {
     /// Get the allowance for "spender" to transfer from "from".
     fn allowance(env: Env, from: Address, spender: Address) -> i128;
 
+    /// Get the allowance for "spender" to transfer from "from" with its expiration ledger.
+    fn get_allowance(env: Env, from: Address, spender: Address) -> AllowanceValue;
+
     // --------------------------------------------------------------------------------
     // Descriptive Interface
     // --------------------------------------------------------------------------------
@@ -123,8 +222,36 @@ pub trait AssetTrait {
     fn decimals(env: Env) -> u32;
 
     // Get the name for this token.
-    fn name(env: Env) -> Bytes;
+    fn name(env: Env) -> String;
 
-    // Get the symbol for this token.
-    fn symbol(env: Env) -> Bytes;
+    // Get the symbol for this token, which is the id of the DAO.
+    fn symbol(env: Env) -> String;
+
+    // --------------------------------------------------------------------------------
+    // Deprecated Token interface
+    // --------------------------------------------------------------------------------
+    //
+    // The names of the pre-release token interface, they are kept during the transition
+    // and will be removed in a future version.
+
+    /// Deprecated, use `approve` instead.
+    /// Increase the allowance by "amount" for "spender" to transfer/burn from "from".
+    /// The allowance keeps its expiration ledger, a new one expires after the bump duration of the protocol.
+    /// Emit event with topics = ["increase_allowance", from: Address, spender: Address], data = [amount: i128, expiration_ledger: u32]
+    fn incr_allow(env: Env, from: Address, spender: Address, amount: i128);
+
+    /// Deprecated, use `approve` instead.
+    /// Decrease the allowance by "amount" for "spender" to transfer/burn from "from".
+    /// If "amount" is greater than the current allowance, set the allowance to 0.
+    /// Emit event with topics = ["decrease_allowance", from: Address, spender: Address], data = [amount: i128, expiration_ledger: u32]
+    fn decr_allow(env: Env, from: Address, spender: Address, amount: i128);
+
+    /// Deprecated, use `transfer` instead.
+    fn xfer(env: Env, from: Address, to: Address, amount: i128);
+
+    /// Deprecated, use `transfer_from` instead.
+    fn xfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128);
+
+    /// Deprecated, use `spendable_balance` instead.
+    fn spendable(env: Env, id: Address) -> i128;
 }
@@ -2,9 +2,13 @@
 
 use events::{
     AssetMintedEventData, AssetNewOwnerEventData, AssetSetGovernanceIDEventData,
-    AssetTransferredEventData, ASSET, CORE_ADDRESS_CHANGED, MINTED, OWNER_CHANGED, TRANSFERRED,
+    AssetTransferredEventData, AssetDelegatedEventData, AssetDecommissionedEventData, ASSET,
+    CORE_ADDRESS_CHANGED, DECOMMISSIONED, DELEGATED, MINTED, OWNER_CHANGED, TRANSFERRED,
+    AssetMigratedEventData, AssetUpgradedEventData, MIGRATED, UPGRADED, AssetBurnedEventData, BURNED,
+    AssetMintLimitSetEventData, MINT_LIMIT_SET, AssetCheckpointRetentionSetEventData,
+    CHECKPOINT_RETENTION_SET,
 };
-use soroban_sdk::{contractimpl, contract, Address, Bytes, Env, Symbol, panic_with_error};
+use soroban_sdk::{contractimpl, contract, Address, Bytes, BytesN, Env, String, Symbol, panic_with_error};
 
 mod core_contract {
     soroban_sdk::contractimport!(file = "../../wasm/elio_core.wasm");
@@ -26,9 +30,9 @@ mod types;
 mod error;
 mod hooks;
 
-use types::{Checkpoint, Token};
+use types::{AllowanceValue, Checkpoint, MintLimit, Token, STORAGE_VERSION};
 use crate::error::AssetError;
-use crate::hooks::{on_decr_allowance, on_incr_allowance, on_xfer, on_xfer_from};
+use crate::hooks::{on_approve, on_burn, on_decr_allowance, on_incr_allowance, on_xfer, on_xfer_from};
 
 
 #[contract]
@@ -40,25 +44,69 @@ fn check_non_negative_amount(env: &Env, amount: i128) {
     }
 }
 
+/// The current owner of the DAO and the accounts with its minter role can mint
+fn check_minter(env: &Env, minter: &Address) {
+    minter.require_auth();
+    let core = core_contract::Client::new(env, &Token::get_core_address(env));
+    let dao_id = Token::get_symbol(env);
+    if minter != &core.get_dao(&dao_id).owner && !core.has_role(&dao_id, &core_contract::Role::Minter, minter) {
+        panic_with_error!(env, AssetError::NotDaoOwner)
+    }
+}
+
 #[contractimpl]
 impl AssetTrait for AssetContract {
-    fn init(env: Env, symbol: Bytes, name: Bytes, owner: Address, core_address: Address) {
-        Token::create(&env, &symbol, &name, &owner, &core_address);
+    fn init(env: Env, symbol: Bytes, name: Bytes, owner: Address, core_address: Address, max_supply: i128) {
+        Token::create(&env, &symbol, &name, &owner, &core_address, max_supply);
     }
 
-    fn mint(env: Env, owner: Address, supply: i128) {
-        Token::check_auth(&env, &owner);
-        Token::check_is_minted(&env, owner.clone());
-        Token::write_balance(&env, owner.clone(), supply);
+    fn mint(env: Env, minter: Address, to: Address, amount: i128) {
+        Token::check_not_decommissioned(&env);
+        check_minter(&env, &minter);
+        check_non_negative_amount(&env, amount);
+        Token::check_mint(&env, amount);
+        // minting is rare enough to refresh the protocol config that transfers bump with
+        Token::sync_protocol_config(&env);
+        Token::receive_balance(&env, to.clone(), amount);
+        Token::write_total_supply(&env, Token::read_total_supply(&env) + amount);
         env.events().publish(
             (ASSET, MINTED, Token::get_symbol(&env)),
             AssetMintedEventData {
-                owner_id: owner,
-                amount: supply,
+                minter_id: minter,
+                owner_id: to,
+                amount,
             },
         );
     }
 
+    fn max_supply(env: Env) -> i128 {
+        Token::get_max_supply(&env)
+    }
+
+    fn set_mint_limit(env: Env, dao_owner: Address, period: u32, amount: i128) {
+        Token::set_mint_limit(&env, &dao_owner, &MintLimit { period, amount });
+        env.events().publish(
+            (ASSET, MINT_LIMIT_SET, Token::get_symbol(&env)),
+            AssetMintLimitSetEventData { period, amount },
+        );
+    }
+
+    fn mint_limit(env: Env) -> MintLimit {
+        Token::get_mint_limit(&env)
+    }
+
+    fn set_checkpoint_retention(env: Env, dao_owner: Address, ledgers: u32) {
+        Token::set_checkpoint_retention(&env, &dao_owner, ledgers);
+        env.events().publish(
+            (ASSET, CHECKPOINT_RETENTION_SET, Token::get_symbol(&env)),
+            AssetCheckpointRetentionSetEventData { ledgers },
+        );
+    }
+
+    fn checkpoint_retention(env: Env) -> u32 {
+        Token::get_checkpoint_retention(&env)
+    }
+
     fn set_owner(env: Env, owner: Address, new_owner: Address) {
         Token::set_owner(&env, &owner, &new_owner);
         env.events().publish(
@@ -85,43 +133,59 @@ impl AssetTrait for AssetContract {
         Token::get_core_address(&env)
     }
 
-    fn incr_allow(env: Env, from: Address, spender: Address, amount: i128) {
-        from.require_auth();
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>, dao_owner: Address) {
+        Token::check_dao_owner(&env, &dao_owner);
+        env.deployer().update_current_contract_wasm(new_wasm_hash.clone());
+        env.events().publish(
+            (ASSET, UPGRADED, Token::get_symbol(&env)),
+            AssetUpgradedEventData {
+                wasm_hash: new_wasm_hash,
+            },
+        );
+    }
 
-        let amount_post_hook = on_incr_allowance(&env, &from, &spender, amount);
+    fn version(env: Env) -> u32 {
+        Token::read_version(&env)
+    }
 
-        check_non_negative_amount(&env, amount_post_hook);
-        let allowance = Token::read_allowance(&env, from.clone(), spender.clone());
-        let new_allowance = allowance + amount_post_hook;
+    fn migrate(env: Env, dao_owner: Address, total_supply: i128) -> u32 {
+        Token::check_dao_owner(&env, &dao_owner);
+        let from_version = Token::migrate(&env, total_supply);
+        env.events().publish(
+            (ASSET, MIGRATED, Token::get_symbol(&env)),
+            AssetMigratedEventData {
+                from_version,
+                to_version: STORAGE_VERSION,
+            },
+        );
+        STORAGE_VERSION
+    }
 
-        Token::write_allowance(&env, from.clone(), spender.clone(), new_allowance);
+    fn decommission(env: Env) {
+        let core_address = Token::decommission(&env);
         env.events().publish(
-            (Symbol::new(&env, "increase_allowance"), from, spender),
-            amount_post_hook
+            (ASSET, DECOMMISSIONED, Token::get_symbol(&env)),
+            AssetDecommissionedEventData { core_address },
         );
     }
 
-    fn decr_allow(env: Env, from: Address, spender: Address, amount: i128) {
+    fn approve(env: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
         from.require_auth();
+        Token::check_not_decommissioned(&env);
 
-        let amount_posthook = on_decr_allowance(&env, &from, &spender, amount);
+        let amount_posthook = on_approve(&env, &from, &spender, amount);
 
         check_non_negative_amount(&env, amount_posthook);
-
-        let allowance = Token::read_allowance(&env, from.clone(), spender.clone());
-        if amount_posthook >= allowance {
-            Token::write_allowance(&env, from.clone(), spender.clone(), 0);
-        } else {
-            Token::write_allowance(&env, from.clone(), spender.clone(), allowance - amount_posthook);
-        }
+        Token::write_allowance(&env, from.clone(), spender.clone(), amount_posthook, expiration_ledger);
         env.events().publish(
-            (Symbol::new(&env, "decrease_allowance"), from, spender),
-            amount_posthook,
+            (Symbol::new(&env, "approve"), from, spender),
+            (amount_posthook, expiration_ledger),
         );
     }
 
-    fn xfer(env: Env, from: Address, to: Address, amount: i128) {
+    fn transfer(env: Env, from: Address, to: Address, amount: i128) {
         from.require_auth();
+        Token::check_not_decommissioned(&env);
 
         let amount_posthook = on_xfer(&env, &from, &to, amount);
 
@@ -138,8 +202,9 @@ impl AssetTrait for AssetContract {
         );
     }
 
-    fn xfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
         spender.require_auth();
+        Token::check_not_decommissioned(&env);
 
         let amount_posthook = on_xfer_from(&env, &spender, &from, &to, amount);
 
@@ -157,11 +222,46 @@ impl AssetTrait for AssetContract {
         );
     }
 
+    fn burn(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+        Token::check_not_decommissioned(&env);
+
+        let amount_posthook = on_burn(&env, &from, amount);
+
+        check_non_negative_amount(&env, amount_posthook);
+        Token::burn(&env, from.clone(), amount_posthook);
+        env.events().publish(
+            (ASSET, BURNED, Token::get_symbol(&env)),
+            AssetBurnedEventData {
+                owner_id: from,
+                amount: amount_posthook,
+            },
+        );
+    }
+
+    fn burn_from(env: Env, spender: Address, from: Address, amount: i128) {
+        spender.require_auth();
+        Token::check_not_decommissioned(&env);
+
+        let amount_posthook = on_burn(&env, &from, amount);
+
+        check_non_negative_amount(&env, amount_posthook);
+        Token::spend_allowance(&env, from.clone(), spender, amount_posthook);
+        Token::burn(&env, from.clone(), amount_posthook);
+        env.events().publish(
+            (ASSET, BURNED, Token::get_symbol(&env)),
+            AssetBurnedEventData {
+                owner_id: from,
+                amount: amount_posthook,
+            },
+        );
+    }
+
     fn balance(env: Env, addr: Address) -> i128 {
         Token::read_balance(&env, addr)
     }
 
-    fn spendable(env: Env, addr: Address) -> i128 {
+    fn spendable_balance(env: Env, addr: Address) -> i128 {
         // just the balance for our purposes
         Self::balance(env, addr)
     }
@@ -172,6 +272,10 @@ impl AssetTrait for AssetContract {
     }
 
     fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        Token::read_allowance(&env, from, spender).amount
+    }
+
+    fn get_allowance(env: Env, from: Address, spender: Address) -> AllowanceValue {
         Token::read_allowance(&env, from, spender)
     }
 
@@ -179,12 +283,12 @@ impl AssetTrait for AssetContract {
         18
     }
 
-    fn name(env: Env) -> Bytes {
-        Token::get_name(&env)
+    fn name(env: Env) -> String {
+        Token::to_string(&env, &Token::get_name(&env))
     }
 
-    fn symbol(env: Env) -> Bytes {
-        Token::get_symbol(&env)
+    fn symbol(env: Env) -> String {
+        Token::to_string(&env, &Token::get_symbol(&env))
     }
 
     fn get_checkpoint_count(env: Env, id: Address) -> u32 {
@@ -195,6 +299,35 @@ impl AssetTrait for AssetContract {
         Token::get_checkpoint_at(&env, id, i)
     }
 
+    fn delegate(env: Env, from: Address, to: Address) {
+        from.require_auth();
+        Token::check_not_decommissioned(&env);
+
+        if Token::read_delegate(&env, from.clone()) == to {
+            panic_with_error!(env, AssetError::AlreadyDelegated)
+        }
+        Token::delegate(&env, from.clone(), to.clone());
+        env.events().publish(
+            (ASSET, DELEGATED, Token::get_symbol(&env)),
+            AssetDelegatedEventData {
+                owner_id: from,
+                delegate_id: to,
+            },
+        );
+    }
+
+    fn delegates(env: Env, addr: Address) -> Address {
+        Token::read_delegate(&env, addr)
+    }
+
+    fn get_votes_at(env: Env, addr: Address, sequence: u32) -> i128 {
+        Token::get_votes_for_sequence(&env, addr, sequence)
+    }
+
+    fn get_total_supply_at(env: Env, sequence: u32) -> i128 {
+        Token::get_supply_for_sequence(&env, sequence)
+    }
+
     fn get_balance_at(env: Env, addr: Address, sequence: u32) -> i128 {
         let checkpoint = Token::get_checkpoint_for_sequence(&env, addr, sequence);
         match checkpoint {
@@ -202,4 +335,60 @@ impl AssetTrait for AssetContract {
             None => 0
         }
     }
+
+    fn incr_allow(env: Env, from: Address, spender: Address, amount: i128) {
+        from.require_auth();
+        Token::check_not_decommissioned(&env);
+
+        let amount_post_hook = on_incr_allowance(&env, &from, &spender, amount);
+
+        check_non_negative_amount(&env, amount_post_hook);
+        let allowance = Token::read_allowance(&env, from.clone(), spender.clone());
+        let new_allowance = allowance.amount + amount_post_hook;
+        // an allowance keeps its expiration, a new one gets the default
+        let expiration_ledger = if allowance.amount > 0 {
+            allowance.expiration_ledger
+        } else {
+            Token::default_allowance_expiration(&env)
+        };
+
+        Token::write_allowance(&env, from.clone(), spender.clone(), new_allowance, expiration_ledger);
+        env.events().publish(
+            (Symbol::new(&env, "increase_allowance"), from, spender),
+            (amount_post_hook, expiration_ledger),
+        );
+    }
+
+    fn decr_allow(env: Env, from: Address, spender: Address, amount: i128) {
+        from.require_auth();
+        Token::check_not_decommissioned(&env);
+
+        let amount_posthook = on_decr_allowance(&env, &from, &spender, amount);
+
+        check_non_negative_amount(&env, amount_posthook);
+
+        let allowance = Token::read_allowance(&env, from.clone(), spender.clone());
+        let new_allowance = if amount_posthook >= allowance.amount {
+            0
+        } else {
+            allowance.amount - amount_posthook
+        };
+        Token::write_allowance(&env, from.clone(), spender.clone(), new_allowance, allowance.expiration_ledger);
+        env.events().publish(
+            (Symbol::new(&env, "decrease_allowance"), from, spender),
+            (amount_posthook, allowance.expiration_ledger),
+        );
+    }
+
+    fn xfer(env: Env, from: Address, to: Address, amount: i128) {
+        Self::transfer(env, from, to, amount)
+    }
+
+    fn xfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        Self::transfer_from(env, spender, from, to, amount)
+    }
+
+    fn spendable(env: Env, addr: Address) -> i128 {
+        Self::spendable_balance(env, addr)
+    }
 }
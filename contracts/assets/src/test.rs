@@ -1,8 +1,8 @@
 #![cfg(test)]
 
-use soroban_sdk::{testutils::{Address as _, Ledger, LedgerInfo}, Address, Env, IntoVal};
+use soroban_sdk::{testutils::{Address as _, Ledger, LedgerInfo}, token, Address, Bytes, Env, IntoVal, String};
 
-use crate::{core_contract, votes_contract, AssetContract, AssetContractClient};
+use crate::{core_contract, types::{Allowances, MintLimit, Token, MAX_TTL}, votes_contract, AssetContract, AssetContractClient};
 
 const SUPPLY: i128 = 1_000_000;
 
@@ -13,6 +13,7 @@ fn create_all_clients() -> (
 ) {
     let env = Env::default();
     env.mock_all_auths();
+    env.budget().reset_unlimited();
 
     let core_id = env.register_contract_wasm(None, core_contract::WASM);
     let votes_id = env.register_contract_wasm(None, votes_contract::WASM);
@@ -22,7 +23,7 @@ fn create_all_clients() -> (
 
     let native_asset_id = env.register_stellar_asset_contract(Address::random(&env));
 
-    core.init(&votes_id, &native_asset_id);
+    core.init(&votes_id, &native_asset_id, &Address::random(&env));
     votes.init(&core_id);
 
     let assets_id = env.register_contract(None, AssetContract);
@@ -32,12 +33,12 @@ fn create_all_clients() -> (
 }
 
 fn create_token(client: &AssetContractClient, core_client: &core_contract::Client) -> Address {
-    let symbol = "DIV".into_val(&client.env);
-    let name = "Deep Ink Ventures".into_val(&client.env);
-    let address = Address::random(&client.env);
-    let core_address = &core_client.address;
-    client.init(&symbol, &name, &address, &core_address);
-    client.mint(&address, &SUPPLY);
+    let env = &client.env;
+    let address = Address::random(env);
+    fund_account(env, &core_client.get_native_asset_id(), &address);
+    let dao = core_client.create_dao(&"DIV".into_val(env), &"Deep Ink Ventures".into_val(env), &address);
+    client.init(&dao.id, &dao.name, &address, &core_client.address, &0);
+    client.mint(&address, &address, &SUPPLY);
     address
 }
 
@@ -45,8 +46,8 @@ fn fund_account(env: &Env, native_asset_id: &Address, address: &Address) {
     const XLM: i128 = 10_000_000;
     const MINT: i128 = 10_000 * XLM;
 
-    let native_token = AssetContractClient::new(&env, &native_asset_id);
-    native_token.mint(&address, &MINT);
+    let native_token = token::StellarAssetClient::new(env, native_asset_id);
+    native_token.mint(address, &MINT);
 }
 
 #[test]
@@ -56,14 +57,16 @@ fn create_a_token() {
     let name = "Deep Ink Ventures".into_val(&client.env);
     let address = Address::random(&client.env);
     let core_address = &core_client.address;
-    client.init(&symbol, &name, &address, &core_address);
+    fund_account(&client.env, &core_client.get_native_asset_id(), &address);
+    core_client.create_dao(&symbol, &name, &address);
+    client.init(&symbol, &name, &address, core_address, &0);
 
-    assert_eq!(symbol, client.symbol());
-    assert_eq!(name, client.name());
+    assert_eq!(String::from_slice(&client.env, "DIV"), client.symbol());
+    assert_eq!(String::from_slice(&client.env, "Deep Ink Ventures"), client.name());
     assert_eq!(address, client.owner());
     assert_eq!(core_address, &client.core_address());
 
-    client.mint(&address, &SUPPLY);
+    client.mint(&address, &address, &SUPPLY);
 
     assert_eq!(SUPPLY, client.balance(&address));
 }
@@ -71,17 +74,194 @@ fn create_a_token() {
 #[test]
 #[should_panic(expected = "#3")]
 fn create_a_token_only_once() {
+    let (client, core_client, _) = create_all_clients();
+    let address = create_token(&client, &core_client);
+    client.init(&"DIV".into_val(&client.env), &"Deep Ink Ventures".into_val(&client.env), &address, &core_client.address, &0);
+}
+
+#[test]
+#[should_panic(expected = "#15")]
+fn create_a_token_with_a_long_name() {
+    let (client, core_client, _) = create_all_clients();
+    let env = &client.env;
+    let name = Bytes::from_slice(env, &[b'D'; 257]);
+    client.init(&"DIV".into_val(env), &name, &Address::random(env), &core_client.address, &0);
+}
+
+#[test]
+fn mint_repeatedly() {
+    let (client, core_client, _) = create_all_clients();
+    let address = create_token(&client, &core_client);
+    let recipient = Address::random(&client.env);
+    client.mint(&address, &recipient, &SUPPLY);
+
+    assert_eq!(client.balance(&recipient), SUPPLY);
+    assert_eq!(client.get_total_supply_at(&client.env.ledger().sequence()), 2 * SUPPLY);
+}
+
+#[test]
+#[should_panic(expected = "#12")]
+fn mint_within_the_supply_cap() {
+    let (client, core_client, _) = create_all_clients();
+    let env = &client.env;
+    let address = Address::random(env);
+    fund_account(env, &core_client.get_native_asset_id(), &address);
+    let dao = core_client.create_dao(&"DIV".into_val(env), &"Deep Ink Ventures".into_val(env), &address);
+    client.init(&dao.id, &dao.name, &address, &core_client.address, &SUPPLY);
+    client.mint(&address, &address, &SUPPLY);
+    client.burn(&address, &1);
+    client.mint(&address, &address, &1);
+
+    assert_eq!(client.max_supply(), SUPPLY);
+    client.mint(&address, &address, &1);
+}
+
+#[test]
+fn mint_limit_resets_every_period() {
+    let (client, core_client, _) = create_all_clients();
+    let env = &client.env;
+    let address = create_token(&client, &core_client);
+    env.ledger().with_mut(|ledger| ledger.sequence_number = 1_000);
+    client.set_mint_limit(&address, &100, &500);
+    assert_eq!(client.mint_limit(), MintLimit { period: 100, amount: 500 });
+
+    client.mint(&address, &address, &300);
+    client.mint(&address, &address, &200);
+    env.ledger().with_mut(|ledger| ledger.sequence_number = 1_100);
+    client.mint(&address, &address, &500);
+
+    assert_eq!(client.balance(&address), SUPPLY + 1_000);
+}
+
+#[test]
+#[should_panic(expected = "#13")]
+fn mint_within_the_mint_limit() {
+    let (client, core_client, _) = create_all_clients();
+    let address = create_token(&client, &core_client);
+    client.set_mint_limit(&address, &100, &500);
+
+    client.mint(&address, &address, &300);
+    client.mint(&address, &address, &201);
+}
+
+#[test]
+#[should_panic(expected = "#10")]
+fn set_mint_limit_only_as_dao_owner() {
     let (client, core_client, _) = create_all_clients();
     create_token(&client, &core_client);
-    create_token(&client, &core_client);
+
+    client.set_mint_limit(&Address::random(&client.env), &100, &500);
 }
 
 #[test]
-#[should_panic(expected = "#5")]
-fn mint_only_once() {
+fn mint_with_minter_role() {
     let (client, core_client, _) = create_all_clients();
-    let address= create_token(&client, &core_client);
-    client.mint(&address, &SUPPLY);
+    let env = &client.env;
+    let dao_owner = Address::random(env);
+    let minter = Address::random(env);
+    fund_account(env, &core_client.get_native_asset_id(), &dao_owner);
+    let dao = core_client.create_dao(&"DIV".into_val(env), &"Deep Ink Ventures".into_val(env), &dao_owner);
+    core_client.grant_role(&dao.id, &core_contract::Role::Minter, &minter, &dao_owner);
+
+    client.init(&dao.id, &dao.name, &dao_owner, &core_client.address, &0);
+    client.mint(&minter, &minter, &SUPPLY);
+    assert_eq!(client.balance(&minter), SUPPLY);
+}
+
+#[test]
+#[should_panic(expected = "#10")]
+fn mint_only_as_minter() {
+    let (client, core_client, _) = create_all_clients();
+    let env = &client.env;
+    let dao_owner = Address::random(env);
+    fund_account(env, &core_client.get_native_asset_id(), &dao_owner);
+    let dao = core_client.create_dao(&"DIV".into_val(env), &"Deep Ink Ventures".into_val(env), &dao_owner);
+
+    client.init(&dao.id, &dao.name, &dao_owner, &core_client.address, &0);
+    let minter = Address::random(env);
+    client.mint(&minter, &minter, &SUPPLY);
+}
+
+#[test]
+#[should_panic(expected = "#10")]
+fn mint_only_as_current_dao_owner() {
+    let (client, core_client, _) = create_all_clients();
+    let env = &client.env;
+    let issuer = create_token(&client, &core_client);
+    let new_owner = Address::random(env);
+    core_client.propose_owner(&"DIV".into_val(env), &new_owner, &100, &issuer);
+    core_client.accept_ownership(&"DIV".into_val(env), &new_owner);
+
+    client.mint(&new_owner, &new_owner, &SUPPLY);
+    assert_eq!(client.balance(&new_owner), SUPPLY);
+    client.mint(&issuer, &issuer, &SUPPLY);
+}
+
+#[test]
+fn total_supply_at() {
+    let (client, core_client, _) = create_all_clients();
+    assert_eq!(client.get_total_supply_at(&0), 0);
+
+    let owner = create_token(&client, &core_client);
+    client.xfer(&owner, &Address::random(&client.env), &100_000);
+
+    assert_eq!(client.get_total_supply_at(&0), SUPPLY);
+}
+
+#[test]
+fn delegate() {
+    let (client, core_client, _) = create_all_clients();
+    let owner = create_token(&client, &core_client);
+    let delegate = Address::random(&client.env);
+    assert_eq!(client.delegates(&owner), owner);
+    assert_eq!(client.get_votes_at(&owner, &0), SUPPLY);
+
+    client.env.ledger().set(LedgerInfo {
+        timestamp: 12345,
+        protocol_version: 20,
+        sequence_number: 10,
+        network_id: Default::default(),
+        base_reserve: 10,
+        min_temp_entry_expiration: 10,
+        min_persistent_entry_expiration: 10,
+        max_entry_expiration: 5_200_000,
+    });
+    client.delegate(&owner, &delegate);
+    assert_eq!(client.delegates(&owner), delegate);
+    assert_eq!(client.get_votes_at(&owner, &10), 0);
+    assert_eq!(client.get_votes_at(&delegate, &10), SUPPLY);
+    assert_eq!(client.get_votes_at(&delegate, &9), 0);
+
+    // votes follow the balance
+    client.xfer(&owner, &delegate, &100_000);
+    assert_eq!(client.get_votes_at(&delegate, &10), SUPPLY);
+
+    client.delegate(&owner, &owner);
+    assert_eq!(client.get_votes_at(&owner, &10), 900_000);
+    assert_eq!(client.get_votes_at(&delegate, &10), 100_000);
+    assert_eq!(client.balance(&delegate), 100_000);
+}
+
+#[test]
+#[should_panic(expected = "#8")]
+fn delegate_only_once() {
+    let (client, core_client, _) = create_all_clients();
+    let owner = create_token(&client, &core_client);
+    let delegate = Address::random(&client.env);
+
+    client.delegate(&owner, &delegate);
+    client.delegate(&owner, &delegate);
+}
+
+#[test]
+#[should_panic(expected = "#9")]
+fn delegate_only_before_decommission() {
+    let (client, core_client, _) = create_all_clients();
+    let owner = create_token(&client, &core_client);
+    client.decommission();
+
+    assert_eq!(client.get_votes_at(&owner, &client.env.ledger().sequence()), SUPPLY);
+    client.delegate(&owner, &Address::random(&client.env));
 }
 
 #[test]
@@ -99,7 +279,7 @@ fn set_owner() {
 }
 
 #[test]
-#[should_panic(expected = "#4")]
+#[should_panic(expected = "#10")]
 fn set_owner_auth() {
     let (client, core_client, ..) = create_all_clients();
     create_token(&client, &core_client);
@@ -120,7 +300,7 @@ fn set_core_address() {
 }
 
 #[test]
-#[should_panic(expected = "#4")]
+#[should_panic(expected = "#10")]
 fn set_core_address_auth() {
     let (client, core_client, ..) = create_all_clients();
     create_token(&client, &core_client);
@@ -128,22 +308,36 @@ fn set_core_address_auth() {
     client.set_core_address(&address, &client.address);
 }
 
+#[test]
+#[should_panic(expected = "#10")]
+fn set_core_address_only_as_current_dao_owner() {
+    let (client, core_client, votes_client) = create_all_clients();
+    let env = &client.env;
+    let owner = create_token(&client, &core_client);
+    let dao_id = "DIV".into_val(env);
+
+    // the DAO is handed to its governance, the previous owner can't rebind the core to mint
+    core_client.propose_owner(&dao_id, &votes_client.address, &(env.ledger().sequence() + 100), &owner);
+    votes_client.accept_dao_ownership(&dao_id);
+    client.set_core_address(&owner, &Address::random(env));
+}
+
 #[test]
 fn spendable_equals_balance() {
     let (client, ..) = create_all_clients();
     let address = Address::random(&client.env);
-    assert_eq!(client.balance(&address), client.spendable(&address));
+    assert_eq!(client.balance(&address), client.spendable_balance(&address));
 }
 
 #[test]
 fn token_assets_are_always_authorized() {
     let (client, ..) = create_all_clients();
     let address = Address::random(&client.env);
-    assert_eq!(client.authorized(&address), true);
+    assert!(client.authorized(&address));
 }
 
 #[test]
-fn xfer() {
+fn transfer() {
     let (client, core_client, ..) = create_all_clients();
     create_token(&client, &core_client);
     let from = client.owner();
@@ -155,20 +349,20 @@ fn xfer() {
     // budget reset
     client.env.budget().reset_default();
 
-    client.xfer(&from, &to, &500_000);
+    client.transfer(&from, &to, &500_000);
 
     assert_eq!(client.balance(&from), 500_000);
     assert_eq!(client.balance(&to), 500_000);
 }
 
 #[test]
-fn xfer_from() {
+fn transfer_from() {
     let (client, core_client, ..) = create_all_clients();
     create_token(&client, &core_client);
     let from = client.owner();
     let to = Address::random(&client.env);
     let spender = Address::random(&client.env);
-    client.incr_allow(&from, &spender, &250_000);
+    client.approve(&from, &spender, &250_000, &client.env.ledger().sequence());
 
     assert_eq!(client.balance(&from), 1_000_000);
     assert_eq!(client.balance(&to), 0);
@@ -177,7 +371,7 @@ fn xfer_from() {
     // budget reset
     client.env.budget().reset_default();
 
-    client.xfer_from(&spender, &from, &to, &100_000);
+    client.transfer_from(&spender, &from, &to, &100_000);
 
     assert_eq!(client.balance(&from), 900_000);
     assert_eq!(client.balance(&to), 100_000);
@@ -185,150 +379,247 @@ fn xfer_from() {
 }
 
 #[test]
-fn checkpoints() {
-    let (client, core_client, votes_client) = create_all_clients();
+fn approve_replaces_the_allowance() {
+    let (client, core_client, ..) = create_all_clients();
+    let owner = create_token(&client, &core_client);
+    let spender = Address::random(&client.env);
+    let sequence = client.env.ledger().sequence();
+
+    client.approve(&owner, &spender, &250_000, &sequence);
+    client.approve(&owner, &spender, &100_000, &sequence);
+    assert_eq!(client.allowance(&owner, &spender), 100_000);
+
+    // an allowance can be removed with an expiration ledger in the past
+    client.env.ledger().with_mut(|ledger| ledger.sequence_number = sequence + 10);
+    client.approve(&owner, &spender, &0, &sequence);
+    assert_eq!(client.allowance(&owner, &spender), 0);
+}
 
+#[test]
+fn allowance_expires() {
+    let (client, core_client, ..) = create_all_clients();
     let owner = create_token(&client, &core_client);
-    let whoever = Address::random(&client.env);
+    let spender = Address::random(&client.env);
+    let sequence = client.env.ledger().sequence();
 
-    client.env.budget().reset_unlimited();
+    client.approve(&owner, &spender, &250_000, &(sequence + 20));
+    let allowance = client.get_allowance(&owner, &spender);
+    assert_eq!(allowance.amount, 250_000);
+    assert_eq!(allowance.expiration_ledger, sequence + 20);
 
-    // owner should have a checkpoint after issuance
-    assert_eq!(client.get_checkpoint_count(&owner), 1);
-    assert_eq!(client.get_checkpoint_count(&whoever), 0);
+    client.env.ledger().with_mut(|ledger| ledger.sequence_number = sequence + 20);
+    assert_eq!(client.allowance(&owner, &spender), 250_000);
 
-    let cp = client.get_checkpoint_at(&owner, &0);
+    client.env.ledger().with_mut(|ledger| ledger.sequence_number = sequence + 21);
+    assert_eq!(client.allowance(&owner, &spender), 0);
+    assert_eq!(client.get_allowance(&owner, &spender).expiration_ledger, sequence + 20);
+}
 
-    assert_eq!(cp.ledger, 0);
-    assert_eq!(cp.balance, 1_000_000);
+#[test]
+fn allowance_of_version_0_is_kept() {
+    let (client, core_client, ..) = create_all_clients();
+    let env = &client.env;
+    let owner = create_token(&client, &core_client);
+    let spender = Address::random(env);
 
-    client.env.ledger().set(LedgerInfo {
-        timestamp: 12345,
-        protocol_version: 20,
-        sequence_number: 1,
-        network_id: Default::default(),
-        base_reserve: 10,
-        min_temp_entry_expiration: 10,
-        min_persistent_entry_expiration: 10,
-        max_entry_expiration: 5_200_000,
-    });
+    // version 0 kept allowances in the persistent storage without an expiration
+    let key = Token::Allowance(Allowances { from: owner.clone(), spender: spender.clone() });
+    env.as_contract(&client.address, || env.storage().persistent().set(&key, &250_000_i128));
 
-    client.xfer(&owner, &whoever, &100_000);
+    assert_eq!(client.allowance(&owner, &spender), 250_000);
+    client.transfer_from(&spender, &owner, &Address::random(env), &100_000);
+    assert_eq!(client.allowance(&owner, &spender), 150_000);
+    let legacy = env.as_contract(&client.address, || env.storage().persistent().has(&key));
+    assert!(!legacy);
+}
 
-    // Since there is no proposal, the owners first cp should be overridden
-    assert_eq!(client.get_checkpoint_count(&owner), 1);
-    assert_eq!(client.get_checkpoint_count(&whoever), 1);
+#[test]
+fn approve_beyond_the_max_ttl() {
+    let (client, core_client, ..) = create_all_clients();
+    let owner = create_token(&client, &core_client);
+    let spender = Address::random(&client.env);
+    client.env.ledger().with_mut(|ledger| ledger.max_entry_expiration = MAX_TTL + 1);
+    let expiration_ledger = client.env.ledger().sequence() + MAX_TTL + 1_000;
 
-    let cp2 = client.get_checkpoint_at(&owner, &0);
+    client.approve(&owner, &spender, &250_000, &expiration_ledger);
+    assert_eq!(client.get_allowance(&owner, &spender).expiration_ledger, expiration_ledger);
+}
 
-    assert_eq!(cp2.ledger, 1);
-    assert_eq!(cp2.balance, 900_000);
+#[test]
+#[should_panic(expected = "#2")]
+fn transfer_from_only_before_the_allowance_expires() {
+    let (client, core_client, ..) = create_all_clients();
+    let from = create_token(&client, &core_client);
+    let spender = Address::random(&client.env);
+    let sequence = client.env.ledger().sequence();
+    client.approve(&from, &spender, &250_000, &(sequence + 20));
 
-    let cp3 = client.get_checkpoint_at(&whoever, &0);
+    client.env.ledger().with_mut(|ledger| ledger.sequence_number = sequence + 21);
+    client.transfer_from(&spender, &from, &Address::random(&client.env), &100_000);
+}
 
-    assert_eq!(cp3.ledger, 1);
-    assert_eq!(cp3.balance, 100_000);
+#[test]
+#[should_panic(expected = "#14")]
+fn approve_only_until_a_future_ledger() {
+    let (client, core_client, ..) = create_all_clients();
+    let owner = create_token(&client, &core_client);
+    client.env.ledger().with_mut(|ledger| ledger.sequence_number = 100);
 
-    client.env.ledger().set(LedgerInfo {
-        timestamp: 12345,
-        protocol_version: 20,
-        sequence_number: 10,
-        network_id: Default::default(),
-        base_reserve: 10,
-        min_temp_entry_expiration: 10,
-        min_persistent_entry_expiration: 10,
-        max_entry_expiration: 5_200_000,
-    });
+    client.approve(&owner, &Address::random(&client.env), &100_000, &99);
+}
 
-    let dao_id = "DIV".into_val(&client.env);
-    let dao_name = "Deep Ink Ventures".into_val(&client.env);
-    let proposal_duration: u32 = 10_000;
-    let min_threshold_configuration: i128 = 1_000;
+#[test]
+fn deprecated_aliases() {
+    let (client, core_client, ..) = create_all_clients();
+    let from = create_token(&client, &core_client);
+    let to = Address::random(&client.env);
+    let spender = Address::random(&client.env);
 
-    fund_account(&client.env, &core_client.get_native_asset_id(), &owner);
-    core_client.create_dao(&dao_id, &dao_name, &owner);
-    votes_client.set_configuration(
-        &dao_id,
-        &proposal_duration,
-        &min_threshold_configuration,
-        &owner
-    );
+    client.xfer(&from, &to, &100_000);
+    client.incr_allow(&from, &spender, &250_000);
+    client.decr_allow(&from, &spender, &50_000);
+    client.xfer_from(&spender, &from, &to, &100_000);
 
-    let proposal_owner_1 = Address::random(&client.env);
-    fund_account(&client.env, &core_client.get_native_asset_id(), &proposal_owner_1);
+    assert_eq!(client.spendable(&to), 200_000);
+    assert_eq!(client.allowance(&from, &spender), 100_000);
+}
 
-    // let's create a proposal
-    votes_client.create_proposal(&dao_id, &proposal_owner_1);
-    client.xfer(&owner, &whoever, &100_000);
+#[test]
+fn burn() {
+    let (client, core_client, ..) = create_all_clients();
+    let owner = create_token(&client, &core_client);
+    let sequence = client.env.ledger().sequence();
 
-    assert_eq!(client.get_checkpoint_count(&owner), 2);
-    assert_eq!(client.get_checkpoint_count(&whoever), 2);
+    client.burn(&owner, &400_000);
 
-    let cp2 = client.get_checkpoint_at(&owner, &1);
+    assert_eq!(client.balance(&owner), 600_000);
+    assert_eq!(client.get_total_supply_at(&sequence), 600_000);
+    assert_eq!(client.get_votes_at(&owner, &sequence), 600_000);
+}
 
-    assert_eq!(cp2.ledger, 10);
-    assert_eq!(cp2.balance, 800_000);
+#[test]
+fn burn_from() {
+    let (client, core_client, ..) = create_all_clients();
+    let owner = create_token(&client, &core_client);
+    let spender = Address::random(&client.env);
+    client.incr_allow(&owner, &spender, &250_000);
 
-    let cp3 = client.get_checkpoint_at(&whoever, &1);
+    client.burn_from(&spender, &owner, &100_000);
 
-    assert_eq!(cp3.ledger, 10);
-    assert_eq!(cp3.balance, 200_000);
+    assert_eq!(client.balance(&owner), 900_000);
+    assert_eq!(client.allowance(&owner, &spender), 150_000);
+    assert_eq!(client.get_total_supply_at(&client.env.ledger().sequence()), 900_000);
+}
 
-    // a second one
-    client.env.ledger().set(LedgerInfo {
-        timestamp: 12345,
-        protocol_version: 20,
-        sequence_number: 20,
-        network_id: Default::default(),
-        base_reserve: 10,
-        min_temp_entry_expiration: 10,
-        min_persistent_entry_expiration: 10,
-        max_entry_expiration: 5_200_000,
-    });
-    let proposal_owner_2 = Address::random(&client.env);
-    fund_account(&client.env, &core_client.get_native_asset_id(), &proposal_owner_2);
-    votes_client.create_proposal(&dao_id, &proposal_owner_2);
-    client.xfer(&owner, &whoever, &100_000);
+#[test]
+#[should_panic(expected = "#2")]
+fn burn_from_only_within_allowance() {
+    let (client, core_client, ..) = create_all_clients();
+    let owner = create_token(&client, &core_client);
+    let spender = Address::random(&client.env);
+    client.incr_allow(&owner, &spender, &100);
 
-    assert_eq!(client.get_checkpoint_count(&owner), 3);
-    assert_eq!(client.get_checkpoint_count(&whoever), 3);
+    client.burn_from(&spender, &owner, &101);
+}
 
-    let cp2 = client.get_checkpoint_at(&owner, &2);
+#[test]
+#[should_panic(expected = "#6")]
+fn burn_only_the_balance() {
+    let (client, core_client, ..) = create_all_clients();
+    let owner = create_token(&client, &core_client);
 
-    assert_eq!(cp2.ledger, 20);
-    assert_eq!(cp2.balance, 700_000);
+    client.burn(&owner, &(SUPPLY + 1));
+}
 
-    let cp3 = client.get_checkpoint_at(&whoever, &2);
+#[test]
+fn checkpoints() {
+    let (client, core_client, _) = create_all_clients();
 
-    assert_eq!(cp3.ledger, 20);
-    assert_eq!(cp3.balance, 300_000);
+    let owner = create_token(&client, &core_client);
+    let whoever = Address::random(&client.env);
+    client.set_checkpoint_retention(&owner, &10);
+    assert_eq!(client.checkpoint_retention(), 10);
 
-    client.env.budget().reset_default();
+    // owner should have a checkpoint after issuance
+    assert_eq!(client.get_checkpoint_count(&owner), 1);
+    assert_eq!(client.get_checkpoint_count(&whoever), 0);
 
-    // now let's outdate the first one
-    client.env.ledger().set(LedgerInfo {
-        timestamp: 12345,
-        protocol_version: 20,
-        sequence_number: 10 + proposal_duration + 1,
-        network_id: Default::default(),
-        base_reserve: 10,
-        min_temp_entry_expiration: 10,
-        min_persistent_entry_expiration: 10,
-        max_entry_expiration: 5_200_000,
-    });
-    client.xfer(&owner, &whoever, &100_000);
+    let cp = client.get_checkpoint_at(&owner, &0);
+
+    assert_eq!(cp.ledger, 0);
+    assert_eq!(cp.balance, 1_000_000);
+
+    client.env.ledger().with_mut(|ledger| ledger.sequence_number = 1);
+    client.transfer(&owner, &whoever, &100_000);
+    // the last write of a ledger replaces its checkpoint
+    client.transfer(&owner, &whoever, &100_000);
+
+    assert_eq!(client.get_checkpoint_count(&owner), 2);
+    assert_eq!(client.get_checkpoint_count(&whoever), 1);
+
+    let cp2 = client.get_checkpoint_at(&owner, &1);
+
+    assert_eq!(cp2.ledger, 1);
+    assert_eq!(cp2.balance, 800_000);
+
+    // the checkpoint at ledger 0 is before the window, the one at the start of it is kept
+    client.env.ledger().with_mut(|ledger| ledger.sequence_number = 20);
+    client.transfer(&owner, &whoever, &100_000);
 
     assert_eq!(client.get_checkpoint_count(&owner), 2);
     assert_eq!(client.get_checkpoint_count(&whoever), 2);
+    assert_eq!(client.get_checkpoint_at(&owner, &0).ledger, 1);
+
+    client.env.ledger().with_mut(|ledger| ledger.sequence_number = 25);
+    client.transfer(&owner, &whoever, &100_000);
 
-    // let's verify we are getting the correct checkpoints for each proposal!
-    assert_eq!(client.get_balance_at(&owner, &19), 700_000);
+    assert_eq!(client.get_checkpoint_count(&owner), 3);
+    assert_eq!(client.get_checkpoint_count(&whoever), 3);
+
+    // let's verify we are getting the correct checkpoints within the window
+    assert_eq!(client.get_balance_at(&owner, &19), 800_000);
     assert_eq!(client.get_balance_at(&owner, &20), 700_000);
+    assert_eq!(client.get_balance_at(&owner, &24), 700_000);
     assert_eq!(client.get_balance_at(&owner, &20_000), 600_000);
+    assert_eq!(client.get_votes_at(&owner, &19), 800_000);
 
-    assert_eq!(client.get_balance_at(&whoever, &19), 300_000);
+    assert_eq!(client.get_balance_at(&whoever, &19), 200_000);
     assert_eq!(client.get_balance_at(&whoever, &20), 300_000);
     assert_eq!(client.get_balance_at(&whoever, &20_000), 400_000);
+    assert_eq!(client.get_total_supply_at(&20_000), 1_000_000);
+}
+
+#[test]
+#[should_panic(expected = "#10")]
+fn set_checkpoint_retention_only_as_dao_owner() {
+    let (client, core_client, _) = create_all_clients();
+    create_token(&client, &core_client);
+
+    client.set_checkpoint_retention(&Address::random(&client.env), &10);
+}
+
+#[test]
+#[should_panic(expected = "#16")]
+fn set_checkpoint_retention_above_zero() {
+    let (client, core_client, _) = create_all_clients();
+    let owner = create_token(&client, &core_client);
+
+    client.set_checkpoint_retention(&owner, &0);
+}
+
+#[test]
+#[should_panic(expected = "#16")]
+fn set_checkpoint_retention_covers_checkpoint_window() {
+    let (client, core_client, votes_client) = create_all_clients();
+    let env = &client.env;
+    let owner = create_token(&client, &core_client);
+    let dao_id = "DIV".into_val(env);
+    votes_client.set_configuration(&dao_id, &10_000, &1_000, &owner);
+    let checkpoint_window = votes_client.get_checkpoint_window(&dao_id);
+    client.set_checkpoint_retention(&owner, &checkpoint_window);
+    assert_eq!(client.checkpoint_retention(), checkpoint_window);
+
+    client.set_checkpoint_retention(&owner, &(checkpoint_window - 1));
 }
 
 #[test]
@@ -346,4 +637,63 @@ fn checkpoint_at_fails_when_out_of_bounds() {
     let owner = create_token(&client, &core_client);
 
     client.get_checkpoint_at(&owner, &2);
-}
\ No newline at end of file
+}
+
+#[test]
+#[should_panic(expected = "#10")]
+fn upgrade_only_as_dao_owner() {
+    let (client, core_client, _) = create_all_clients();
+    let env = &client.env;
+    let dao_owner = Address::random(env);
+    fund_account(env, &core_client.get_native_asset_id(), &dao_owner);
+    let dao = core_client.create_dao(&"DIV".into_val(env), &"Deep Ink Ventures".into_val(env), &dao_owner);
+    client.init(&dao.id, &dao.name, &dao_owner, &core_client.address, &0);
+
+    let wasm_hash = env.deployer().upload_contract_wasm(votes_contract::WASM);
+    client.upgrade(&wasm_hash, &Address::random(env));
+}
+
+#[test]
+#[should_panic(expected = "#11")]
+fn migrate_when_up_to_date() {
+    let (client, core_client, _) = create_all_clients();
+    let env = &client.env;
+    let dao_owner = Address::random(env);
+    fund_account(env, &core_client.get_native_asset_id(), &dao_owner);
+    let dao = core_client.create_dao(&"DIV".into_val(env), &"Deep Ink Ventures".into_val(env), &dao_owner);
+    client.init(&dao.id, &dao.name, &dao_owner, &core_client.address, &0);
+
+    assert_eq!(client.version(), 1);
+    client.migrate(&dao_owner, &0);
+}
+
+#[test]
+fn migrate_from_version_0() {
+    let (client, core_client, _) = create_all_clients();
+    let env = &client.env;
+    let owner = create_token(&client, &core_client);
+    let holder = Address::random(env);
+    client.xfer(&owner, &holder, &100_000);
+
+    // version 0 kept neither a version nor the checkpoints of the votes and the supply
+    env.as_contract(&client.address, || {
+        env.storage().instance().remove(&Token::Version);
+        env.storage().persistent().remove(&Token::SupplyCheckpoints);
+        env.storage().persistent().remove(&Token::VoteCheckpoints(owner.clone()));
+        env.storage().persistent().remove(&Token::VoteCheckpoints(holder.clone()));
+    });
+    assert_eq!(client.version(), 0);
+    assert_eq!(client.get_votes_at(&owner, &0), 900_000);
+    assert_eq!(client.get_votes_at(&holder, &0), 100_000);
+
+    assert_eq!(client.migrate(&owner, &SUPPLY), 1);
+    assert_eq!(client.version(), 1);
+    assert_eq!(client.get_total_supply_at(&0), SUPPLY);
+
+    env.ledger().with_mut(|ledger| ledger.sequence_number = 10);
+    client.xfer(&owner, &holder, &100_000);
+    client.delegate(&holder, &owner);
+    assert_eq!(client.get_votes_at(&owner, &0), 900_000);
+    assert_eq!(client.get_votes_at(&owner, &10), SUPPLY);
+    assert_eq!(client.get_votes_at(&holder, &10), 0);
+}
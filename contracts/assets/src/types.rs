@@ -1,7 +1,8 @@
-use soroban_sdk::{contracttype, panic_with_error, Address, Bytes, Env, Vec};
+use soroban_sdk::{contracttype, panic_with_error, Address, Bytes, Env, String, Vec};
 
 use crate::error::AssetError;
-use crate::{core_contract, votes_contract};
+use crate::core_contract;
+use crate::votes_contract;
 
 #[derive(Clone)]
 #[contracttype]
@@ -20,6 +21,25 @@ pub enum Token {
     Owner,
     CoreAddress,
     Checkpoints(Address),
+    SupplyCheckpoints,
+    Delegate(Address),
+    VoteCheckpoints(Address),
+    ProtocolConfig,
+    Version,
+    MaxSupply,
+    MintLimit,
+    MintPeriod,
+    CheckpointRetention,
+    /// Set once the DAO of the token is destroyed
+    Decommissioned,
+}
+
+/// An allowance reads as 0 after its expiration ledger
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowanceValue {
+    pub amount: i128,
+    pub expiration_ledger: u32,
 }
 
 #[contracttype]
@@ -29,19 +49,53 @@ pub struct Checkpoint {
     pub balance: i128,
 }
 
+/// Limits the amount minted within each period of `period` ledgers, a period of 0 disables it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MintLimit {
+    pub period: u32,
+    pub amount: i128,
+}
+
+/// The amount minted in the period that starts at `start`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct MintPeriod {
+    start: u32,
+    minted: i128,
+}
+
+/// The layout of the storage. Version 0 is the layout before versioning, it kept the balance
+/// checkpoints only: the votes of an account that didn't delegate are its balance checkpoints
+/// until its votes change, the supply checkpoints are written by `migrate`.
+pub const STORAGE_VERSION: u32 = 1;
+
+/// The max length of the name and symbol returned as `String`, the max length of the id and the
+/// name of a DAO in the core
+pub const MAX_STRING_LENGTH: usize = 256;
+
 pub const A_WEEK_IN_LEDGERS: u32 = 100800;
+/// The ledgers checkpoints are kept for unless the owner sets a different retention window
+pub const DEFAULT_CHECKPOINT_RETENTION: u32 = 432000;
 pub const BUMP_A_MONTH: u32 = 432000;
 pub const BUMP_A_MONTH_THRESHOLD: u32 = 432000 - A_WEEK_IN_LEDGERS;
+/// The max ledgers an entry can be bumped by, the max entry expiration of the network (180 days)
+/// includes the current ledger
+pub const MAX_TTL: u32 = 3_110_400 - 1;
 
 impl Token {
     pub fn get_checkpoints(env: &Env, id: Address) -> Vec<Checkpoint> {
-        let key = Token::Checkpoints(id);
+        Self::read_checkpoints(env, Token::Checkpoints(id))
+    }
+
+    fn read_checkpoints(env: &Env, key: Token) -> Vec<Checkpoint> {
+        let (bump_threshold, bump_ledgers) = Token::get_bump(env);
         if !env.storage().persistent().has(&key) {
             return Vec::new(env);
         }
         env.storage()
             .persistent()
-            .bump(&key, BUMP_A_MONTH_THRESHOLD, BUMP_A_MONTH);
+            .bump(&key, bump_threshold, bump_ledgers);
         env.storage().persistent().get(&key).unwrap()
     }
 
@@ -62,21 +116,31 @@ impl Token {
         id: Address,
         sequence: u32,
     ) -> Option<Checkpoint> {
-        let checkpoints = Token::get_checkpoints(env, id);
-        let cp_candidate = checkpoints.first();
+        Self::find_checkpoint(Token::get_checkpoints(env, id), sequence)
+    }
 
-        cp_candidate.as_ref()?;
+    /// Returns the closest checkpoint at or BEFORE a given sequence, or the oldest one if there is
+    /// none that early
+    fn find_checkpoint(checkpoints: Vec<Checkpoint>, sequence: u32) -> Option<Checkpoint> {
+        match Self::find_checkpoint_index(&checkpoints, sequence) {
+            Some(i) => Some(checkpoints.get_unchecked(i)),
+            None => checkpoints.first(),
+        }
+    }
 
-        let mut cp = cp_candidate.unwrap();
-        for checkpoint in checkpoints.into_iter() {
-            if checkpoint.ledger > sequence {
-                break;
-            }
-            if checkpoint.ledger > cp.ledger {
-                cp = checkpoint
+    /// Binary search for the index of the last checkpoint at or before `sequence`, the
+    /// checkpoints are sorted by their ledger as they are only ever appended
+    fn find_checkpoint_index(checkpoints: &Vec<Checkpoint>, sequence: u32) -> Option<u32> {
+        let (mut low, mut high) = (0, checkpoints.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if checkpoints.get_unchecked(mid).ledger <= sequence {
+                low = mid + 1;
+            } else {
+                high = mid;
             }
         }
-        Some(cp)
+        low.checked_sub(1)
     }
 
     /// Writes a checkpoint for a given balance at the current sequence number
@@ -87,65 +151,231 @@ impl Token {
     /// If you roll your own implementation you need to have a strategy to keep the number of
     /// checkpoints bounded, otherwise the required storage will escalate on busy tokens.
     ///
-    /// Our strategy is to set the maximum number of concurrently active proposals to 25;
-    /// too many active proposals creates voter fatigue anyway.
+    /// Our strategy is a retention window: checkpoints older than the window are pruned on write,
+    /// so the history holds at most one checkpoint per ledger of the window. The window has to
+    /// cover the proposal duration of the DAO.
     pub fn write_checkpoint(env: &Env, id: Address) {
-        let key = Self::Checkpoints(id.clone());
+        let checkpoints = Self::get_checkpoints(env, id.clone());
+        let balance = Token::read_balance(env, id.clone());
+        Self::write_checkpoints(env, Self::Checkpoints(id), checkpoints, balance);
+    }
+
+    /// The ledgers checkpoints are kept for
+    pub fn get_checkpoint_retention(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&Token::CheckpointRetention)
+            .unwrap_or(DEFAULT_CHECKPOINT_RETENTION)
+    }
+
+    /// The retention has to cover the checkpoint window of the DAO in the votes contract, so
+    /// proposals can read the voting power of their creation ledger until they are executed
+    pub fn set_checkpoint_retention(env: &Env, dao_owner: &Address, ledgers: u32) {
+        Token::check_dao_owner(env, dao_owner);
+        let core = core_contract::Client::new(env, &Token::get_core_address(env));
+        let votes = votes_contract::Client::new(env, &core.get_votes_id());
+        if ledgers == 0 || ledgers < votes.get_checkpoint_window(&Token::get_symbol(env)) {
+            panic_with_error!(env, AssetError::InvalidCheckpointRetention)
+        }
+        env.storage().instance().set(&Token::CheckpointRetention, &ledgers);
+    }
+
+    /// Reads the protocol config through the core and keeps it to bump the storage without
+    /// calling the core
+    pub fn sync_protocol_config(env: &Env) {
+        let core_contract = core_contract::Client::new(env, &Self::get_core_address(env));
+        env.storage()
+            .instance()
+            .set(&Token::ProtocolConfig, &core_contract.get_protocol_config());
+    }
 
-        let core_address = Self::get_core_address(env);
+    /// The bump threshold and ledgers of the protocol config as of the last mint
+    pub fn get_bump(env: &Env) -> (u32, u32) {
+        match env.storage().instance().get::<_, core_contract::ProtocolConfig>(&Token::ProtocolConfig) {
+            Some(config) => (config.bump_threshold, config.bump_ledgers),
+            None => (BUMP_A_MONTH_THRESHOLD, BUMP_A_MONTH),
+        }
+    }
 
-        let core_contract = core_contract::Client::new(env, &core_address);
-        let vote_id = core_contract.get_votes_id();
-        let votes_contract = votes_contract::Client::new(env, &vote_id);
+    pub fn get_supply_checkpoints(env: &Env) -> Vec<Checkpoint> {
+        Self::read_checkpoints(env, Token::SupplyCheckpoints)
+    }
 
-        let active_proposals = votes_contract.get_active_proposals(&Self::get_symbol(env));
+    /// Returns the total supply at or BEFORE a given sequence
+    pub fn get_supply_for_sequence(env: &Env, sequence: u32) -> i128 {
+        match Self::find_checkpoint(Self::get_supply_checkpoints(env), sequence) {
+            Some(cp) => cp.balance,
+            None => 0,
+        }
+    }
 
-        let mut filtered_checkpoints: Vec<Checkpoint> = Vec::new(env);
-        for proposal in active_proposals.into_iter() {
-            let checkpoint =
-                Self::get_checkpoint_for_sequence(env, id.clone(), proposal.inner.ledger);
+    pub fn read_total_supply(env: &Env) -> i128 {
+        match Self::get_supply_checkpoints(env).last() {
+            Some(cp) => cp.balance,
+            None => 0,
+        }
+    }
+
+    /// Writes a checkpoint of the total supply, e.g. to compute majorities of the supply
+    pub fn write_total_supply(env: &Env, supply: i128) {
+        let checkpoints = Self::get_supply_checkpoints(env);
+        Self::write_checkpoints(env, Self::SupplyCheckpoints, checkpoints, supply);
+    }
 
-            if let Some(cp) = checkpoint {
-                filtered_checkpoints.push_back(cp);
+    /// Returns the account the votes of `addr` are delegated to, which is `addr` itself by default
+    pub fn read_delegate(env: &Env, addr: Address) -> Address {
+        let (bump_threshold, bump_ledgers) = Token::get_bump(env);
+        let key = Token::Delegate(addr.clone());
+        match env.storage().persistent().get(&key) {
+            Some(delegate) => {
+                env.storage()
+                    .persistent()
+                    .bump(&key, bump_threshold, bump_ledgers);
+                delegate
             }
+            None => addr,
+        }
+    }
+
+    /// Moves the votes of `addr` from its current delegate to `delegate`
+    pub fn delegate(env: &Env, addr: Address, delegate: Address) {
+        let (bump_threshold, bump_ledgers) = Token::get_bump(env);
+        let key = Token::Delegate(addr.clone());
+        let balance = Token::read_balance(env, addr.clone());
+        Token::add_votes(env, Token::read_delegate(env, addr.clone()), -balance);
+        Token::add_votes(env, delegate.clone(), balance);
+
+        if addr == delegate {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &delegate);
+            env.storage()
+                .persistent()
+                .bump(&key, bump_threshold, bump_ledgers);
+        }
+    }
+
+    /// Returns the votes delegated to `addr` at or BEFORE a given sequence
+    pub fn get_votes_for_sequence(env: &Env, addr: Address, sequence: u32) -> i128 {
+        // votes can be delegated to an account after a proposal was created, so a later checkpoint
+        // must not stand in for the missing one
+        match Self::find_checkpoint(Self::read_vote_checkpoints(env, addr), sequence) {
+            Some(cp) if cp.ledger <= sequence => cp.balance,
+            _ => 0,
+        }
+    }
+
+    pub fn read_votes(env: &Env, addr: Address) -> i128 {
+        match Self::read_vote_checkpoints(env, addr).last() {
+            Some(cp) => cp.balance,
+            None => 0,
+        }
+    }
+
+    /// Writes a checkpoint of the votes delegated to `addr`, changed by `amount`
+    fn add_votes(env: &Env, addr: Address, amount: i128) {
+        if amount == 0 {
+            return;
+        }
+        let checkpoints = Self::read_vote_checkpoints(env, addr.clone());
+        let votes = checkpoints.last().map_or(0, |cp| cp.balance) + amount;
+        Self::write_checkpoints(env, Token::VoteCheckpoints(addr), checkpoints, votes);
+    }
+
+    /// The checkpoints of the votes delegated to `addr`, version 0 kept none, so an account
+    /// without them that didn't delegate votes with its balance checkpoints
+    fn read_vote_checkpoints(env: &Env, addr: Address) -> Vec<Checkpoint> {
+        let key = Token::VoteCheckpoints(addr.clone());
+        if !env.storage().persistent().has(&key) && Token::read_delegate(env, addr.clone()) == addr {
+            return Self::get_checkpoints(env, addr);
+        }
+        Self::read_checkpoints(env, key)
+    }
+
+    /// Appends the new checkpoint and prunes the ones before the retention window
+    ///
+    /// The checkpoint at the start of the window is kept, so lookups within the window stay exact.
+    fn write_checkpoints(env: &Env, key: Token, mut checkpoints: Vec<Checkpoint>, balance: i128) {
+        let (bump_threshold, bump_ledgers) = Token::get_bump(env);
+        let sequence = env.ledger().sequence();
+
+        // the last write of a ledger replaces its checkpoint
+        if checkpoints.last().map_or(false, |cp| cp.ledger == sequence) {
+            checkpoints.pop_back();
+        }
+        let window_start = sequence.saturating_sub(Self::get_checkpoint_retention(env));
+        if let Some(i) = Self::find_checkpoint_index(&checkpoints, window_start) {
+            checkpoints = checkpoints.slice(i..);
         }
 
-        filtered_checkpoints.push_back(Checkpoint {
-            balance: Token::read_balance(env, id),
-            ledger: env.ledger().sequence(),
+        checkpoints.push_back(Checkpoint {
+            balance,
+            ledger: sequence,
         });
-        env.storage().persistent().set(&key, &filtered_checkpoints);
+        env.storage().persistent().set(&key, &checkpoints);
         env.storage()
             .persistent()
-            .bump(&key, BUMP_A_MONTH_THRESHOLD, BUMP_A_MONTH);
+            .bump(&key, bump_threshold, bump_ledgers);
     }
 
-    pub fn read_allowance(env: &Env, from: Address, spender: Address) -> i128 {
-        let key = Self::Allowance(Allowances { from, spender });
+    /// Allowances live in the temporary storage until their expiration ledger and aren't bumped
+    /// on reads, an expired allowance has an amount of 0
+    pub fn read_allowance(env: &Env, from: Address, spender: Address) -> AllowanceValue {
+        let key = Self::Allowance(Allowances { from: from.clone(), spender: spender.clone() });
+        match env.storage().temporary().get::<Token, AllowanceValue>(&key) {
+            Some(allowance) if allowance.expiration_ledger >= env.ledger().sequence() => allowance,
+            Some(allowance) => AllowanceValue {
+                amount: 0,
+                expiration_ledger: allowance.expiration_ledger,
+            },
+            None => Self::take_legacy_allowance(env, from, spender),
+        }
+    }
 
-        let allowance = env.storage().persistent().get(&key).unwrap_or(0);
-        if allowance > 0 {
-            env.storage()
-                .persistent()
-                .bump(&key, BUMP_A_MONTH_THRESHOLD, BUMP_A_MONTH);
+    /// Moves an allowance of version 0, a persistent amount without expiration, to the temporary
+    /// storage with the default expiration
+    fn take_legacy_allowance(env: &Env, from: Address, spender: Address) -> AllowanceValue {
+        let key = Self::Allowance(Allowances { from: from.clone(), spender: spender.clone() });
+        match env.storage().persistent().get::<Token, i128>(&key) {
+            Some(amount) => {
+                env.storage().persistent().remove(&key);
+                let expiration_ledger = Self::default_allowance_expiration(env);
+                Self::write_allowance(env, from, spender, amount, expiration_ledger);
+                AllowanceValue { amount, expiration_ledger }
+            }
+            None => AllowanceValue {
+                amount: 0,
+                expiration_ledger: 0,
+            },
         }
-        allowance
     }
 
-    pub fn write_allowance(env: &Env, from: Address, spender: Address, amount: i128) {
+    pub fn write_allowance(env: &Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
+        let sequence = env.ledger().sequence();
+        if amount > 0 && expiration_ledger < sequence {
+            panic_with_error!(env, AssetError::InvalidExpirationLedger)
+        }
         let key = Self::Allowance(Allowances { from, spender });
-        env.storage().persistent().set(&key, &amount);
-        env.storage()
-            .persistent()
-            .bump(&key, BUMP_A_MONTH_THRESHOLD, BUMP_A_MONTH);
+        env.storage().temporary().set(&key, &AllowanceValue { amount, expiration_ledger });
+        if amount > 0 {
+            // an allowance expiring after the max TTL is dropped with its entry
+            let live_for = (expiration_ledger - sequence).min(MAX_TTL);
+            env.storage().temporary().bump(&key, live_for, live_for);
+        }
+    }
+
+    /// The expiration of allowances set without one, e.g. through `incr_allow`
+    pub fn default_allowance_expiration(env: &Env) -> u32 {
+        let (_, bump_ledgers) = Token::get_bump(env);
+        env.ledger().sequence() + bump_ledgers
     }
 
     pub fn spend_allowance(env: &Env, from: Address, spender: Address, amount: i128) {
         let allowance = Self::read_allowance(env, from.clone(), spender.clone());
-        if allowance < amount {
+        if allowance.amount < amount {
             panic_with_error!(env, AssetError::InsufficientAllowance)
         }
-        Self::write_allowance(env, from, spender, allowance - amount);
+        Self::write_allowance(env, from, spender, allowance.amount - amount, allowance.expiration_ledger);
     }
 
     pub fn get_symbol(env: &Env) -> Bytes {
@@ -156,12 +386,29 @@ impl Token {
         env.storage().instance().get(&Token::Name).unwrap()
     }
 
+    /// Converts the bytes of the name or symbol, which have to be valid UTF-8
+    pub fn to_string(env: &Env, bytes: &Bytes) -> String {
+        let len = bytes.len() as usize;
+        if len > MAX_STRING_LENGTH {
+            panic_with_error!(env, AssetError::InvalidString)
+        }
+        let mut buffer = [0u8; MAX_STRING_LENGTH];
+        bytes.copy_into_slice(&mut buffer[..len]);
+        match core::str::from_utf8(&buffer[..len]) {
+            Ok(string) => String::from_slice(env, string),
+            Err(_) => panic_with_error!(env, AssetError::InvalidString),
+        }
+    }
+
     pub fn get_owner(env: &Env) -> Address {
         env.storage().instance().get(&Token::Owner).unwrap()
     }
 
     pub fn get_core_address(env: &Env) -> Address {
-        env.storage().instance().get(&Token::CoreAddress).unwrap()
+        match env.storage().instance().get(&Token::CoreAddress) {
+            Some(core_address) => core_address,
+            None => panic_with_error!(env, AssetError::Decommissioned),
+        }
     }
 
     /// Create a new token
@@ -171,65 +418,166 @@ impl Token {
         name: &Bytes,
         owner: &Address,
         core_address: &Address,
+        max_supply: i128,
     ) {
+        let (bump_threshold, bump_ledgers) = Token::get_bump(env);
         if env.storage().instance().has(&Token::Symbol) {
             panic_with_error!(env, AssetError::DaoAlreadyIssuedToken)
         }
+        if max_supply < 0 {
+            panic_with_error!(env, AssetError::NegativeAmount)
+        }
+        if symbol.len() as usize > MAX_STRING_LENGTH || name.len() as usize > MAX_STRING_LENGTH {
+            panic_with_error!(env, AssetError::InvalidString)
+        }
         env.storage().instance().set(&Token::Symbol, symbol);
         env.storage().instance().set(&Token::Name, name);
         env.storage().instance().set(&Token::Owner, owner);
         env.storage()
             .instance()
             .set(&Token::CoreAddress, core_address);
+        env.storage().instance().set(&Token::MaxSupply, &max_supply);
+        Token::write_version(env);
 
         env.storage()
             .instance()
-            .bump(BUMP_A_MONTH_THRESHOLD, BUMP_A_MONTH);
+            .bump(bump_threshold, bump_ledgers);
     }
 
+    /// The owner functions follow the DAO owner in the core, the stored owner is informational
     pub fn set_owner(env: &Env, owner: &Address, new_owner: &Address) {
-        Token::check_auth(env, owner);
+        Token::check_dao_owner(env, owner);
         env.storage().instance().set(&Token::Owner, &new_owner);
     }
 
     pub fn set_core_address(env: &Env, owner: &Address, core_address: &Address) {
-        Token::check_auth(env, owner);
+        Token::check_dao_owner(env, owner);
+        // a decommissioned token can't be attached to a core again
+        Token::check_not_decommissioned(env);
         env.storage()
             .instance()
             .set(&Token::CoreAddress, core_address);
     }
 
+    /// Requires the auth of the owner of the DAO in the core
+    pub fn check_dao_owner(env: &Env, owner: &Address) {
+        owner.require_auth();
+        let core = core_contract::Client::new(env, &Token::get_core_address(env));
+        if &core.get_dao(&Token::get_symbol(env)).owner != owner {
+            panic_with_error!(env, AssetError::NotDaoOwner)
+        }
+    }
+
+    pub fn read_version(env: &Env) -> u32 {
+        env.storage().instance().get(&Token::Version).unwrap_or(0)
+    }
+
+    pub fn write_version(env: &Env) {
+        env.storage().instance().set(&Token::Version, &STORAGE_VERSION);
+    }
+
+    /// Rewrites the storage of an older layout after an upgrade, returns the previous version
+    ///
+    /// Version 0 didn't keep the total supply, the DAO owner states it.
+    pub fn migrate(env: &Env, total_supply: i128) -> u32 {
+        let from_version = Token::read_version(env);
+        if from_version >= STORAGE_VERSION {
+            panic_with_error!(env, AssetError::AlreadyMigrated)
+        }
+        if from_version < 1 {
+            if total_supply < 0 {
+                panic_with_error!(env, AssetError::NegativeAmount)
+            }
+            Token::write_total_supply(env, total_supply);
+        }
+        Token::write_version(env);
+        from_version
+    }
+
+    /// Freezes the token once the DAO is destroyed, it can't be minted, moved or approved anymore
+    pub fn decommission(env: &Env) -> Address {
+        let core_address = Token::get_core_address(env);
+        core_address.require_auth();
+        Token::check_not_decommissioned(env);
+        env.storage().instance().set(&Token::Decommissioned, &true);
+        core_address
+    }
+
+    pub fn is_decommissioned(env: &Env) -> bool {
+        env.storage().instance().has(&Token::Decommissioned)
+    }
+
+    pub fn check_not_decommissioned(env: &Env) {
+        if Token::is_decommissioned(env) {
+            panic_with_error!(env, AssetError::Decommissioned)
+        }
+    }
+
+    /// Writes the balance and its checkpoints
     pub fn write_balance(env: &Env, addr: Address, amount: i128) {
+        let (bump_threshold, bump_ledgers) = Token::get_bump(env);
         let key = Token::Balance(addr.clone());
+        let previous = Token::read_balance(env, addr.clone());
         env.storage().persistent().set(&key, &amount);
         env.storage()
             .persistent()
-            .bump(&key, BUMP_A_MONTH_THRESHOLD, BUMP_A_MONTH);
+            .bump(&key, bump_threshold, bump_ledgers);
+        // the votes go first, an account of version 0 reads them from its previous balance checkpoints
+        Token::add_votes(env, Token::read_delegate(env, addr.clone()), amount - previous);
         Token::write_checkpoint(env, addr);
     }
 
     pub fn read_balance(env: &Env, addr: Address) -> i128 {
+        let (bump_threshold, bump_ledgers) = Token::get_bump(env);
         let key = Token::Balance(addr);
         let balance = env.storage().persistent().get(&key).unwrap_or(0);
         if balance > 0 {
             env.storage()
                 .persistent()
-                .bump(&key, BUMP_A_MONTH_THRESHOLD, BUMP_A_MONTH);
+                .bump(&key, bump_threshold, bump_ledgers);
         }
         balance
     }
 
-    pub fn check_auth(env: &Env, owner: &Address) {
-        owner.require_auth();
-        if owner != &Token::get_owner(env) {
-            panic_with_error!(env, AssetError::NotTokenOwner)
+    /// The hard cap of the total supply, 0 if the supply is uncapped
+    pub fn get_max_supply(env: &Env) -> i128 {
+        env.storage().instance().get(&Token::MaxSupply).unwrap_or(0)
+    }
+
+    pub fn get_mint_limit(env: &Env) -> MintLimit {
+        env.storage().instance().get(&Token::MintLimit).unwrap_or(MintLimit { period: 0, amount: 0 })
+    }
+
+    pub fn set_mint_limit(env: &Env, dao_owner: &Address, mint_limit: &MintLimit) {
+        Token::check_dao_owner(env, dao_owner);
+        if mint_limit.amount < 0 {
+            panic_with_error!(env, AssetError::NegativeAmount)
         }
+        env.storage().instance().set(&Token::MintLimit, mint_limit);
+        env.storage().instance().remove(&Token::MintPeriod);
     }
 
-    pub fn check_is_minted(env: &Env, owner: Address) {
-        if !Token::get_checkpoints(env, owner).is_empty() {
-            panic_with_error!(env, AssetError::CanOnlyBeMintedOnce)
+    /// Checks `amount` against the supply cap and the mint limit of the current period and records it
+    pub fn check_mint(env: &Env, amount: i128) {
+        let max_supply = Token::get_max_supply(env);
+        if max_supply > 0 && Token::read_total_supply(env) + amount > max_supply {
+            panic_with_error!(env, AssetError::SupplyCapExceeded)
+        }
+
+        let mint_limit = Token::get_mint_limit(env);
+        if mint_limit.period == 0 {
+            return;
         }
+        let sequence = env.ledger().sequence();
+        let start = sequence - sequence % mint_limit.period;
+        let minted = match env.storage().instance().get::<Token, MintPeriod>(&Token::MintPeriod) {
+            Some(period) if period.start == start => period.minted + amount,
+            _ => amount,
+        };
+        if minted > mint_limit.amount {
+            panic_with_error!(env, AssetError::MintLimitExceeded)
+        }
+        env.storage().instance().set(&Token::MintPeriod, &MintPeriod { start, minted });
     }
 
     pub fn spend_balance(env: &Env, addr: Address, amount: i128) {
@@ -240,6 +588,12 @@ impl Token {
         Token::write_balance(env, addr, balance - amount);
     }
 
+    /// Destroys assets of `addr` and reduces the total supply
+    pub fn burn(env: &Env, addr: Address, amount: i128) {
+        Token::spend_balance(env, addr, amount);
+        Token::write_total_supply(env, Token::read_total_supply(env) - amount);
+    }
+
     pub fn receive_balance(env: &Env, addr: Address, amount: i128) {
         let balance = Token::read_balance(env, addr.clone());
         Token::write_balance(env, addr, balance + amount);
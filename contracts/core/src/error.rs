@@ -13,5 +13,28 @@ pub enum CoreError {
     NoMetadata = 6,
     NoHookpoint = 7,
     MustRemoveConfigFirst = 8,
-    NotEnoughFunds = 9
+    NotEnoughFunds = 9,
+    InvalidExpiration = 10,
+    NoOwnershipTransfer = 11,
+    NotProposedOwner = 12,
+    OwnershipTransferExpired = 13,
+    RoleAlreadyGranted = 14,
+    RoleNotGranted = 15,
+    InvalidCouncil = 16,
+    NoCouncil = 17,
+    NotCouncilSigner = 18,
+    CouncilActionNotFound = 19,
+    CouncilActionAlreadyApproved = 20,
+    CouncilActionExecuted = 21,
+    CouncilThresholdNotReached = 22,
+    InvalidCouncilAction = 23,
+    NotProtocolAdmin = 24,
+    InvalidProtocolConfig = 25,
+    AlreadyMigrated = 26,
+    MissingRole = 27,
+    NotProposedAdmin = 28,
+    NameTooLong = 29,
+    DaoHasOpenProposals = 30,
+    TreasuryNotEmpty = 31,
+    WithheldReserveNotClaimed = 32,
 }
\ No newline at end of file
@@ -1,13 +1,31 @@
-use soroban_sdk::{contracttype, Address, Bytes, Symbol, symbol_short};
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, Symbol, Vec, symbol_short};
+
+use crate::types::{CouncilAction, ProtocolConfig, Role};
 
 pub const DAO: Symbol = symbol_short!("DAO");
 pub const ASSET: Symbol = symbol_short!("ASSET");
 pub const VOTES: Symbol = symbol_short!("VOTES");
+pub const PROTOCOL: Symbol = symbol_short!("PROTOCOL");
 
 pub const CREATED: Symbol = symbol_short!("created");
 pub const DESTROYED: Symbol = symbol_short!("destroyed");
+pub const DECOMMISSIONED: Symbol = symbol_short!("decomm");
 pub const METADATA_SET: Symbol = symbol_short!("meta_set");
 pub const OWNER_CHANGED: Symbol = symbol_short!("new_owner");
+pub const OWNER_PROPOSED: Symbol = symbol_short!("own_prop");
+pub const TRANSFER_CANCELLED: Symbol = symbol_short!("own_cncl");
+pub const ROLE_GRANTED: Symbol = symbol_short!("role_grnt");
+pub const ROLE_REVOKED: Symbol = symbol_short!("role_rvkd");
+pub const COUNCIL_SET: Symbol = symbol_short!("cncl_set");
+pub const COUNCIL_PROPOSED: Symbol = symbol_short!("cncl_prop");
+pub const COUNCIL_APPROVED: Symbol = symbol_short!("cncl_appr");
+pub const COUNCIL_EXECUTED: Symbol = symbol_short!("cncl_exec");
+pub const CONFIG_SET: Symbol = symbol_short!("conf_set");
+pub const ADMIN_CHANGED: Symbol = symbol_short!("new_admin");
+pub const ADMIN_PROPOSED: Symbol = symbol_short!("adm_prop");
+pub const UPGRADED: Symbol = symbol_short!("upgraded");
+pub const MIGRATED: Symbol = symbol_short!("migrated");
+pub const TREASURY_SET: Symbol = symbol_short!("trsry_set");
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -22,6 +40,10 @@ pub struct DaoCreatedEventData {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DaoDestroyedEventData {
     pub dao_id: Bytes,
+    pub dao_name: Bytes,
+    pub owner_id: Address,
+    /// The reserve that was returned to the owner
+    pub reserve_amount: i128,
 }
 
 #[contracttype]
@@ -39,6 +61,68 @@ pub struct DaoOwnerChangedEventData {
     pub new_owner_id: Address,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DaoOwnerProposedEventData {
+    pub dao_id: Bytes,
+    pub new_owner_id: Address,
+    pub expiration_ledger: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DaoOwnershipTransferCancelledEventData {
+    pub dao_id: Bytes,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DaoRoleGrantedEventData {
+    pub dao_id: Bytes,
+    pub role: Role,
+    pub account_id: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DaoRoleRevokedEventData {
+    pub dao_id: Bytes,
+    pub role: Role,
+    pub account_id: Address,
+}
+
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DaoCouncilSetEventData {
+    pub dao_id: Bytes,
+    pub signers: Vec<Address>,
+    pub threshold: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DaoCouncilActionProposedEventData {
+    pub dao_id: Bytes,
+    pub action_id: u32,
+    pub signer_id: Address,
+    pub action: CouncilAction,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DaoCouncilActionApprovedEventData {
+    pub dao_id: Bytes,
+    pub action_id: u32,
+    pub signer_id: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DaoCouncilActionExecutedEventData {
+    pub dao_id: Bytes,
+    pub action_id: u32,
+}
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -46,4 +130,48 @@ pub struct AssetCreatedEventData {
     pub dao_id: Bytes,
     pub asset_id: Address,
     pub owner_id: Address,
-}
\ No newline at end of file
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetDecommissionedEventData {
+    pub dao_id: Bytes,
+    pub asset_id: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProtocolConfigSetEventData {
+    pub config: ProtocolConfig,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasurySetEventData {
+    pub treasury_id: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProtocolAdminProposedEventData {
+    pub new_admin_id: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProtocolAdminChangedEventData {
+    pub new_admin_id: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradedEventData {
+    pub wasm_hash: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigratedEventData {
+    pub from_version: u32,
+    pub to_version: u32,
+}
@@ -1,14 +1,64 @@
-use soroban_sdk::{Env, Bytes, Address, BytesN};
+use soroban_sdk::{Env, Bytes, Address, BytesN, Vec};
 
-use crate::types::{Dao, Metadata};
+use crate::types::{
+    Council, CouncilAction, CouncilProposal, Dao, Metadata, OwnershipTransfer, ProtocolConfig, Role,
+};
 
 pub trait CoreTrait {
     /// Initialize the contract
     ///
-    /// - `votes_wasm_hash`: The wasm hash of the votes contract
-    /// - `votes_salt`: a 32 bytes salt to derive the contract id
+    /// - `votes_id`: The address of the votes contract
+    /// - `native_asset_id`: The address of the native asset, the reserves are paid in it
+    /// - `protocol_admin`: The account or governance contract that can change the protocol config
     ///
-    fn init(env: Env, votes_id: Address, native_asset_id: Address);
+    fn init(env: Env, votes_id: Address, native_asset_id: Address, protocol_admin: Address);
+
+    /// Replace the wasm of the contract, the storage is kept
+    ///
+    /// Call `migrate` afterwards if the new wasm changes the storage layout.
+    ///
+    /// - `new_wasm_hash`: The hash of the installed wasm
+    /// - `protocol_admin`: The protocol admin
+    ///
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>, protocol_admin: Address);
+
+    /// Gets the version of the storage layout
+    fn version(env: Env) -> u32;
+
+    /// Rewrite the storage of an older layout after an upgrade, returns the current version
+    ///
+    /// - `protocol_admin`: The protocol admin
+    ///
+    fn migrate(env: Env, protocol_admin: Address) -> u32;
+
+    /// Gets the protocol config, the votes and assets contracts read it from here
+    fn get_protocol_config(env: Env) -> ProtocolConfig;
+
+    /// Change the protocol config
+    ///
+    /// The reserves of existing DAOs and proposals are recorded and returned as paid.
+    ///
+    /// - `config`: The new protocol config
+    /// - `protocol_admin`: The protocol admin
+    ///
+    fn set_protocol_config(env: Env, config: ProtocolConfig, protocol_admin: Address);
+
+    /// Gets the protocol admin
+    fn get_protocol_admin(env: Env) -> Address;
+
+    /// Propose a new admin of the protocol config, the current admin stays in charge until the
+    /// new one accepts
+    ///
+    /// - `new_admin`: The proposed protocol admin
+    /// - `protocol_admin`: The current protocol admin
+    ///
+    fn propose_protocol_admin(env: Env, new_admin: Address, protocol_admin: Address);
+
+    /// Accept the protocol config as the proposed admin
+    ///
+    /// - `new_admin`: The proposed protocol admin (must be authed)
+    ///
+    fn accept_protocol_admin(env: Env, new_admin: Address);
 
     /// Gets the Votes ID
     fn get_votes_id(env: Env) -> Address;
@@ -16,10 +66,20 @@ pub trait CoreTrait {
     /// Gets the Native Asset ID
     fn get_native_asset_id(env: Env) -> Address;
 
+    /// Gets the Treasury ID, if a treasury is registered
+    fn get_treasury_id(env: Env) -> Option<Address>;
+
+    /// Register the treasury contract, DAOs are not destroyed while it holds funds for them
+    ///
+    /// - `treasury_id`: The address of the treasury contract
+    /// - `protocol_admin`: The protocol admin
+    ///
+    fn set_treasury_id(env: Env, treasury_id: Address, protocol_admin: Address);
+
     /// Create a fresh DAO.
     ///
-    /// - `dao_id`: Unique identifier for the DAO
-    /// - `dao_name`: Name of the to-be-created DAO
+    /// - `dao_id`: Unique identifier for the DAO, at most 256 bytes
+    /// - `dao_name`: Name of the to-be-created DAO, at most 256 bytes
     /// - `dao_owner`: The owner of the freshly created dao
     ///
     fn create_dao(env: Env, dao_id: Bytes, dao_name: Bytes, dao_owner: Address) -> Dao;
@@ -29,8 +89,30 @@ pub trait CoreTrait {
     /// - `dao_id`: The id of the dao to load;
     fn get_dao(env: Env, dao_id: Bytes) -> Dao;
 
+    /// Get the number of DAOs.
+    fn get_dao_count(env: Env) -> u32;
+
+    /// List the DAOs. Destroying a DAO moves the last DAO to its position.
+    ///
+    /// - `start`: The position of the first DAO to return;
+    /// - `limit`: The max number of DAOs to return;
+    fn list_daos(env: Env, start: u32, limit: u32) -> Vec<Dao>;
+
+    /// List the DAOs of an owner.
+    ///
+    /// - `owner`: The owner of the DAOs;
+    fn list_daos_by_owner(env: Env, owner: Address) -> Vec<Dao>;
+
     /// Destroy a DAO.
     ///
+    /// Removes the DAO with its metadata, hookpoint, roles and council and decommissions the
+    /// issued asset, which freezes its transfers. The reserve is returned to the owner.
+    ///
+    /// The votes contract resets the proposal lists and numbering of the DAO. A DAO with open
+    /// proposals, an unclaimed withheld reserve or funds in the treasury can't be destroyed, and
+    /// neither can a DAO of a council: the council hands it to an owner with `propose_owner`
+    /// first, who receives the reserve.
+    ///
     /// - `dao_id`: The DAO to destroy
     /// - `dao_owner`: The owner of to-be-destroyed dao
     fn destroy_dao(env: Env, dao_id: Bytes, dao_owner: Address);
@@ -38,13 +120,22 @@ pub trait CoreTrait {
     /// Issue the DAO token
     ///
     /// - `dao_id`: The DAO for which to issue a token
-    /// - `dao_owner`: The owner of the dao about to issue a token
+    /// - `dao_owner`: The owner of the dao or an account with the admin role
     /// - `assets_wasm_hash`: The wasm hash of the assets contract
     /// - `asset_salt`: a 32 bytes salt to derive the contract id
+    /// - `max_supply`: The hard cap of the token supply, 0 for an uncapped supply
     ///
     /// Tokens can only be issued once and the signer of this TX needs to be the owner
-    /// of the DAO.
-    fn issue_token(env: Env, dao_id: Bytes, dao_owner: Address, assets_wasm_hash: BytesN<32>, asset_salt: BytesN<32>) -> Address;
+    /// of the DAO or an admin. The owner of the DAO becomes the owner of the token. A custom
+    /// token has to implement `get_votes_at` and `get_total_supply_at` of the assets contract.
+    fn issue_token(
+        env: Env,
+        dao_id: Bytes,
+        dao_owner: Address,
+        assets_wasm_hash: BytesN<32>,
+        asset_salt: BytesN<32>,
+        max_supply: i128,
+    ) -> Address;
 
     /// Returns the contract id of the dao asset (if exists).
     ///
@@ -57,7 +148,7 @@ pub trait CoreTrait {
     /// - `dao_id`: The DAO for which to set metadata
     /// - `meta`: HTTP or IPFS address for the metadata about this DAO (description, logo)
     /// - `hash`: SHA3 hash of the metadata to be found via `meta`
-    /// - `dao_owner`: the current owner of the dao
+    /// - `dao_owner`: the current owner of the dao or an account with the metadata editor role
     fn set_metadata(env: Env, dao_id: Bytes, meta: Bytes, hash: Bytes, dao_owner: Address) -> Metadata;
 
     /// Load metadata for a dao
@@ -79,19 +170,117 @@ pub trait CoreTrait {
     ///
     /// - `dao_id`: The DAO for which to set the hookpoint
     /// - `hookpoint`: The address of the deployed hookpoint contract
-    /// - `dao_owner`: the current owner of the dao
+    /// - `dao_owner`: the current owner of the dao or an account with the hookpoint manager role
     fn set_hookpoint(env: Env, dao_id: Bytes, hookpoint: Address, dao_owner: Address);
 
     /// Remove the hookpoint
     ///
     /// - `dao_id`: The DAO for which to set the hookpoint
-    /// - `dao_owner`: the current owner of the dao
+    /// - `dao_owner`: the current owner of the dao or an account with the hookpoint manager role
     fn remove_hookpoint(env: Env, dao_id: Bytes, dao_owner: Address);
 
-    /// Change owner
+    /// Propose a new owner, who has to accept the ownership before the expiration ledger
+    ///
+    /// A pending transfer is replaced by the new proposal. The storage of a transfer is kept
+    /// alive for at most the max entry expiration of the network, about 180 days.
     ///
     /// - `dao_id`: the DAO to transfer ownership of
-    /// - `new_owner`: the new owner
+    /// - `new_owner`: the proposed owner
+    /// - `expiration_ledger`: the last ledger the ownership can be accepted at
     /// - `dao_owner`: the current owner of the dao
-    fn change_owner(env: Env, dao_id: Bytes, new_owner: Address, dao_owner: Address) -> Dao;
+    fn propose_owner(env: Env, dao_id: Bytes, new_owner: Address, expiration_ledger: u32, dao_owner: Address) -> OwnershipTransfer;
+
+    /// Accept the ownership of a DAO
+    ///
+    /// The roles granted by the previous owner are revoked.
+    ///
+    /// - `dao_id`: the DAO to take ownership of
+    /// - `new_owner`: the proposed owner
+    fn accept_ownership(env: Env, dao_id: Bytes, new_owner: Address) -> Dao;
+
+    /// Cancel a pending ownership transfer
+    ///
+    /// - `dao_id`: the DAO with the pending transfer
+    /// - `dao_owner`: the current owner of the dao
+    fn cancel_ownership_transfer(env: Env, dao_id: Bytes, dao_owner: Address);
+
+    /// Load the pending ownership transfer of a DAO
+    ///
+    /// - `dao_id`: the DAO with the pending transfer
+    fn get_ownership_transfer(env: Env, dao_id: Bytes) -> OwnershipTransfer;
+
+    /// Grant a role of a DAO to an account
+    ///
+    /// - `dao_id`: the DAO to grant the role for
+    /// - `role`: the role to grant
+    /// - `account`: the account that is granted the role
+    /// - `admin`: the current owner of the dao or an account with the admin role
+    fn grant_role(env: Env, dao_id: Bytes, role: Role, account: Address, admin: Address);
+
+    /// Revoke a role of a DAO from an account
+    ///
+    /// - `dao_id`: the DAO to revoke the role for
+    /// - `role`: the role to revoke
+    /// - `account`: the account that loses the role
+    /// - `admin`: the current owner of the dao or an account with the admin role
+    fn revoke_role(env: Env, dao_id: Bytes, role: Role, account: Address, admin: Address);
+
+    /// Checks if an account holds a role of a DAO, the owner holds all roles
+    ///
+    /// - `dao_id`: the DAO to check the role for
+    /// - `role`: the role to check
+    /// - `account`: the account to check
+    fn has_role(env: Env, dao_id: Bytes, role: Role, account: Address) -> bool;
+
+    /// Hand the DAO to a council of signers
+    ///
+    /// The core contract becomes the owner of the DAO. The owner functions of the core and votes
+    /// contracts are then called through council actions with the core contract as the owner.
+    /// Setting the council again through a council action replaces the signers and discards the
+    /// pending actions. Handing the DAO to a council revokes the granted roles.
+    ///
+    /// - `dao_id`: the DAO to hand to the council
+    /// - `signers`: the accounts that propose and approve council actions
+    /// - `threshold`: the number of approvals an action needs to be executed
+    /// - `dao_owner`: the current owner of the dao
+    fn set_council(env: Env, dao_id: Bytes, signers: Vec<Address>, threshold: u32, dao_owner: Address) -> Council;
+
+    /// Load the council of a DAO
+    ///
+    /// - `dao_id`: the DAO of the council
+    fn get_council(env: Env, dao_id: Bytes) -> Council;
+
+    /// Propose a call of an owner function of the core or votes contract, the signer approves it
+    ///
+    /// - `dao_id`: the DAO of the council, has to be the first argument of the action
+    /// - `action`: the call to propose
+    /// - `signer`: a signer of the council
+    fn propose_council_action(env: Env, dao_id: Bytes, action: CouncilAction, signer: Address) -> u32;
+
+    /// Approve a pending council action
+    ///
+    /// - `dao_id`: the DAO of the council
+    /// - `action_id`: the id of the action
+    /// - `signer`: a signer of the council
+    fn approve_council_action(env: Env, dao_id: Bytes, action_id: u32, signer: Address);
+
+    /// Execute a council action of the core contract once it has enough approvals
+    ///
+    /// Actions of the votes contract are executed with `execute_council_action` of the votes contract.
+    ///
+    /// - `dao_id`: the DAO of the council
+    /// - `action_id`: the id of the action
+    fn execute_council_action(env: Env, dao_id: Bytes, action_id: u32);
+
+    /// Hand an approved council action of the votes contract to the votes contract for execution
+    ///
+    /// - `dao_id`: the DAO of the council
+    /// - `action_id`: the id of the action
+    fn take_council_action(env: Env, dao_id: Bytes, action_id: u32) -> CouncilAction;
+
+    /// Load a council action with its approvals
+    ///
+    /// - `dao_id`: the DAO of the council
+    /// - `action_id`: the id of the action
+    fn get_council_action(env: Env, dao_id: Bytes, action_id: u32) -> CouncilProposal;
 }
@@ -1,5 +1,5 @@
 #![no_std]
-use soroban_sdk::{contractimpl, contract, token, Address, Bytes, BytesN, Env, Symbol, panic_with_error, symbol_short};
+use soroban_sdk::{contractimpl, contract, token, Address, Bytes, BytesN, Env, IntoVal, Symbol, TryFromVal, Val, Vec, panic_with_error, symbol_short};
 
 mod votes_contract {
     soroban_sdk::contractimport!(file = "../../wasm/elio_votes.wasm");
@@ -11,12 +11,21 @@ mod events;
 mod interface;
 use events::{
     DaoCreatedEventData, DaoDestroyedEventData, DaoMetadataSetEventData, DaoOwnerChangedEventData,
-    CREATED, DAO, DESTROYED, METADATA_SET, OWNER_CHANGED, VOTES,
+    DaoOwnerProposedEventData, DaoOwnershipTransferCancelledEventData, DaoRoleGrantedEventData,
+    DaoRoleRevokedEventData, DaoCouncilSetEventData, DaoCouncilActionProposedEventData,
+    DaoCouncilActionApprovedEventData, DaoCouncilActionExecutedEventData, CREATED, DAO, DESTROYED,
+    METADATA_SET, OWNER_CHANGED, OWNER_PROPOSED, ROLE_GRANTED, ROLE_REVOKED, TRANSFER_CANCELLED,
+    COUNCIL_SET, COUNCIL_PROPOSED, COUNCIL_APPROVED, COUNCIL_EXECUTED, VOTES,
+    ProtocolAdminChangedEventData, ProtocolAdminProposedEventData, ProtocolConfigSetEventData,
+    ADMIN_CHANGED, ADMIN_PROPOSED, CONFIG_SET, PROTOCOL,
+    MigratedEventData, UpgradedEventData, MIGRATED, UPGRADED, TreasurySetEventData, TREASURY_SET,
 };
 use interface::CoreTrait;
 
 mod types;
-use types::{Dao, Metadata};
+use types::{
+    Council, CouncilAction, CouncilProposal, Dao, Metadata, OwnershipTransfer, ProtocolConfig, Role,
+};
 use crate::error::CoreError;
 use crate::hooks::{on_before_change_owner, on_before_destroy_dao};
 use crate::types::DaoArtifact;
@@ -25,22 +34,88 @@ mod error;
 mod hooks;
 
 pub const NATIVE: Symbol = symbol_short!("NATIVE");
-
-const XLM: i128 = 10_000_000;
-const RESERVE_AMOUNT: i128 = 1000 * XLM;
+pub const TREASURY: Symbol = symbol_short!("TREASURY");
 
 #[contract]
 pub struct CoreContract;
 
 #[contractimpl]
 impl CoreTrait for CoreContract {
-    fn init(env: Env, votes_id: Address, native_asset_id: Address) {
+    fn init(env: Env, votes_id: Address, native_asset_id: Address, protocol_admin: Address) {
         if env.storage().instance().has(&VOTES) {
             panic_with_error!(env, CoreError::VotesAlreadyInitiated)
         }
 
         env.storage().instance().set(&VOTES, &votes_id);
         env.storage().instance().set(&NATIVE, &native_asset_id);
+        ProtocolConfig::init(&env, &protocol_admin);
+        types::write_version(&env);
+    }
+
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>, protocol_admin: Address) {
+        ProtocolConfig::check_admin(&env, &protocol_admin);
+        env.deployer().update_current_contract_wasm(new_wasm_hash.clone());
+        env.events().publish(
+            (PROTOCOL, UPGRADED),
+            UpgradedEventData {
+                wasm_hash: new_wasm_hash,
+            },
+        );
+    }
+
+    fn version(env: Env) -> u32 {
+        types::read_version(&env)
+    }
+
+    fn migrate(env: Env, protocol_admin: Address) -> u32 {
+        ProtocolConfig::check_admin(&env, &protocol_admin);
+        let from_version = types::migrate(&env);
+        env.events().publish(
+            (PROTOCOL, MIGRATED),
+            MigratedEventData {
+                from_version,
+                to_version: types::STORAGE_VERSION,
+            },
+        );
+        types::STORAGE_VERSION
+    }
+
+    fn get_protocol_config(env: Env) -> ProtocolConfig {
+        ProtocolConfig::load(&env)
+    }
+
+    fn set_protocol_config(env: Env, config: ProtocolConfig, protocol_admin: Address) {
+        ProtocolConfig::check_admin(&env, &protocol_admin);
+        config.save(&env);
+        env.events().publish(
+            (PROTOCOL, CONFIG_SET),
+            ProtocolConfigSetEventData { config },
+        );
+    }
+
+    fn get_protocol_admin(env: Env) -> Address {
+        ProtocolConfig::load_admin(&env)
+    }
+
+    fn propose_protocol_admin(env: Env, new_admin: Address, protocol_admin: Address) {
+        ProtocolConfig::check_admin(&env, &protocol_admin);
+        ProtocolConfig::propose_admin(&env, &new_admin);
+        env.events().publish(
+            (PROTOCOL, ADMIN_PROPOSED),
+            ProtocolAdminProposedEventData {
+                new_admin_id: new_admin,
+            },
+        );
+    }
+
+    fn accept_protocol_admin(env: Env, new_admin: Address) {
+        ProtocolConfig::accept_admin(&env, &new_admin);
+        env.events().publish(
+            (PROTOCOL, ADMIN_CHANGED),
+            ProtocolAdminChangedEventData {
+                new_admin_id: new_admin,
+            },
+        );
     }
 
     fn get_votes_id(env: Env) -> Address {
@@ -51,6 +126,16 @@ impl CoreTrait for CoreContract {
         env.storage().instance().get(&NATIVE).unwrap()
     }
 
+    fn get_treasury_id(env: Env) -> Option<Address> {
+        env.storage().instance().get(&TREASURY)
+    }
+
+    fn set_treasury_id(env: Env, treasury_id: Address, protocol_admin: Address) {
+        ProtocolConfig::check_admin(&env, &protocol_admin);
+        env.storage().instance().set(&TREASURY, &treasury_id);
+        env.events().publish((PROTOCOL, TREASURY_SET), TreasurySetEventData { treasury_id });
+    }
+
     fn create_dao(env: Env, dao_id: Bytes, dao_name: Bytes, dao_owner: Address) -> Dao {
         dao_owner.require_auth();
 
@@ -60,12 +145,14 @@ impl CoreTrait for CoreContract {
 
         let contract = &env.current_contract_address();
 
-        if native_token.balance(&dao_owner) < RESERVE_AMOUNT {
+        let reserve = ProtocolConfig::load(&env).dao_reserve;
+        if native_token.balance(&dao_owner) < reserve {
             panic_with_error!(env, CoreError::NotEnoughFunds);
         }
-        native_token.transfer(&dao_owner, contract, &RESERVE_AMOUNT);
+        native_token.transfer(&dao_owner, contract, &reserve);
 
         let dao = Dao::create(&env, dao_id.clone(), dao_name.clone(), dao_owner.clone());
+        dao.set_reserve(&env, reserve);
 
         env.events().publish(
             (DAO, CREATED),
@@ -82,6 +169,18 @@ impl CoreTrait for CoreContract {
         Dao::load(&env, &dao_id)
     }
 
+    fn get_dao_count(env: Env) -> u32 {
+        Dao::count(&env)
+    }
+
+    fn list_daos(env: Env, start: u32, limit: u32) -> Vec<Dao> {
+        Dao::list(&env, start, limit)
+    }
+
+    fn list_daos_by_owner(env: Env, owner: Address) -> Vec<Dao> {
+        Dao::list_by_owner(&env, &owner)
+    }
+
     fn destroy_dao(env: Env, dao_id: Bytes, dao_owner: Address) {
         on_before_destroy_dao(&env, &dao_id);
         let votes_id = Self::get_votes_id(env.clone());
@@ -90,15 +189,41 @@ impl CoreTrait for CoreContract {
         if has_configuration {
             panic_with_error!(env, CoreError::MustRemoveConfigFirst)
         }
-        Dao::load_for_owner(&env, &dao_id, &dao_owner).destroy(&env);
+        if votes_contract.has_open_proposals(&dao_id) {
+            panic_with_error!(env, CoreError::DaoHasOpenProposals)
+        }
+        if votes_contract.get_withheld_reserve(&dao_id) > 0 {
+            panic_with_error!(env, CoreError::WithheldReserveNotClaimed)
+        }
+        if let Some(treasury_id) = Self::get_treasury_id(env.clone()) {
+            let has_balance: bool = env.invoke_contract(
+                &treasury_id,
+                &Symbol::new(&env, "has_balance"),
+                (dao_id.clone(),).into_val(&env),
+            );
+            if has_balance {
+                panic_with_error!(env, CoreError::TreasuryNotEmpty)
+            }
+        }
+        let dao = Dao::load_for_owner(&env, &dao_id, &dao_owner);
+        let reserve = dao.get_reserve(&env);
+        dao.destroy(&env);
+        votes_contract.remove_dao(&dao_id);
 
         let native_asset_id = env.storage().instance().get(&NATIVE).unwrap();
         let native_token = token::Client::new(&env, &native_asset_id);
         let contract = &env.current_contract_address();
-        native_token.transfer(contract, &dao_owner, &RESERVE_AMOUNT);
+        native_token.transfer(contract, &dao_owner, &reserve);
 
-        env.events()
-            .publish((DAO, DESTROYED), DaoDestroyedEventData { dao_id });
+        env.events().publish(
+            (DAO, DESTROYED),
+            DaoDestroyedEventData {
+                dao_id,
+                dao_name: dao.name,
+                owner_id: dao_owner,
+                reserve_amount: reserve,
+            },
+        );
     }
 
     fn issue_token(
@@ -107,9 +232,10 @@ impl CoreTrait for CoreContract {
         dao_owner: Address,
         assets_wasm_hash: BytesN<32>,
         asset_salt: BytesN<32>,
+        max_supply: i128,
     ) -> Address {
-        let dao = Dao::load_for_owner(&env, &dao_id, &dao_owner);
-        dao.issue_token(&env, assets_wasm_hash, asset_salt)
+        let dao = Dao::load_for_role(&env, &dao_id, Role::Admin, &dao_owner);
+        dao.issue_token(&env, assets_wasm_hash, asset_salt, max_supply)
     }
 
     fn get_dao_asset_id(env: Env, dao_id: Bytes) -> Address {
@@ -123,8 +249,8 @@ impl CoreTrait for CoreContract {
         hash: Bytes,
         dao_owner: Address,
     ) -> Metadata {
-        // this is to load & verify ownership
-        Dao::load_for_owner(&env, &dao_id, &dao_owner);
+        // this is to load & verify the role
+        Dao::load_for_role(&env, &dao_id, Role::MetadataEditor, &dao_owner);
         let meta = Metadata::create(&env, dao_id.clone(), url.clone(), hash.clone());
         env.events().publish(
             (DAO, METADATA_SET),
@@ -152,22 +278,45 @@ impl CoreTrait for CoreContract {
     }
 
     fn set_hookpoint(env: Env, dao_id: Bytes, hookpoint: Address, dao_owner: Address) {
-        let dao = Dao::load_for_owner(&env, &dao_id, &dao_owner);
+        let dao = Dao::load_for_role(&env, &dao_id, Role::HookpointManager, &dao_owner);
         env.storage().persistent().set(&DaoArtifact::Hookpoint(dao.id), &hookpoint);
     }
 
     fn remove_hookpoint(env: Env, dao_id: Bytes, dao_owner: Address) {
-        let dao = Dao::load_for_owner(&env, &dao_id, &dao_owner);
+        let dao = Dao::load_for_role(&env, &dao_id, Role::HookpointManager, &dao_owner);
         if env.storage().persistent().has(&DaoArtifact::Hookpoint(dao_id)) {
             env.storage().persistent().remove(&DaoArtifact::Hookpoint(dao.id))
         }
     }
 
-    fn change_owner(env: Env, dao_id: Bytes, new_owner: Address, dao_owner: Address) -> Dao {
-        on_before_change_owner(&env, &dao_id, &new_owner, &dao_owner);
-        let mut dao = Dao::load_for_owner(&env, &dao_id, &dao_owner);
-        dao.owner = new_owner.clone();
-        dao.save(&env);
+    fn propose_owner(
+        env: Env,
+        dao_id: Bytes,
+        new_owner: Address,
+        expiration_ledger: u32,
+        dao_owner: Address,
+    ) -> OwnershipTransfer {
+        Dao::load_for_owner(&env, &dao_id, &dao_owner);
+        let transfer = OwnershipTransfer::propose(&env, dao_id.clone(), new_owner.clone(), expiration_ledger);
+        env.events().publish(
+            (DAO, OWNER_PROPOSED),
+            DaoOwnerProposedEventData {
+                dao_id,
+                new_owner_id: new_owner,
+                expiration_ledger,
+            },
+        );
+        transfer
+    }
+
+    fn accept_ownership(env: Env, dao_id: Bytes, new_owner: Address) -> Dao {
+        let mut dao = Dao::load(&env, &dao_id);
+        OwnershipTransfer::accept(&env, &dao_id, &new_owner);
+        on_before_change_owner(&env, &dao_id, &new_owner, &dao.owner);
+        if dao.owner == env.current_contract_address() {
+            Council::remove(&env, &dao_id);
+        }
+        dao.change_owner(&env, new_owner.clone());
         env.events().publish(
             (DAO, OWNER_CHANGED),
             DaoOwnerChangedEventData {
@@ -177,4 +326,199 @@ impl CoreTrait for CoreContract {
         );
         dao
     }
+
+    fn cancel_ownership_transfer(env: Env, dao_id: Bytes, dao_owner: Address) {
+        Dao::load_for_owner(&env, &dao_id, &dao_owner);
+        OwnershipTransfer::remove(&env, &dao_id);
+        env.events().publish(
+            (DAO, TRANSFER_CANCELLED),
+            DaoOwnershipTransferCancelledEventData { dao_id },
+        );
+    }
+
+    fn get_ownership_transfer(env: Env, dao_id: Bytes) -> OwnershipTransfer {
+        OwnershipTransfer::load(&env, &dao_id)
+    }
+
+    fn grant_role(env: Env, dao_id: Bytes, role: Role, account: Address, admin: Address) {
+        let dao = Dao::load_for_role(&env, &dao_id, Role::Admin, &admin);
+        dao.grant_role(&env, role, account.clone());
+        env.events().publish(
+            (DAO, ROLE_GRANTED),
+            DaoRoleGrantedEventData {
+                dao_id,
+                role,
+                account_id: account,
+            },
+        );
+    }
+
+    fn revoke_role(env: Env, dao_id: Bytes, role: Role, account: Address, admin: Address) {
+        let dao = Dao::load_for_role(&env, &dao_id, Role::Admin, &admin);
+        dao.revoke_role(&env, role, account.clone());
+        env.events().publish(
+            (DAO, ROLE_REVOKED),
+            DaoRoleRevokedEventData {
+                dao_id,
+                role,
+                account_id: account,
+            },
+        );
+    }
+
+    fn has_role(env: Env, dao_id: Bytes, role: Role, account: Address) -> bool {
+        Dao::load(&env, &dao_id).has_role(&env, role, &account)
+    }
+
+    fn set_council(
+        env: Env,
+        dao_id: Bytes,
+        signers: Vec<Address>,
+        threshold: u32,
+        dao_owner: Address,
+    ) -> Council {
+        let mut dao = Dao::load_for_owner(&env, &dao_id, &dao_owner);
+        let council = Council::set(&env, dao_id.clone(), signers.clone(), threshold);
+
+        let core = env.current_contract_address();
+        if dao.owner != core {
+            on_before_change_owner(&env, &dao_id, &core, &dao.owner);
+            dao.change_owner(&env, core.clone());
+            env.events().publish(
+                (DAO, OWNER_CHANGED),
+                DaoOwnerChangedEventData {
+                    dao_id: dao_id.clone(),
+                    new_owner_id: core,
+                },
+            );
+        }
+        env.events().publish(
+            (DAO, COUNCIL_SET),
+            DaoCouncilSetEventData {
+                dao_id,
+                signers,
+                threshold,
+            },
+        );
+        council
+    }
+
+    fn get_council(env: Env, dao_id: Bytes) -> Council {
+        Council::load(&env, &dao_id)
+    }
+
+    fn propose_council_action(env: Env, dao_id: Bytes, action: CouncilAction, signer: Address) -> u32 {
+        let council = Council::load(&env, &dao_id);
+        let targets_dao = match action.args.first() {
+            Some(arg) => Bytes::try_from_val(&env, &arg).map_or(false, |id| id == dao_id),
+            None => false,
+        };
+        let contract_id = action.contract_id.clone();
+        if !targets_dao || (contract_id != env.current_contract_address() && contract_id != Self::get_votes_id(env.clone())) {
+            panic_with_error!(env, CoreError::InvalidCouncilAction)
+        }
+        let action_id = council.propose(&env, &dao_id, action.clone(), signer.clone());
+        env.events().publish(
+            (DAO, COUNCIL_PROPOSED),
+            DaoCouncilActionProposedEventData {
+                dao_id,
+                action_id,
+                signer_id: signer,
+                action,
+            },
+        );
+        action_id
+    }
+
+    fn approve_council_action(env: Env, dao_id: Bytes, action_id: u32, signer: Address) {
+        Council::load(&env, &dao_id).approve(&env, &dao_id, action_id, signer.clone());
+        env.events().publish(
+            (DAO, COUNCIL_APPROVED),
+            DaoCouncilActionApprovedEventData {
+                dao_id,
+                action_id,
+                signer_id: signer,
+            },
+        );
+    }
+
+    fn execute_council_action(env: Env, dao_id: Bytes, action_id: u32) {
+        let council = Council::load(&env, &dao_id);
+        if council.load_proposal(&env, &dao_id, action_id).action.contract_id != env.current_contract_address() {
+            panic_with_error!(env, CoreError::InvalidCouncilAction)
+        }
+        let action = council.execute(&env, &dao_id, action_id);
+
+        Council::begin_execution(&env, &dao_id);
+        Self::dispatch(&env, &dao_id, &action);
+        Council::end_execution(&env, &dao_id);
+
+        env.events().publish(
+            (DAO, COUNCIL_EXECUTED),
+            DaoCouncilActionExecutedEventData { dao_id, action_id },
+        );
+    }
+
+    fn take_council_action(env: Env, dao_id: Bytes, action_id: u32) -> CouncilAction {
+        let votes_id = Self::get_votes_id(env.clone());
+        votes_id.require_auth();
+
+        let council = Council::load(&env, &dao_id);
+        if council.load_proposal(&env, &dao_id, action_id).action.contract_id != votes_id {
+            panic_with_error!(env, CoreError::InvalidCouncilAction)
+        }
+        let action = council.execute(&env, &dao_id, action_id);
+        env.events().publish(
+            (DAO, COUNCIL_EXECUTED),
+            DaoCouncilActionExecutedEventData { dao_id, action_id },
+        );
+        action
+    }
+
+    fn get_council_action(env: Env, dao_id: Bytes, action_id: u32) -> CouncilProposal {
+        Council::load(&env, &dao_id).read_proposal(&env, &dao_id, action_id)
+    }
+}
+
+impl CoreContract {
+    /// Applies a council action that targets this contract. A contract can't call itself, so the
+    /// owner functions are dispatched internally while the contract is authorized as the owner.
+    fn dispatch(env: &Env, dao_id: &Bytes, action: &CouncilAction) {
+        let (e, id, func) = (env.clone(), dao_id.clone(), action.func.clone());
+        if func == Symbol::new(env, "set_metadata") {
+            Self::set_metadata(e, id, action_arg(env, action, 1), action_arg(env, action, 2), action_arg(env, action, 3));
+        } else if func == Symbol::new(env, "set_hookpoint") {
+            Self::set_hookpoint(e, id, action_arg(env, action, 1), action_arg(env, action, 2));
+        } else if func == Symbol::new(env, "remove_hookpoint") {
+            Self::remove_hookpoint(e, id, action_arg(env, action, 1));
+        } else if func == Symbol::new(env, "issue_token") {
+            Self::issue_token(
+                e,
+                id,
+                action_arg(env, action, 1),
+                action_arg(env, action, 2),
+                action_arg(env, action, 3),
+                action_arg(env, action, 4),
+            );
+        } else if func == Symbol::new(env, "grant_role") {
+            Self::grant_role(e, id, action_arg(env, action, 1), action_arg(env, action, 2), action_arg(env, action, 3));
+        } else if func == Symbol::new(env, "revoke_role") {
+            Self::revoke_role(e, id, action_arg(env, action, 1), action_arg(env, action, 2), action_arg(env, action, 3));
+        } else if func == Symbol::new(env, "propose_owner") {
+            Self::propose_owner(e, id, action_arg(env, action, 1), action_arg(env, action, 2), action_arg(env, action, 3));
+        } else if func == Symbol::new(env, "cancel_ownership_transfer") {
+            Self::cancel_ownership_transfer(e, id, action_arg(env, action, 1));
+        } else if func == Symbol::new(env, "set_council") {
+            Self::set_council(e, id, action_arg(env, action, 1), action_arg(env, action, 2), action_arg(env, action, 3));
+        } else {
+            panic_with_error!(env, CoreError::InvalidCouncilAction)
+        }
+    }
+}
+
+fn action_arg<T: TryFromVal<Env, Val>>(env: &Env, action: &CouncilAction, index: u32) -> T {
+    match action.args.get(index).map(|arg| T::try_from_val(env, &arg)) {
+        Some(Ok(arg)) => arg,
+        _ => panic_with_error!(env, CoreError::InvalidCouncilAction),
+    }
 }
@@ -8,9 +8,9 @@ mod assets_contract {
     soroban_sdk::contractimport!(file = "../../wasm/elio_assets.wasm");
 }
 
-use soroban_sdk::{log, testutils::Address as _, token, Address, BytesN, Env, IntoVal};
+use soroban_sdk::{log, symbol_short, testutils::{Address as _, Ledger}, token, vec, Address, Bytes, BytesN, Env, IntoVal, String, Symbol};
 
-use crate::{types::Dao, CoreContract, CoreContractClient};
+use crate::{types::{CouncilAction, Dao, ProtocolConfig, RegistryKey, Role, MAX_TTL}, CoreContract, CoreContractClient};
 use votes_contract::Client as VotesContractClient;
 
 struct Clients {
@@ -18,6 +18,7 @@ struct Clients {
     votes: VotesContractClient<'static>,
     native_asset: token::Client<'static>,
     native_asset_admin: token::StellarAssetClient<'static>,
+    protocol_admin: Address,
 }
 
 pub const MAX_I128: i128 = 170_141_183_460_469_231_731_687_303_715_884_105_727;
@@ -38,9 +39,10 @@ fn create_clients() -> Clients {
 
     let native_asset_admin = token::StellarAssetClient::new(&env, &native_asset_id);
 
-    core.init(&votes_id, &native_asset_id);
+    let protocol_admin = Address::random(&env);
+    core.init(&votes_id, &native_asset_id, &protocol_admin);
     votes.init(&core_id);
-    Clients { core, votes, native_asset, native_asset_admin }
+    Clients { core, votes, native_asset, native_asset_admin, protocol_admin }
 }
 
 fn create_dao(core: &CoreContractClient<'static>, dao_owner: &Address) -> Dao {
@@ -49,12 +51,12 @@ fn create_dao(core: &CoreContractClient<'static>, dao_owner: &Address) -> Dao {
     let id = "DIV".into_val(env);
     let name = "Deep Ink Ventures".into_val(env);
 
-    core.create_dao(&id, &name, &dao_owner)
+    core.create_dao(&id, &name, dao_owner)
 }
 
 fn mint_and_create_dao(clients: &Clients, dao_owner: &Address) -> Dao {
-    clients.native_asset_admin.mint(&dao_owner, &MAX_I128);
-    create_dao(&clients.core, &dao_owner)
+    clients.native_asset_admin.mint(dao_owner, &MAX_I128);
+    create_dao(&clients.core, dao_owner)
 }
 
 #[test]
@@ -62,7 +64,7 @@ fn mint_and_create_dao(clients: &Clients, dao_owner: &Address) -> Dao {
 fn cannot_initialize_twice() {
     let core = create_clients().core;
     let fake_id = Address::random(&core.env);
-    core.init(&fake_id, &fake_id);
+    core.init(&fake_id, &fake_id, &fake_id);
 }
 
 #[test]
@@ -88,6 +90,19 @@ fn create_a_dao() {
     assert_eq!(dao.owner, user);
 }
 
+#[test]
+#[should_panic(expected = "#29")]
+fn cannot_create_a_dao_with_a_long_name() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let env = &core.env;
+    let user = Address::random(env);
+    clients.native_asset_admin.mint(&user, &MAX_I128);
+
+    let name = Bytes::from_slice(env, &[b'D'; 257]);
+    core.create_dao(&"DIV".into_val(env), &name, &user);
+}
+
 #[test]
 #[should_panic(expected = "#9")]
 fn cannot_create_a_dao_without_funds() {
@@ -103,7 +118,7 @@ fn cannot_create_a_dao_twice() {
     let user = Address::random(&core.env);
 
     mint_and_create_dao(&clients, &user);
-    create_dao(&core, &user);
+    create_dao(core, &user);
 }
 
 #[test]
@@ -124,6 +139,250 @@ fn destroy_a_dao() {
     core.get_dao(&dao.id);
 }
 
+#[test]
+fn destroy_a_dao_removes_artifacts() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let env = &core.env;
+    let dao = mint_and_create_dao(&clients, &Address::random(env));
+    core.set_metadata(&dao.id, &"https://deep-ink.ventures".into_val(env), &"hash".into_val(env), &dao.owner);
+
+    let assets_wasm_hash = env.deployer().upload_contract_wasm(assets_contract::WASM);
+    let asset_id = core.issue_token(&dao.id, &dao.owner, &assets_wasm_hash, &BytesN::from_array(env, &[0; 32]), &0);
+    let asset = assets_contract::Client::new(env, &asset_id);
+    asset.mint(&dao.owner, &dao.owner, &1_000_000);
+
+    core.destroy_dao(&dao.id, &dao.owner);
+    assert_eq!(asset.balance(&dao.owner), 1_000_000);
+    assert_eq!(asset.core_address(), core.address);
+
+    // the id can be used again, without the metadata and asset of the destroyed DAO
+    create_dao(core, &dao.owner);
+    let asset_id = core.issue_token(&dao.id, &dao.owner, &assets_wasm_hash, &BytesN::from_array(env, &[1; 32]), &0);
+    assert_ne!(asset_id, asset.address);
+    assert_eq!(core.get_dao_count(), 1);
+}
+
+#[test]
+#[should_panic(expected = "#6")]
+fn destroy_a_dao_removes_metadata() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let env = &core.env;
+    let dao = mint_and_create_dao(&clients, &Address::random(env));
+    core.set_metadata(&dao.id, &"https://deep-ink.ventures".into_val(env), &"hash".into_val(env), &dao.owner);
+    core.destroy_dao(&dao.id, &dao.owner);
+
+    create_dao(core, &dao.owner);
+    core.get_metadata(&dao.id);
+}
+
+#[test]
+#[should_panic(expected = "#9")]
+fn destroy_a_dao_decommissions_asset() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let env = &core.env;
+    let dao = mint_and_create_dao(&clients, &Address::random(env));
+
+    let assets_wasm_hash = env.deployer().upload_contract_wasm(assets_contract::WASM);
+    let asset_id = core.issue_token(&dao.id, &dao.owner, &assets_wasm_hash, &BytesN::from_array(env, &[0; 32]), &0);
+    let asset = assets_contract::Client::new(env, &asset_id);
+    asset.mint(&dao.owner, &dao.owner, &1_000_000);
+    core.destroy_dao(&dao.id, &dao.owner);
+
+    asset.xfer(&dao.owner, &Address::random(env), &1_000);
+}
+
+#[test]
+fn destroy_a_dao_resets_its_proposals() {
+    let clients = create_clients();
+    let (core, votes) = (&clients.core, &clients.votes);
+    let env = &core.env;
+    let dao = mint_and_create_dao(&clients, &Address::random(env));
+    votes.set_configuration(&dao.id, &10_000, &1_000, &dao.owner);
+    votes.set_cancel_refund(&dao.id, &0, &dao.owner);
+    let proposal_id = votes.create_proposal(&dao.id, &dao.owner);
+    votes.cancel_proposal(&dao.id, &proposal_id, &dao.owner);
+    votes.claim_withheld_reserve(&dao.id, &dao.owner, &dao.owner);
+    votes.remove_configuration(&dao.id, &dao.owner);
+    core.destroy_dao(&dao.id, &dao.owner);
+
+    // a DAO created with the id starts without the proposals of the destroyed one
+    create_dao(core, &dao.owner);
+    votes.set_configuration(&dao.id, &10_000, &1_000, &dao.owner);
+    assert_eq!(votes.get_proposal_count(&dao.id), 0);
+    assert!(votes.get_archived_proposals(&dao.id, &0, &10).is_empty());
+    assert_eq!(votes.get_withheld_reserve(&dao.id), 0);
+    let proposal_id = votes.create_proposal(&dao.id, &dao.owner);
+    assert_eq!(votes.get_proposal_number(&proposal_id), 1);
+    assert_eq!(votes.get_active_proposals(&dao.id).len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "#30")]
+fn destroy_a_dao_with_open_proposals() {
+    let clients = create_clients();
+    let (core, votes) = (&clients.core, &clients.votes);
+    let env = &core.env;
+    let dao = mint_and_create_dao(&clients, &Address::random(env));
+    votes.set_configuration(&dao.id, &10_000, &1_000, &dao.owner);
+    votes.create_proposal(&dao.id, &dao.owner);
+    votes.remove_configuration(&dao.id, &dao.owner);
+
+    core.destroy_dao(&dao.id, &dao.owner);
+}
+
+#[test]
+#[should_panic(expected = "#32")]
+fn destroy_a_dao_with_withheld_reserve() {
+    let clients = create_clients();
+    let (core, votes) = (&clients.core, &clients.votes);
+    let env = &core.env;
+    let dao = mint_and_create_dao(&clients, &Address::random(env));
+    votes.set_configuration(&dao.id, &10_000, &1_000, &dao.owner);
+    votes.set_cancel_refund(&dao.id, &0, &dao.owner);
+    let proposal_id = votes.create_proposal(&dao.id, &dao.owner);
+    votes.cancel_proposal(&dao.id, &proposal_id, &dao.owner);
+    votes.remove_configuration(&dao.id, &dao.owner);
+
+    core.destroy_dao(&dao.id, &dao.owner);
+}
+
+#[test]
+fn dao_reserve_follows_protocol_config() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let env = &core.env;
+    let user = Address::random(env);
+    clients.native_asset_admin.mint(&user, &MAX_I128);
+
+    let mut config = core.get_protocol_config();
+    config.dao_reserve = 10_000_000;
+    core.set_protocol_config(&config, &clients.protocol_admin);
+    let dao = create_dao(core, &user);
+    assert_eq!(clients.native_asset.balance(&user), MAX_I128 - 10_000_000);
+
+    // the recorded reserve is returned, not the current one
+    config.dao_reserve = 20_000_000;
+    core.set_protocol_config(&config, &clients.protocol_admin);
+    core.destroy_dao(&dao.id, &user);
+    assert_eq!(clients.native_asset.balance(&user), MAX_I128);
+}
+
+#[test]
+#[should_panic(expected = "#24")]
+fn set_protocol_config_only_as_admin() {
+    let clients = create_clients();
+    let core = &clients.core;
+
+    core.set_protocol_config(&core.get_protocol_config(), &Address::random(&core.env));
+}
+
+#[test]
+#[should_panic(expected = "#25")]
+fn invalid_protocol_config() {
+    let clients = create_clients();
+    let core = &clients.core;
+
+    let mut config = core.get_protocol_config();
+    config.bump_threshold = config.bump_ledgers + 1;
+    core.set_protocol_config(&config, &clients.protocol_admin);
+}
+
+#[test]
+fn change_protocol_admin() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let new_admin = Address::random(&core.env);
+
+    core.propose_protocol_admin(&new_admin, &clients.protocol_admin);
+    assert_eq!(core.get_protocol_admin(), clients.protocol_admin);
+
+    core.accept_protocol_admin(&new_admin);
+    assert_eq!(core.get_protocol_admin(), new_admin);
+    core.set_protocol_config(&core.get_protocol_config(), &new_admin);
+}
+
+#[test]
+#[should_panic(expected = "#28")]
+fn accept_protocol_admin_only_as_proposed_admin() {
+    let clients = create_clients();
+    let core = &clients.core;
+
+    core.propose_protocol_admin(&Address::random(&core.env), &clients.protocol_admin);
+    core.accept_protocol_admin(&Address::random(&core.env));
+}
+
+#[test]
+#[should_panic(expected = "#25")]
+fn protocol_config_bumps_within_max_ttl() {
+    let clients = create_clients();
+    let core = &clients.core;
+
+    let mut config = core.get_protocol_config();
+    config.bump_ledgers = MAX_TTL + 1;
+    core.set_protocol_config(&config, &clients.protocol_admin);
+}
+
+#[test]
+#[should_panic(expected = "#24")]
+fn upgrade_only_as_protocol_admin() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let env = &core.env;
+
+    let wasm_hash = env.deployer().upload_contract_wasm(votes_contract::WASM);
+    core.upgrade(&wasm_hash, &Address::random(env));
+}
+
+#[test]
+#[should_panic(expected = "#26")]
+fn migrate_when_up_to_date() {
+    let clients = create_clients();
+    let core = &clients.core;
+
+    assert_eq!(core.version(), 1);
+    core.migrate(&clients.protocol_admin);
+}
+
+#[test]
+fn migrate_from_version_0() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let env = &core.env;
+
+    // version 0 kept neither a version nor the protocol config
+    env.as_contract(&core.address, || {
+        env.storage().instance().remove(&symbol_short!("VERSION"));
+        env.storage().instance().remove(&symbol_short!("PROTOCOL"));
+    });
+    assert_eq!(core.version(), 0);
+    assert_eq!(core.migrate(&clients.protocol_admin), 1);
+    assert_eq!(core.version(), 1);
+    let protocol_config = env.as_contract(&core.address, || {
+        env.storage().instance().get::<Symbol, ProtocolConfig>(&symbol_short!("PROTOCOL"))
+    });
+    assert_eq!(protocol_config, Some(ProtocolConfig::initial()));
+}
+
+#[test]
+fn upgrade_asset_keeps_balances() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let env = &core.env;
+    let dao = mint_and_create_dao(&clients, &Address::random(env));
+
+    let assets_wasm_hash = env.deployer().upload_contract_wasm(assets_contract::WASM);
+    let asset_id = core.issue_token(&dao.id, &dao.owner, &assets_wasm_hash, &BytesN::from_array(env, &[0; 32]), &0);
+    let asset = assets_contract::Client::new(env, &asset_id);
+    asset.mint(&dao.owner, &dao.owner, &1_000_000);
+
+    asset.upgrade(&assets_wasm_hash, &dao.owner);
+    assert_eq!(asset.balance(&dao.owner), 1_000_000);
+    assert_eq!(asset.version(), 1);
+}
+
 #[test]
 #[should_panic(expected = "#8")]
 fn destroy_a_dao_requires_destroy_configuration() {
@@ -158,7 +417,7 @@ fn destroy_a_dao_only_as_owner() {
 }
 
 #[test]
-fn change_dao_owner() {
+fn transfer_dao_ownership() {
     let clients = create_clients();
     let core = &clients.core;
     let env = &core.env;
@@ -166,13 +425,69 @@ fn change_dao_owner() {
     let dao = mint_and_create_dao(&clients, &user);
 
     let new_owner = Address::random(&core.env);
-    core.change_owner(&dao.id, &new_owner, &dao.owner);
+    core.propose_owner(&dao.id, &new_owner, &100, &dao.owner);
+    assert_eq!(core.get_dao(&dao.id).owner, dao.owner);
+    assert_eq!(core.get_ownership_transfer(&dao.id).new_owner, new_owner);
+
+    core.accept_ownership(&dao.id, &new_owner);
     assert_eq!(core.get_dao(&dao.id).owner, new_owner);
 }
 
+#[test]
+fn registry_lists_daos() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let env = &core.env;
+    let (alice, bob) = (Address::random(env), Address::random(env));
+    clients.native_asset_admin.mint(&alice, &MAX_I128);
+    clients.native_asset_admin.mint(&bob, &MAX_I128);
+
+    let ids: [Bytes; 3] = ["DIV".into_val(env), "ELIO".into_val(env), "INK".into_val(env)];
+    core.create_dao(&ids[0], &"Deep Ink Ventures".into_val(env), &alice);
+    core.create_dao(&ids[1], &"Elio".into_val(env), &alice);
+    core.create_dao(&ids[2], &"Ink".into_val(env), &bob);
+
+    assert_eq!(core.get_dao_count(), 3);
+    let page = core.list_daos(&1, &5);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get_unchecked(0).id, ids[1]);
+    assert_eq!(core.list_daos_by_owner(&alice).len(), 2);
+
+    core.propose_owner(&ids[1], &bob, &100, &alice);
+    core.accept_ownership(&ids[1], &bob);
+    assert_eq!(core.list_daos_by_owner(&alice).get_unchecked(0).id, ids[0]);
+    assert_eq!(core.list_daos_by_owner(&bob).len(), 2);
+
+    core.destroy_dao(&ids[0], &alice);
+    assert_eq!(core.get_dao_count(), 2);
+    assert_eq!(core.list_daos(&0, &5).get_unchecked(0).id, ids[2]);
+    assert!(core.list_daos_by_owner(&alice).is_empty());
+}
+
+#[test]
+fn destroy_a_dao_created_before_the_registry() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let env = &core.env;
+    let user = Address::random(env);
+    let dao = mint_and_create_dao(&clients, &user);
+
+    // daos of the former layout are not registered
+    env.as_contract(&core.address, || {
+        env.storage().persistent().remove(&RegistryKey::Position(dao.id.clone()));
+        env.storage().persistent().remove(&RegistryKey::Entry(0));
+        env.storage().persistent().remove(&RegistryKey::Count);
+    });
+    assert_eq!(core.list_daos(&0, &5).len(), 0);
+
+    core.destroy_dao(&dao.id, &user);
+    assert_eq!(core.get_dao_count(), 0);
+    assert!(core.list_daos_by_owner(&user).is_empty());
+}
+
 #[test]
 #[should_panic(expected = "#3")]
-fn change_dao_owner_only_as_owner() {
+fn propose_dao_owner_only_as_owner() {
     let clients = create_clients();
     let core = &clients.core;
     let env = &core.env;
@@ -180,7 +495,66 @@ fn change_dao_owner_only_as_owner() {
     let dao = mint_and_create_dao(&clients, &user);
 
     let new_owner = Address::random(&core.env);
-    core.change_owner(&dao.id, &new_owner, &new_owner);
+    core.propose_owner(&dao.id, &new_owner, &100, &new_owner);
+}
+
+#[test]
+#[should_panic(expected = "#12")]
+fn accept_ownership_only_as_proposed_owner() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let env = &core.env;
+    let user = Address::random(env);
+    let dao = mint_and_create_dao(&clients, &user);
+
+    core.propose_owner(&dao.id, &Address::random(env), &100, &dao.owner);
+    core.accept_ownership(&dao.id, &Address::random(env));
+}
+
+#[test]
+fn ownership_transfer_outlives_max_ttl() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let env = &core.env;
+    let user = Address::random(env);
+    let dao = mint_and_create_dao(&clients, &user);
+
+    env.ledger().with_mut(|ledger| ledger.max_entry_expiration = MAX_TTL + 1);
+    let new_owner = Address::random(env);
+    core.propose_owner(&dao.id, &new_owner, &(MAX_TTL * 2), &dao.owner);
+    core.accept_ownership(&dao.id, &new_owner);
+
+    assert_eq!(core.get_dao(&dao.id).owner, new_owner);
+}
+
+#[test]
+#[should_panic(expected = "#13")]
+fn ownership_transfer_expires() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let env = &core.env;
+    let user = Address::random(env);
+    let dao = mint_and_create_dao(&clients, &user);
+
+    let new_owner = Address::random(env);
+    core.propose_owner(&dao.id, &new_owner, &100, &dao.owner);
+    env.ledger().with_mut(|ledger| ledger.sequence_number = 101);
+    core.accept_ownership(&dao.id, &new_owner);
+}
+
+#[test]
+#[should_panic(expected = "#11")]
+fn cancel_ownership_transfer() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let env = &core.env;
+    let user = Address::random(env);
+    let dao = mint_and_create_dao(&clients, &user);
+
+    let new_owner = Address::random(env);
+    core.propose_owner(&dao.id, &new_owner, &100, &dao.owner);
+    core.cancel_ownership_transfer(&dao.id, &dao.owner);
+    core.accept_ownership(&dao.id, &new_owner);
 }
 
 #[test]
@@ -203,7 +577,7 @@ fn set_metadata() {
 }
 
 #[test]
-#[should_panic(expected = "#3")]
+#[should_panic(expected = "#27")]
 fn set_metadata_only_owner() {
     let clients = create_clients();
     let core = &clients.core;
@@ -219,6 +593,126 @@ fn set_metadata_only_owner() {
     core.set_metadata(&dao.id, &url, &hash, &whoever);
 }
 
+#[test]
+fn grant_and_revoke_role() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let env = &core.env;
+    let user = Address::random(env);
+    let dao = mint_and_create_dao(&clients, &user);
+
+    let url = "https://deep-ink.ventures".into_val(&core.env);
+    let hash =
+        "e337ba02296d560d167b4c301505f1252c29bcf614893a806043d33fd3509181".into_val(&core.env);
+
+    let editor = Address::random(env);
+    assert!(core.has_role(&dao.id, &Role::MetadataEditor, &dao.owner));
+    assert!(!core.has_role(&dao.id, &Role::MetadataEditor, &editor));
+
+    core.grant_role(&dao.id, &Role::MetadataEditor, &editor, &dao.owner);
+    assert!(core.has_role(&dao.id, &Role::MetadataEditor, &editor));
+    assert!(!core.has_role(&dao.id, &Role::HookpointManager, &editor));
+    core.set_metadata(&dao.id, &url, &hash, &editor);
+    assert_eq!(core.get_metadata(&dao.id).url, url);
+
+    core.revoke_role(&dao.id, &Role::MetadataEditor, &editor, &dao.owner);
+    assert!(!core.has_role(&dao.id, &Role::MetadataEditor, &editor));
+}
+
+#[test]
+#[should_panic(expected = "#27")]
+fn revoked_role_is_missing() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let env = &core.env;
+    let user = Address::random(env);
+    let dao = mint_and_create_dao(&clients, &user);
+
+    let url = "https://deep-ink.ventures".into_val(&core.env);
+    let hash =
+        "e337ba02296d560d167b4c301505f1252c29bcf614893a806043d33fd3509181".into_val(&core.env);
+
+    let editor = Address::random(env);
+    core.grant_role(&dao.id, &Role::MetadataEditor, &editor, &dao.owner);
+    core.revoke_role(&dao.id, &Role::MetadataEditor, &editor, &dao.owner);
+    core.set_metadata(&dao.id, &url, &hash, &editor);
+}
+
+#[test]
+fn roles_are_revoked_on_ownership_change() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let env = &core.env;
+    let user = Address::random(env);
+    let dao = mint_and_create_dao(&clients, &user);
+
+    let admin = Address::random(env);
+    core.grant_role(&dao.id, &Role::Admin, &admin, &dao.owner);
+
+    let new_owner = Address::random(env);
+    core.propose_owner(&dao.id, &new_owner, &100, &dao.owner);
+    core.accept_ownership(&dao.id, &new_owner);
+
+    assert!(!core.has_role(&dao.id, &Role::Admin, &admin));
+    assert!(!core.has_role(&dao.id, &Role::Admin, &dao.owner));
+    assert!(core.has_role(&dao.id, &Role::Admin, &new_owner));
+}
+
+#[test]
+fn admin_grants_roles() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let env = &core.env;
+    let user = Address::random(env);
+    let dao = mint_and_create_dao(&clients, &user);
+
+    let admin = Address::random(env);
+    let moderator = Address::random(env);
+    core.grant_role(&dao.id, &Role::Admin, &admin, &dao.owner);
+    core.grant_role(&dao.id, &Role::ProposalModerator, &moderator, &admin);
+    assert!(core.has_role(&dao.id, &Role::ProposalModerator, &moderator));
+}
+
+#[test]
+#[should_panic(expected = "#27")]
+fn grant_role_only_as_admin() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let env = &core.env;
+    let user = Address::random(env);
+    let dao = mint_and_create_dao(&clients, &user);
+
+    let editor = Address::random(env);
+    core.grant_role(&dao.id, &Role::MetadataEditor, &editor, &dao.owner);
+    core.grant_role(&dao.id, &Role::Admin, &editor, &editor);
+}
+
+#[test]
+#[should_panic(expected = "#14")]
+fn grant_role_only_once() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let env = &core.env;
+    let user = Address::random(env);
+    let dao = mint_and_create_dao(&clients, &user);
+
+    let editor = Address::random(env);
+    core.grant_role(&dao.id, &Role::MetadataEditor, &editor, &dao.owner);
+    core.grant_role(&dao.id, &Role::MetadataEditor, &editor, &dao.owner);
+}
+
+#[test]
+#[should_panic(expected = "#15")]
+fn revoke_role_requires_grant() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let env = &core.env;
+    let user = Address::random(env);
+    let dao = mint_and_create_dao(&clients, &user);
+
+    core.revoke_role(&dao.id, &Role::Minter, &Address::random(env), &dao.owner);
+}
+
 #[test]
 #[should_panic(expected = "#6")]
 fn non_existing_meta_panics() {
@@ -244,13 +738,13 @@ fn issue_token_once() {
 
     log!(env, "issuing token");
     let salt = BytesN::from_array(env, &[0; 32]);
-    core.issue_token(&dao.id, &dao.owner, &assets_wasm_hash, &salt);
+    core.issue_token(&dao.id, &dao.owner, &assets_wasm_hash, &salt, &0);
 
     log!(env, "getting DAO asset id");
     let asset_id = core.get_dao_asset_id(&dao.id);
     let asset_core = assets_contract::Client::new(env, &asset_id);
-    assert_eq!(dao.id, asset_core.symbol());
-    assert_eq!(dao.name, asset_core.name());
+    assert_eq!(String::from_slice(env, "DIV"), asset_core.symbol());
+    assert_eq!(String::from_slice(env, "Deep Ink Ventures"), asset_core.name());
     assert_eq!(dao.owner, asset_core.owner());
     assert_eq!(core.address, asset_core.core_address());
 
@@ -259,7 +753,7 @@ fn issue_token_once() {
 
     log!(env, "minting token");
     let supply = 1_000_000;
-    asset_core.mint(&dao.owner, &supply);
+    asset_core.mint(&dao.owner, &dao.owner, &supply);
     assert_eq!(supply, asset_core.balance(&dao.owner));
 }
 
@@ -278,8 +772,8 @@ fn cannot_issue_token_twice() {
     log!(env, "issuing token twice");
     let salt = BytesN::from_array(&core.env, &[0; 32]);
     let salt2 = BytesN::from_array(&core.env, &[1; 32]);
-    core.issue_token(&dao.id, &dao.owner, &assets_wasm_hash, &salt);
-    core.issue_token(&dao.id, &dao.owner, &assets_wasm_hash, &salt2);
+    core.issue_token(&dao.id, &dao.owner, &assets_wasm_hash, &salt, &0);
+    core.issue_token(&dao.id, &dao.owner, &assets_wasm_hash, &salt2, &0);
 }
 
 #[test]
@@ -292,3 +786,89 @@ fn cannot_get_asset_id_if_non_existing() {
     let dao = mint_and_create_dao(&clients, &user);
     core.get_dao_asset_id(&dao.id);
 }
+
+/// Hands a fresh DAO to a 2-of-3 council and proposes a metadata update as the first signer
+fn setup_council_action(clients: &Clients) -> (Dao, [Address; 3], u32) {
+    let core = &clients.core;
+    let env = &core.env;
+    let dao = mint_and_create_dao(clients, &Address::random(env));
+
+    let signers = [Address::random(env), Address::random(env), Address::random(env)];
+    let signer_vec = vec![env, signers[0].clone(), signers[1].clone(), signers[2].clone()];
+    core.set_council(&dao.id, &signer_vec, &2, &dao.owner);
+    assert_eq!(core.get_dao(&dao.id).owner, core.address);
+
+    let url: Bytes = "https://deep-ink.ventures".into_val(env);
+    let hash: Bytes = "e337ba02296d560d167b4c301505f1252c29bcf614893a806043d33fd3509181".into_val(env);
+    let action = CouncilAction {
+        contract_id: core.address.clone(),
+        func: Symbol::new(env, "set_metadata"),
+        args: (dao.id.clone(), url, hash, core.address.clone()).into_val(env),
+    };
+    let action_id = core.propose_council_action(&dao.id, &action, &signers[0]);
+    (dao, signers, action_id)
+}
+
+#[test]
+fn council_executes_action() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let (dao, signers, action_id) = setup_council_action(&clients);
+
+    core.approve_council_action(&dao.id, &action_id, &signers[1]);
+    assert_eq!(core.get_council_action(&dao.id, &action_id).approvals.len(), 2);
+    core.execute_council_action(&dao.id, &action_id);
+
+    assert_eq!(core.get_metadata(&dao.id).url, "https://deep-ink.ventures".into_val(&core.env));
+}
+
+#[test]
+#[should_panic(expected = "#22")]
+fn council_action_requires_threshold() {
+    let clients = create_clients();
+    let (dao, _, action_id) = setup_council_action(&clients);
+
+    clients.core.execute_council_action(&dao.id, &action_id);
+}
+
+#[test]
+#[should_panic(expected = "#18")]
+fn council_action_only_approved_by_signers() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let (dao, _, action_id) = setup_council_action(&clients);
+
+    core.approve_council_action(&dao.id, &action_id, &Address::random(&core.env));
+}
+
+#[test]
+#[should_panic(expected = "#20")]
+fn council_action_approved_once_per_signer() {
+    let clients = create_clients();
+    let (dao, signers, action_id) = setup_council_action(&clients);
+
+    clients.core.approve_council_action(&dao.id, &action_id, &signers[0]);
+}
+
+#[test]
+#[should_panic(expected = "#21")]
+fn council_action_executed_once() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let (dao, signers, action_id) = setup_council_action(&clients);
+
+    core.approve_council_action(&dao.id, &action_id, &signers[1]);
+    core.execute_council_action(&dao.id, &action_id);
+    core.execute_council_action(&dao.id, &action_id);
+}
+
+#[test]
+#[should_panic(expected = "#3")]
+fn council_dao_rejects_direct_owner_calls() {
+    let clients = create_clients();
+    let core = &clients.core;
+    let env = &core.env;
+    let (dao, _, _) = setup_council_action(&clients);
+
+    core.set_metadata(&dao.id, &"https://deep-ink.ventures".into_val(env), &"hash".into_val(env), &core.address);
+}
@@ -1,9 +1,12 @@
 use soroban_sdk::{
-    contracttype, panic_with_error, symbol_short, Address, Bytes, BytesN, Env, IntoVal,
+    contracttype, panic_with_error, symbol_short, Address, Bytes, BytesN, Env, IntoVal, Map, Symbol,
+    Val, Vec,
 };
 
 use crate::error::CoreError;
-use crate::events::{AssetCreatedEventData, ASSET, CREATED};
+use crate::events::{
+    AssetCreatedEventData, AssetDecommissionedEventData, ASSET, CREATED, DECOMMISSIONED,
+};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -20,28 +23,140 @@ pub struct Metadata {
     pub hash: Bytes,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OwnershipTransfer {
+    pub new_owner: Address,
+    /// The last ledger the transfer can be accepted at
+    pub expiration_ledger: u32,
+}
+
+/// Permissions the owner can grant to other accounts, the owner holds all of them
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Role {
+    /// Issue the token, configure the proposals and grant roles
+    Admin,
+    MetadataEditor,
+    HookpointManager,
+    /// Fault proposals
+    ProposalModerator,
+    /// Mint the dao asset
+    Minter,
+}
+
+/// Signers that own a DAO together, through the core contract as the owner of the DAO
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Council {
+    pub signers: Vec<Address>,
+    /// The number of approvals a council action needs to be executed
+    pub threshold: u32,
+    /// Actions proposed before the council was set are discarded
+    pub first_action_id: u32,
+}
+
+/// A call of an owner function in the core or votes contract with the core contract as owner
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CouncilAction {
+    pub contract_id: Address,
+    pub func: Symbol,
+    pub args: Vec<Val>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CouncilProposal {
+    pub action: CouncilAction,
+    pub approvals: Vec<Address>,
+    pub executed: bool,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub enum DaoArtifact {
+    /// The reserve paid on creation, it is returned on destruction
+    Reserve(Bytes),
     Metadata(Bytes),
     Asset(Bytes),
     Hookpoint(Bytes),
+    OwnershipTransfer(Bytes),
+    Roles(Bytes),
+    Council(Bytes),
+    CouncilActionCount(Bytes),
+    CouncilAction(Bytes, u32),
+    CouncilExecuting(Bytes),
+}
+
+/// Keys of the registry that lists all DAOs
+#[derive(Clone)]
+#[contracttype]
+pub enum RegistryKey {
+    Count,
+    Entry(u32),
+    Position(Bytes),
+    Owner(Address),
+}
+
+/// Protocol wide parameters, they can be changed by the protocol admin
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProtocolConfig {
+    /// The native asset amount reserved on the creation of a DAO
+    pub dao_reserve: i128,
+    /// The native asset amount deposited on the creation of a proposal
+    pub proposal_reserve: i128,
+    /// The max number of active proposals of a DAO
+    pub max_active_proposals: u32,
+    /// The ledgers storage entries are kept alive for
+    pub bump_ledgers: u32,
+    /// Storage entries are bumped once they are kept alive for less ledgers than this
+    pub bump_threshold: u32,
 }
 
+pub const XLM: i128 = 10_000_000;
 pub const A_WEEK_IN_LEDGERS: u32 = 100800;
 pub const BUMP_A_MONTH: u32 = 432000;
 pub const BUMP_A_MONTH_THRESHOLD: u32 = 432000 - A_WEEK_IN_LEDGERS;
+/// The max ledgers an entry can be bumped by, the max entry expiration of the network (180 days)
+/// includes the current ledger
+pub const MAX_TTL: u32 = 3_110_400 - 1;
+
+/// The layout of the storage. Version 0 is the layout before versioning, it kept no protocol
+/// config and read the initial one instead.
+pub const STORAGE_VERSION: u32 = 1;
+
+/// The max length of the id and the name of a DAO, the DAO asset returns them as `String`
+pub const MAX_NAME_LENGTH: u32 = 256;
+
+const VERSION: Symbol = symbol_short!("VERSION");
+const PROTOCOL_CONFIG: Symbol = symbol_short!("PROTOCOL");
+const PROTOCOL_ADMIN: Symbol = symbol_short!("ADMIN");
+const PROPOSED_ADMIN: Symbol = symbol_short!("PROP_ADM");
 
 impl Dao {
     /// Bumps all keys associated with a dao
     pub fn bump(env: &Env, id: Bytes) {
+        let config = ProtocolConfig::load(env);
         env.storage()
             .instance()
-            .bump(BUMP_A_MONTH_THRESHOLD, BUMP_A_MONTH);
+            .bump(config.bump_threshold, config.bump_ledgers);
         env.storage()
             .persistent()
-            .bump(&id, BUMP_A_MONTH_THRESHOLD, BUMP_A_MONTH);
+            .bump(&id, config.bump_threshold, config.bump_ledgers);
 
+        if env
+            .storage()
+            .persistent()
+            .has(&DaoArtifact::Reserve(id.clone()))
+        {
+            env.storage().persistent().bump(
+                &DaoArtifact::Reserve(id.clone()),
+                config.bump_threshold,
+                config.bump_ledgers,
+            );
+        }
         if env
             .storage()
             .persistent()
@@ -49,8 +164,8 @@ impl Dao {
         {
             env.storage().persistent().bump(
                 &DaoArtifact::Metadata(id.clone()),
-                BUMP_A_MONTH_THRESHOLD,
-                BUMP_A_MONTH,
+                config.bump_threshold,
+                config.bump_ledgers,
             );
         }
         if env
@@ -60,8 +175,8 @@ impl Dao {
         {
             env.storage().persistent().bump(
                 &DaoArtifact::Hookpoint(id.clone()),
-                BUMP_A_MONTH_THRESHOLD,
-                BUMP_A_MONTH,
+                config.bump_threshold,
+                config.bump_ledgers,
             );
         }
         if env
@@ -71,8 +186,37 @@ impl Dao {
         {
             env.storage().persistent().bump(
                 &DaoArtifact::Asset(id.clone()),
-                BUMP_A_MONTH_THRESHOLD,
-                BUMP_A_MONTH,
+                config.bump_threshold,
+                config.bump_ledgers,
+            );
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DaoArtifact::Roles(id.clone()))
+        {
+            env.storage().persistent().bump(
+                &DaoArtifact::Roles(id.clone()),
+                config.bump_threshold,
+                config.bump_ledgers,
+            );
+        }
+        if let Some(dao) = env.storage().persistent().get::<_, Dao>(&id) {
+            let owner_key = RegistryKey::Owner(dao.owner);
+            if env.storage().persistent().has(&owner_key) {
+                env.storage().persistent().bump(&owner_key, config.bump_threshold, config.bump_ledgers);
+            }
+        }
+        if env.storage().persistent().has(&RegistryKey::Count) {
+            env.storage().persistent().bump(&RegistryKey::Count, config.bump_threshold, config.bump_ledgers);
+        }
+        let position_key = RegistryKey::Position(id.clone());
+        if let Some(position) = env.storage().persistent().get::<_, u32>(&position_key) {
+            env.storage().persistent().bump(&position_key, config.bump_threshold, config.bump_ledgers);
+            env.storage().persistent().bump(
+                &RegistryKey::Entry(position),
+                config.bump_threshold,
+                config.bump_ledgers,
             );
         }
     }
@@ -82,12 +226,16 @@ impl Dao {
         if Self::exists(env, &id) {
             panic_with_error!(env, CoreError::DaoAlreadyExists)
         }
+        if id.len() > MAX_NAME_LENGTH || name.len() > MAX_NAME_LENGTH {
+            panic_with_error!(env, CoreError::NameTooLong)
+        }
         let dao = Dao {
             id: id.clone(),
             name,
             owner,
         };
         env.storage().persistent().set(&dao.id, &dao);
+        dao.register(env);
         Dao::bump(env, id);
         dao
     }
@@ -103,7 +251,7 @@ impl Dao {
 
     /// Loads the DAO but with checks for the owner
     pub fn load_for_owner(env: &Env, id: &Bytes, owner: &Address) -> Self {
-        owner.require_auth();
+        Self::authorize(env, id, owner);
 
         let dao = Self::load(env, id);
         if owner != &dao.owner {
@@ -113,6 +261,27 @@ impl Dao {
         dao
     }
 
+    /// Loads the DAO but with checks for the role of the account
+    pub fn load_for_role(env: &Env, id: &Bytes, role: Role, account: &Address) -> Self {
+        Self::authorize(env, id, account);
+
+        let dao = Self::load(env, id);
+        if !dao.has_role(env, role, account) {
+            panic_with_error!(env, CoreError::MissingRole)
+        }
+        dao
+    }
+
+    /// Requires the auth of the account. The core contract owns the DAOs of a council and is
+    /// authorized while it executes their council actions.
+    fn authorize(env: &Env, id: &Bytes, account: &Address) {
+        if account != &env.current_contract_address() {
+            account.require_auth();
+        } else if !env.storage().temporary().has(&DaoArtifact::CouncilExecuting(id.clone())) {
+            panic_with_error!(env, CoreError::NotDaoOwner)
+        }
+    }
+
     /// Checks if a DAO exists
     pub fn exists(env: &Env, id: &Bytes) -> bool {
         env.storage().persistent().has(id)
@@ -125,6 +294,7 @@ impl Dao {
         env: &Env,
         assets_wasm_hash: BytesN<32>,
         asset_salt: BytesN<32>,
+        max_supply: i128,
     ) -> Address {
         let key = DaoArtifact::Asset(self.id.clone());
 
@@ -143,7 +313,7 @@ impl Dao {
 
         let core_address = env.current_contract_address();
         let init_args =
-            (self.id.clone(), self.name, self.owner.clone(), core_address).into_val(env);
+            (self.id.clone(), self.name, self.owner.clone(), core_address, max_supply).into_val(env);
         env.invoke_contract::<()>(&asset_id, &init_fn, init_args);
 
         env.events().publish(
@@ -167,9 +337,104 @@ impl Dao {
         env.storage().persistent().get(&key).unwrap()
     }
 
-    /// Destroys a dao
+    /// Destroys a dao with all of its artifacts, so the id can be used for a fresh dao
+    ///
+    /// Council actions are kept, the action count keeps their ids unique if the id is reused.
     pub fn destroy(&self, env: &Env) {
+        self.decommission_asset(env);
+        self.unregister(env);
         env.storage().persistent().remove(&self.id);
+        for key in [
+            DaoArtifact::Reserve(self.id.clone()),
+            DaoArtifact::Metadata(self.id.clone()),
+            DaoArtifact::Hookpoint(self.id.clone()),
+            DaoArtifact::Asset(self.id.clone()),
+            DaoArtifact::OwnershipTransfer(self.id.clone()),
+            DaoArtifact::Roles(self.id.clone()),
+            DaoArtifact::Council(self.id.clone()),
+        ] {
+            env.storage().persistent().remove(&key);
+        }
+    }
+
+    pub fn set_reserve(&self, env: &Env, reserve: i128) {
+        env.storage().persistent().set(&DaoArtifact::Reserve(self.id.clone()), &reserve);
+        Dao::bump(env, self.id.clone());
+    }
+
+    /// DAOs created before the reserves were recorded paid the initial reserve
+    pub fn get_reserve(&self, env: &Env) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DaoArtifact::Reserve(self.id.clone()))
+            .unwrap_or(ProtocolConfig::initial().dao_reserve)
+    }
+
+    /// Freezes the issued asset, unless its owner already attached it to another core
+    fn decommission_asset(&self, env: &Env) {
+        let asset_id: Address = match env.storage().persistent().get(&DaoArtifact::Asset(self.id.clone())) {
+            Some(asset_id) => asset_id,
+            None => return,
+        };
+        let core_address = env.current_contract_address();
+        let asset_core: Address = env.invoke_contract(&asset_id, &Symbol::new(env, "core_address"), Vec::new(env));
+        if asset_core != core_address {
+            return;
+        }
+        env.invoke_contract::<()>(&asset_id, &Symbol::new(env, "decommission"), Vec::new(env));
+        env.events().publish(
+            (ASSET, DECOMMISSIONED, self.id.clone()),
+            AssetDecommissionedEventData {
+                dao_id: self.id.clone(),
+                asset_id,
+            },
+        );
+    }
+
+    fn read_roles(&self, env: &Env) -> Map<Address, Vec<Role>> {
+        env.storage()
+            .persistent()
+            .get(&DaoArtifact::Roles(self.id.clone()))
+            .unwrap_or(Map::new(env))
+    }
+
+    /// Checks if the account is the owner or was granted the role
+    pub fn has_role(&self, env: &Env, role: Role, account: &Address) -> bool {
+        if account == &self.owner {
+            return true;
+        }
+        match self.read_roles(env).get(account.clone()) {
+            Some(roles) => roles.contains(role),
+            None => false,
+        }
+    }
+
+    pub fn grant_role(&self, env: &Env, role: Role, account: Address) {
+        let mut all_roles = self.read_roles(env);
+        let mut roles = all_roles.get(account.clone()).unwrap_or(Vec::new(env));
+        if roles.contains(role) {
+            panic_with_error!(env, CoreError::RoleAlreadyGranted)
+        }
+        roles.push_back(role);
+        all_roles.set(account, roles);
+        env.storage().persistent().set(&DaoArtifact::Roles(self.id.clone()), &all_roles);
+        Dao::bump(env, self.id.clone());
+    }
+
+    pub fn revoke_role(&self, env: &Env, role: Role, account: Address) {
+        let mut all_roles = self.read_roles(env);
+        let mut roles = all_roles.get(account.clone()).unwrap_or(Vec::new(env));
+        match roles.first_index_of(role) {
+            Some(i) => roles.remove(i),
+            None => panic_with_error!(env, CoreError::RoleNotGranted),
+        };
+        if roles.is_empty() {
+            all_roles.remove(account);
+        } else {
+            all_roles.set(account, roles);
+        }
+        env.storage().persistent().set(&DaoArtifact::Roles(self.id.clone()), &all_roles);
+        Dao::bump(env, self.id.clone());
     }
 
     /// Saves a dao
@@ -177,6 +442,105 @@ impl Dao {
         env.storage().persistent().set(&self.id, self);
         Dao::bump(env, self.id.clone());
     }
+
+    /// Hands the dao to a new owner in the owner index and revokes the roles granted by the previous owner
+    pub fn change_owner(&mut self, env: &Env, new_owner: Address) {
+        env.storage().persistent().remove(&DaoArtifact::Roles(self.id.clone()));
+        Self::remove_from_owner(env, &self.id, &self.owner);
+        Self::add_to_owner(env, &self.id, &new_owner);
+        self.owner = new_owner;
+        self.save(env);
+    }
+
+    /// Gets the number of registered daos
+    pub fn count(env: &Env) -> u32 {
+        env.storage().persistent().get(&RegistryKey::Count).unwrap_or(0)
+    }
+
+    /// Lists the registered daos, destroying a dao moves the last dao to its position
+    pub fn list(env: &Env, start: u32, limit: u32) -> Vec<Dao> {
+        let mut daos = Vec::new(env);
+        let end = Self::count(env).min(start.saturating_add(limit));
+        for i in start..end {
+            // entries that expired are skipped
+            if let Some(id) = env.storage().persistent().get::<_, Bytes>(&RegistryKey::Entry(i)) {
+                daos.push_back(Self::load(env, &id));
+            }
+        }
+        daos
+    }
+
+    /// Lists the daos of an owner
+    pub fn list_by_owner(env: &Env, owner: &Address) -> Vec<Dao> {
+        let mut daos = Vec::new(env);
+        for id in Self::read_owner_index(env, owner).iter() {
+            daos.push_back(Self::load(env, &id));
+        }
+        daos
+    }
+
+    fn register(&self, env: &Env) {
+        let position = Self::count(env);
+        Self::write_entry(env, position, &self.id);
+        Self::write_registry(env, &RegistryKey::Count, &(position + 1));
+        Self::add_to_owner(env, &self.id, &self.owner);
+    }
+
+    fn unregister(&self, env: &Env) {
+        Self::remove_from_owner(env, &self.id, &self.owner);
+        // daos created before the registry have no position
+        let position_key = RegistryKey::Position(self.id.clone());
+        let position: u32 = match env.storage().persistent().get(&position_key) {
+            Some(position) => position,
+            None => return,
+        };
+        let last = Self::count(env) - 1;
+        if position != last {
+            if let Some(last_id) = env.storage().persistent().get::<_, Bytes>(&RegistryKey::Entry(last)) {
+                Self::write_entry(env, position, &last_id);
+            }
+        }
+        env.storage().persistent().remove(&RegistryKey::Entry(last));
+        env.storage().persistent().remove(&position_key);
+        Self::write_registry(env, &RegistryKey::Count, &last);
+    }
+
+    fn write_entry(env: &Env, position: u32, id: &Bytes) {
+        Self::write_registry(env, &RegistryKey::Entry(position), id);
+        Self::write_registry(env, &RegistryKey::Position(id.clone()), &position);
+    }
+
+    fn read_owner_index(env: &Env, owner: &Address) -> Vec<Bytes> {
+        env.storage()
+            .persistent()
+            .get(&RegistryKey::Owner(owner.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    fn add_to_owner(env: &Env, id: &Bytes, owner: &Address) {
+        let mut ids = Self::read_owner_index(env, owner);
+        ids.push_back(id.clone());
+        Self::write_registry(env, &RegistryKey::Owner(owner.clone()), &ids);
+    }
+
+    fn remove_from_owner(env: &Env, id: &Bytes, owner: &Address) {
+        let key = RegistryKey::Owner(owner.clone());
+        let mut ids = Self::read_owner_index(env, owner);
+        if let Some(i) = ids.first_index_of(id) {
+            ids.remove(i);
+        }
+        if ids.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            Self::write_registry(env, &key, &ids);
+        }
+    }
+
+    fn write_registry<V: IntoVal<Env, Val>>(env: &Env, key: &RegistryKey, value: &V) {
+        let config = ProtocolConfig::load(env);
+        env.storage().persistent().set(key, value);
+        env.storage().persistent().bump(key, config.bump_threshold, config.bump_ledgers);
+    }
 }
 
 impl Metadata {
@@ -210,3 +574,277 @@ impl Metadata {
             .has(&DaoArtifact::Metadata(dao_id.clone()))
     }
 }
+
+impl OwnershipTransfer {
+    /// Proposes a new owner for the dao, replacing a pending transfer
+    pub fn propose(env: &Env, dao_id: Bytes, new_owner: Address, expiration_ledger: u32) -> Self {
+        let config = ProtocolConfig::load(env);
+        if expiration_ledger < env.ledger().sequence() {
+            panic_with_error!(env, CoreError::InvalidExpiration)
+        }
+        let transfer = OwnershipTransfer {
+            new_owner,
+            expiration_ledger,
+        };
+        let key = DaoArtifact::OwnershipTransfer(dao_id);
+        env.storage().persistent().set(&key, &transfer);
+        env.storage().persistent().bump(
+            &key,
+            config.bump_threshold,
+            config.bump_ledgers.max(expiration_ledger - env.ledger().sequence()).min(MAX_TTL),
+        );
+        transfer
+    }
+
+    /// Loads the pending transfer of the dao
+    pub fn load(env: &Env, dao_id: &Bytes) -> Self {
+        let key = DaoArtifact::OwnershipTransfer(dao_id.clone());
+        match env.storage().persistent().get(&key) {
+            Some(transfer) => transfer,
+            None => panic_with_error!(env, CoreError::NoOwnershipTransfer),
+        }
+    }
+
+    /// Removes the pending transfer after checking it was proposed to the new owner and has not expired
+    pub fn accept(env: &Env, dao_id: &Bytes, new_owner: &Address) {
+        new_owner.require_auth();
+
+        let transfer = Self::load(env, dao_id);
+        if &transfer.new_owner != new_owner {
+            panic_with_error!(env, CoreError::NotProposedOwner)
+        }
+        if transfer.expiration_ledger < env.ledger().sequence() {
+            panic_with_error!(env, CoreError::OwnershipTransferExpired)
+        }
+        Self::remove(env, dao_id);
+    }
+
+    /// Removes the pending transfer of the dao
+    pub fn remove(env: &Env, dao_id: &Bytes) {
+        // loading panics if there is no transfer to remove
+        Self::load(env, dao_id);
+        env.storage()
+            .persistent()
+            .remove(&DaoArtifact::OwnershipTransfer(dao_id.clone()));
+    }
+}
+
+impl Council {
+    /// Sets the council of the dao, pending actions of a previous council are discarded
+    pub fn set(env: &Env, dao_id: Bytes, signers: Vec<Address>, threshold: u32) -> Self {
+        if threshold == 0 || threshold > signers.len() {
+            panic_with_error!(env, CoreError::InvalidCouncil)
+        }
+        for (i, signer) in signers.iter().enumerate() {
+            if signers.first_index_of(signer) != Some(i as u32) {
+                panic_with_error!(env, CoreError::InvalidCouncil)
+            }
+        }
+        let council = Council {
+            signers,
+            threshold,
+            first_action_id: Self::read_action_count(env, &dao_id),
+        };
+        env.storage().persistent().set(&DaoArtifact::Council(dao_id.clone()), &council);
+        Dao::bump(env, dao_id.clone());
+        Self::bump(env, &dao_id);
+        council
+    }
+
+    pub fn load(env: &Env, dao_id: &Bytes) -> Self {
+        match env.storage().persistent().get(&DaoArtifact::Council(dao_id.clone())) {
+            Some(council) => {
+                Self::bump(env, dao_id);
+                council
+            }
+            None => panic_with_error!(env, CoreError::NoCouncil),
+        }
+    }
+
+    pub fn remove(env: &Env, dao_id: &Bytes) {
+        env.storage().persistent().remove(&DaoArtifact::Council(dao_id.clone()));
+    }
+
+    fn bump(env: &Env, dao_id: &Bytes) {
+        let config = ProtocolConfig::load(env);
+        env.storage().persistent().bump(
+            &DaoArtifact::Council(dao_id.clone()),
+            config.bump_threshold,
+            config.bump_ledgers,
+        );
+    }
+
+    fn check_signer(&self, env: &Env, signer: &Address) {
+        signer.require_auth();
+        if !self.signers.contains(signer) {
+            panic_with_error!(env, CoreError::NotCouncilSigner)
+        }
+    }
+
+    fn read_action_count(env: &Env, dao_id: &Bytes) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DaoArtifact::CouncilActionCount(dao_id.clone()))
+            .unwrap_or(0)
+    }
+
+    /// Proposes an action, the proposing signer approves it
+    pub fn propose(&self, env: &Env, dao_id: &Bytes, action: CouncilAction, signer: Address) -> u32 {
+        let config = ProtocolConfig::load(env);
+        self.check_signer(env, &signer);
+        let id = Self::read_action_count(env, dao_id);
+        let proposal = CouncilProposal {
+            action,
+            approvals: Vec::from_array(env, [signer]),
+            executed: false,
+        };
+        let count_key = DaoArtifact::CouncilActionCount(dao_id.clone());
+        env.storage().persistent().set(&count_key, &(id + 1));
+        env.storage().persistent().bump(&count_key, config.bump_threshold, config.bump_ledgers);
+        Self::write_proposal(env, dao_id, id, &proposal);
+        id
+    }
+
+    pub fn approve(&self, env: &Env, dao_id: &Bytes, id: u32, signer: Address) {
+        self.check_signer(env, &signer);
+        let mut proposal = self.load_proposal(env, dao_id, id);
+        if proposal.approvals.contains(&signer) {
+            panic_with_error!(env, CoreError::CouncilActionAlreadyApproved)
+        }
+        proposal.approvals.push_back(signer);
+        Self::write_proposal(env, dao_id, id, &proposal);
+    }
+
+    /// Marks an action with enough approvals of the current signers as executed
+    pub fn execute(&self, env: &Env, dao_id: &Bytes, id: u32) -> CouncilAction {
+        let mut proposal = self.load_proposal(env, dao_id, id);
+        let approvals = proposal
+            .approvals
+            .iter()
+            .filter(|signer| self.signers.contains(signer))
+            .count() as u32;
+        if approvals < self.threshold {
+            panic_with_error!(env, CoreError::CouncilThresholdNotReached)
+        }
+        proposal.executed = true;
+        Self::write_proposal(env, dao_id, id, &proposal);
+        proposal.action
+    }
+
+    /// Loads a pending action of the council
+    pub fn load_proposal(&self, env: &Env, dao_id: &Bytes, id: u32) -> CouncilProposal {
+        let proposal = self.read_proposal(env, dao_id, id);
+        if proposal.executed {
+            panic_with_error!(env, CoreError::CouncilActionExecuted)
+        }
+        proposal
+    }
+
+    /// Loads an action of the council, no matter if it was executed
+    pub fn read_proposal(&self, env: &Env, dao_id: &Bytes, id: u32) -> CouncilProposal {
+        let key = DaoArtifact::CouncilAction(dao_id.clone(), id);
+        match env.storage().persistent().get(&key) {
+            Some(proposal) if id >= self.first_action_id => proposal,
+            _ => panic_with_error!(env, CoreError::CouncilActionNotFound),
+        }
+    }
+
+    fn write_proposal(env: &Env, dao_id: &Bytes, id: u32, proposal: &CouncilProposal) {
+        let config = ProtocolConfig::load(env);
+        let key = DaoArtifact::CouncilAction(dao_id.clone(), id);
+        env.storage().persistent().set(&key, proposal);
+        env.storage().persistent().bump(&key, config.bump_threshold, config.bump_ledgers);
+    }
+
+    /// Authorizes the core contract as the owner of the dao until `end_execution`
+    pub fn begin_execution(env: &Env, dao_id: &Bytes) {
+        env.storage().temporary().set(&DaoArtifact::CouncilExecuting(dao_id.clone()), &true);
+    }
+
+    pub fn end_execution(env: &Env, dao_id: &Bytes) {
+        env.storage().temporary().remove(&DaoArtifact::CouncilExecuting(dao_id.clone()));
+    }
+}
+
+impl ProtocolConfig {
+    /// The parameters the protocol is deployed with
+    pub fn initial() -> Self {
+        ProtocolConfig {
+            dao_reserve: 1000 * XLM,
+            proposal_reserve: 100 * XLM,
+            max_active_proposals: 25,
+            bump_ledgers: BUMP_A_MONTH,
+            bump_threshold: BUMP_A_MONTH_THRESHOLD,
+        }
+    }
+
+    pub fn init(env: &Env, admin: &Address) {
+        env.storage().instance().set(&PROTOCOL_ADMIN, admin);
+        env.storage().instance().set(&PROTOCOL_CONFIG, &Self::initial());
+    }
+
+    pub fn load(env: &Env) -> Self {
+        env.storage().instance().get(&PROTOCOL_CONFIG).unwrap_or(Self::initial())
+    }
+
+    pub fn load_admin(env: &Env) -> Address {
+        env.storage().instance().get(&PROTOCOL_ADMIN).unwrap()
+    }
+
+    /// Requires the auth of the protocol admin, which can be an account or a governance contract
+    pub fn check_admin(env: &Env, admin: &Address) {
+        admin.require_auth();
+        if admin != &Self::load_admin(env) {
+            panic_with_error!(env, CoreError::NotProtocolAdmin)
+        }
+    }
+
+    /// The new admin has to accept, so the protocol can't be handed to an account nobody controls
+    pub fn propose_admin(env: &Env, new_admin: &Address) {
+        env.storage().instance().set(&PROPOSED_ADMIN, new_admin);
+    }
+
+    pub fn accept_admin(env: &Env, new_admin: &Address) {
+        new_admin.require_auth();
+        match env.storage().instance().get::<Symbol, Address>(&PROPOSED_ADMIN) {
+            Some(proposed_admin) if &proposed_admin == new_admin => (),
+            _ => panic_with_error!(env, CoreError::NotProposedAdmin),
+        }
+        env.storage().instance().remove(&PROPOSED_ADMIN);
+        env.storage().instance().set(&PROTOCOL_ADMIN, new_admin);
+    }
+
+    pub fn save(&self, env: &Env) {
+        if self.dao_reserve < 0
+            || self.proposal_reserve < 0
+            || self.max_active_proposals == 0
+            || self.bump_threshold == 0
+            || self.bump_threshold > self.bump_ledgers
+            || self.bump_ledgers > MAX_TTL
+        {
+            panic_with_error!(env, CoreError::InvalidProtocolConfig)
+        }
+        env.storage().instance().set(&PROTOCOL_CONFIG, self);
+    }
+}
+
+pub fn read_version(env: &Env) -> u32 {
+    env.storage().instance().get(&VERSION).unwrap_or(0)
+}
+
+pub fn write_version(env: &Env) {
+    env.storage().instance().set(&VERSION, &STORAGE_VERSION);
+}
+
+/// Rewrites the storage of an older layout after an upgrade, returns the previous version
+pub fn migrate(env: &Env) -> u32 {
+    let from_version = read_version(env);
+    if from_version >= STORAGE_VERSION {
+        panic_with_error!(env, CoreError::AlreadyMigrated)
+    }
+    if from_version < 1 && !env.storage().instance().has(&PROTOCOL_CONFIG) {
+        env.storage().instance().set(&PROTOCOL_CONFIG, &ProtocolConfig::initial());
+    }
+    write_version(env);
+    from_version
+}
@@ -62,6 +62,17 @@ pub trait HookpointsTrait {
     /// - `proposal_id`: The id of the proposal to be declared implemented.
     fn on_before_mark_implemented(env: Env, dao_id: Bytes, proposal_id: u32);
 
+    /// Called when assets contract sets an allowance.
+    ///
+    /// Added with the token interface, the amount is kept for hookpoint contracts deployed before
+    /// that don't have it.
+    ///
+    /// - `dao_id`: The dao id of the asset.
+    /// - `from`: The address setting the allowance. Needs authentication.
+    /// - `spender`: The address of the spender.
+    /// - `amount`: The allowance to set.
+    fn on_approve(env: Env, dao_id: Bytes, from: Address, spender: Address, amount: i128) -> i128;
+
     /// Called when assets contract increases allowance.
     ///
     /// - `dao_id`: The dao id that will be implemented for.
@@ -93,4 +104,14 @@ pub trait HookpointsTrait {
     /// - `to`: The address receiving the asset.
     /// - `amount`: The amount to be sent.
     fn on_xfer_from(env: Env, dao_id: Bytes, spender: Address, from: Address, to: Address, amount: i128) -> i128;
+
+    /// Called when assets are burned, by the holder or by a spender with an allowance.
+    ///
+    /// Added with burning, the amount is kept for hookpoint contracts deployed before that don't
+    /// have it.
+    ///
+    /// - `dao_id`: The dao id of the asset.
+    /// - `from`: The address whose assets are burned.
+    /// - `amount`: The amount to be burned.
+    fn on_burn(env: Env, dao_id: Bytes, from: Address, amount: i128) -> i128;
 }
@@ -67,6 +67,10 @@ impl HookpointsTrait for HookpointsContract {
     }
 
     /* Assets HookPoints */
+    fn on_approve(_env: Env, _dao_id: Bytes, _from: Address, _spender: Address, amount: i128) -> i128 {
+        amount
+    }
+
     fn on_incr_allowance(_env: Env, _dao_id: Bytes, _from: Address, _spender: Address, amount: i128) -> i128 {
         amount
     }
@@ -82,4 +86,8 @@ impl HookpointsTrait for HookpointsContract {
     fn on_xfer_from(_env: Env, _dao_id: Bytes, _spender: Address, _from: Address, _to: Address, amount: i128) -> i128 {
         amount
     }
+
+    fn on_burn(_env: Env, _dao_id: Bytes, _from: Address, amount: i128) -> i128 {
+        amount
+    }
 }
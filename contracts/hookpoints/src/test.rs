@@ -5,7 +5,7 @@ use soroban_sdk::testutils::{Ledger, LedgerInfo};
 
 use crate::{
     core_contract::{WASM as CoreWASM, Client as CoreClient},
-    votes_contract::{WASM as VotesWASM, Client as VotesClient},
+    votes_contract::{WASM as VotesWASM, Client as VotesClient, VoteChoice},
     assets_contract::{WASM as AssetsWASM, Client as AssetsClient},
 };
 use crate::interface::HookpointsTrait;
@@ -17,6 +17,7 @@ pub struct TestHookpointsContract;
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
+#[allow(clippy::enum_variant_names)]
 pub enum HookTestError {
     OnBeforeDestroyDao = 0,
     OnBeforeChangeOwner = 1,
@@ -65,6 +66,10 @@ impl HookpointsTrait for TestHookpointsContract {
         panic_with_error!(env, HookTestError::OnBeforeMarkImplemented)
     }
 
+    fn on_approve(_env: Env, _dao_id: Bytes, _from: Address, _spender: Address, amount: i128) -> i128 {
+        amount + 30
+    }
+
     fn on_incr_allowance(_env: Env, _dao_id: Bytes, _from: Address, _spender: Address, amount: i128) -> i128 {
         amount + 20
     }
@@ -80,7 +85,27 @@ impl HookpointsTrait for TestHookpointsContract {
     fn on_xfer_from(_env: Env, _dao_id: Bytes, _spender: Address, _from: Address, _to: Address, amount: i128) -> i128 {
         amount + 20
     }
+
+    fn on_burn(_env: Env, _dao_id: Bytes, _from: Address, amount: i128) -> i128 {
+        amount + 5
+    }
+}
+
+/// A hookpoint contract deployed before `on_approve` and `on_burn` were added
+mod legacy_hookpoints {
+    use soroban_sdk::{contract, contractimpl, Address, Bytes, Env};
+
+    #[contract]
+    pub struct LegacyHookpointsContract;
+
+    #[contractimpl]
+    impl LegacyHookpointsContract {
+        pub fn on_xfer(_env: Env, _dao_id: Bytes, _from: Address, _to: Address, amount: i128) -> i128 {
+            amount * 5
+        }
+    }
 }
+use legacy_hookpoints::LegacyHookpointsContract;
 
 const MINT: i128 = 1_000 * 10_000_000;
 pub const MAX_I128: i128 = 170_141_183_460_469_231_731_687_303_715_884_105_727;
@@ -115,7 +140,7 @@ impl Protocol {
         let native_asset_id = env.register_stellar_asset_contract(Address::random(&env));
         let native_asset_admin = token::StellarAssetClient::new(&env, &native_asset_id);
 
-        core.init(&votes_id, &native_asset_id);
+        core.init(&votes_id, &native_asset_id, &Address::random(&env));
         votes.init(&core_id);
 
         native_asset_admin.mint(&dao_owner, &MAX_I128);
@@ -125,11 +150,11 @@ impl Protocol {
 
         let assets_wasm_hash = env.deployer().upload_contract_wasm(AssetsWASM);
         let salt = BytesN::from_array(&env, &[1; 32]);
-        core.issue_token(&dao_id, &dao_owner, &assets_wasm_hash, &salt);
+        core.issue_token(&dao_id, &dao_owner, &assets_wasm_hash, &salt, &0);
 
         let asset_id = core.get_dao_asset_id(&dao_id);
         let asset = AssetsClient::new(&env, &asset_id);
-        asset.mint(&dao_owner, &MINT);
+        asset.mint(&dao_owner, &dao_owner, &MINT);
 
         let proposal_duration: u32 = 10_000;
         let min_threshold_configuration: i128 = 1_000;
@@ -161,7 +186,7 @@ fn should_remove_hookpoint() {
 
     protocol.core.set_hookpoint(&protocol.dao_id, &hookpoints_address, &protocol.dao_owner);
     protocol.core.remove_hookpoint(&protocol.dao_id, &protocol.dao_owner);
-    let voting_power = protocol.votes.vote(&protocol.dao_id, &protocol.proposal_id, &true, &protocol.dao_owner);
+    let voting_power = protocol.votes.vote(&protocol.dao_id, &protocol.proposal_id, &VoteChoice::InFavor, &protocol.dao_owner);
     assert_eq!(voting_power, MINT);
 }
 
@@ -183,7 +208,8 @@ fn should_respect_contract_on_before_change_owner() {
 
     let whoever = Address::random(&protocol.env);
     protocol.core.set_hookpoint(&protocol.dao_id, &hookpoints_address, &protocol.dao_owner);
-    protocol.core.change_owner(&protocol.dao_id, &whoever, &protocol.dao_owner);
+    protocol.core.propose_owner(&protocol.dao_id, &whoever, &100, &protocol.dao_owner);
+    protocol.core.accept_ownership(&protocol.dao_id, &whoever);
 }
 
 #[test]
@@ -192,7 +218,7 @@ fn should_respect_contract_on_vote() {
     let hookpoints_address = protocol.env.register_contract(None, TestHookpointsContract);
 
     protocol.core.set_hookpoint(&protocol.dao_id, &hookpoints_address, &protocol.dao_owner);
-    let voting_power = protocol.votes.vote(&protocol.dao_id, &protocol.proposal_id, &true, &protocol.dao_owner);
+    let voting_power = protocol.votes.vote(&protocol.dao_id, &protocol.proposal_id, &VoteChoice::InFavor, &protocol.dao_owner);
     assert_eq!(voting_power, MINT * 10);
 }
 
@@ -246,6 +272,24 @@ fn should_respect_contract_on_before_fault_proposal() {
     protocol.votes.fault_proposal(&protocol.dao_id, &protocol.proposal_id, &reason, &protocol.dao_owner);
 }
 
+#[test]
+fn should_keep_amounts_of_hooks_missing_in_legacy_contracts() {
+    let protocol = Protocol::new();
+    let hookpoints_address = protocol.env.register_contract(None, LegacyHookpointsContract);
+    let whoever = Address::random(&protocol.env);
+    let someone = Address::random(&protocol.env);
+    protocol.core.set_hookpoint(&protocol.dao_id, &hookpoints_address, &protocol.dao_owner);
+
+    protocol.asset.xfer(&protocol.dao_owner, &whoever, &10);
+    assert_eq!(protocol.asset.balance(&whoever), 50);
+
+    protocol.asset.approve(&whoever, &someone, &10, &protocol.env.ledger().sequence());
+    assert_eq!(protocol.asset.allowance(&whoever, &someone), 10);
+
+    protocol.asset.burn(&whoever, &10);
+    assert_eq!(protocol.asset.balance(&whoever), 40);
+}
+
 #[test]
 #[should_panic(expected="#5")]
 fn should_respect_contract_on_before_finalize_proposal() {
@@ -276,6 +320,14 @@ fn should_manipulate_asset_funcs() {
     assert_eq!(protocol.asset.allowance(&protocol.dao_owner, &someone), 30);
     protocol.asset.decr_allow(&protocol.dao_owner, &someone, &10);
     assert_eq!(protocol.asset.allowance(&protocol.dao_owner, &someone), 0);
+
+    protocol.asset.approve(&protocol.dao_owner, &someone, &10, &protocol.env.ledger().sequence());
+    assert_eq!(protocol.asset.allowance(&protocol.dao_owner, &someone), 40);
+    protocol.asset.transfer_from(&someone, &protocol.dao_owner, &whoever, &10);
+    assert_eq!(protocol.asset.balance(&whoever), 110);
+
+    protocol.asset.burn(&whoever, &10);
+    assert_eq!(protocol.asset.balance(&whoever), 95);
 }
 
 #[test]
@@ -0,0 +1,10 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum TreasuryError {
+    CoreAlreadyInitialized = 0,
+    InvalidAmount = 1,
+    InsufficientFunds = 2,
+}
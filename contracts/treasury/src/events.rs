@@ -0,0 +1,25 @@
+use soroban_sdk::{contracttype, Address, Bytes, Symbol, symbol_short};
+
+pub const CORE: Symbol = symbol_short!("CORE");
+pub const TREASURY: Symbol = symbol_short!("TREASURY");
+
+pub const DEPOSITED: Symbol = symbol_short!("deposited");
+pub const TRANSFERRED: Symbol = symbol_short!("transfer");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryDepositedEventData {
+    pub dao_id: Bytes,
+    pub token_id: Address,
+    pub from: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryTransferredEventData {
+    pub dao_id: Bytes,
+    pub token_id: Address,
+    pub to: Address,
+    pub amount: i128,
+}
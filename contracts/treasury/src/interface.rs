@@ -0,0 +1,45 @@
+use soroban_sdk::{Address, Bytes, Env};
+
+pub trait TreasuryTrait {
+    /// Initialize the contract
+    ///
+    /// - `core_id`: The address of the core-contract.
+    fn init(env: Env, core_id: Address);
+
+    /// Gets the core_id
+    fn get_core_id(env: Env) -> Address;
+
+    /// Deposit tokens into the treasury of a DAO, returns the credited amount
+    ///
+    /// The DAO is credited what the treasury received, so tokens that charge a fee on transfer
+    /// credit less than the transferred amount.
+    ///
+    /// - `dao_id`: The id of the DAO receiving the funds.
+    /// - `token_id`: The address of the token, the native asset included.
+    /// - `from`: The account the funds are transferred from.
+    /// - `amount`: The amount to transfer.
+    fn deposit(env: Env, dao_id: Bytes, token_id: Address, from: Address, amount: i128) -> i128;
+
+    /// Gets the balance of a token held for a DAO
+    ///
+    /// - `dao_id`: The id of the DAO.
+    /// - `token_id`: The address of the token.
+    fn balance(env: Env, dao_id: Bytes, token_id: Address) -> i128;
+
+    /// Checks if the treasury holds a balance of any token for a DAO, the core contract doesn't
+    /// destroy a DAO while it does
+    ///
+    /// - `dao_id`: The id of the DAO.
+    fn has_balance(env: Env, dao_id: Bytes) -> bool;
+
+    /// Transfer tokens out of the treasury of a DAO
+    ///
+    /// Only the votes contract can release funds, so a transfer has to be an action of an
+    /// accepted proposal of the DAO.
+    ///
+    /// - `dao_id`: The id of the DAO spending the funds.
+    /// - `token_id`: The address of the token.
+    /// - `to`: The recipient of the funds.
+    /// - `amount`: The amount to transfer.
+    fn transfer(env: Env, dao_id: Bytes, token_id: Address, to: Address, amount: i128);
+}
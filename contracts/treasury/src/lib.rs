@@ -0,0 +1,90 @@
+#![no_std]
+
+use soroban_sdk::{contractimpl, contract, Address, Bytes, Env, panic_with_error};
+
+mod core_contract {
+    soroban_sdk::contractimport!(file = "../../wasm/elio_core.wasm");
+}
+
+#[cfg(test)]
+mod test;
+
+mod types;
+
+mod interface;
+
+mod events;
+mod error;
+
+use core_contract::Client as CoreContractClient;
+use events::{
+    TreasuryDepositedEventData, TreasuryTransferredEventData, CORE, DEPOSITED, TRANSFERRED, TREASURY,
+};
+use interface::TreasuryTrait;
+use types::Treasury;
+use crate::error::TreasuryError;
+
+#[contract]
+pub struct TreasuryContract;
+
+#[contractimpl]
+impl TreasuryTrait for TreasuryContract {
+    fn init(env: Env, core_id: Address) {
+        if env.storage().instance().has(&CORE) {
+            panic_with_error!(env, TreasuryError::CoreAlreadyInitialized)
+        }
+        env.storage().instance().set(&CORE, &core_id);
+    }
+
+    fn get_core_id(env: Env) -> Address {
+        env.storage().instance().get(&CORE).unwrap()
+    }
+
+    fn deposit(env: Env, dao_id: Bytes, token_id: Address, from: Address, amount: i128) -> i128 {
+        from.require_auth();
+
+        // check that DAO exists
+        let core = CoreContractClient::new(&env, &Self::get_core_id(env.clone()));
+        let _ = core.get_dao(&dao_id);
+        Treasury::sync_protocol_config(&env, &core.get_protocol_config());
+
+        let amount = Treasury::deposit(&env, dao_id.clone(), token_id.clone(), from.clone(), amount);
+        env.events().publish(
+            (TREASURY, DEPOSITED),
+            TreasuryDepositedEventData {
+                dao_id,
+                token_id,
+                from,
+                amount,
+            },
+        );
+        amount
+    }
+
+    fn balance(env: Env, dao_id: Bytes, token_id: Address) -> i128 {
+        Treasury::read_balance(&env, dao_id, token_id)
+    }
+
+    fn has_balance(env: Env, dao_id: Bytes) -> bool {
+        Treasury::has_holdings(&env, dao_id)
+    }
+
+    fn transfer(env: Env, dao_id: Bytes, token_id: Address, to: Address, amount: i128) {
+        // the votes contract only invokes actions of accepted proposals and makes sure that they
+        // name the DAO of the proposal as the first argument
+        let core = CoreContractClient::new(&env, &Self::get_core_id(env.clone()));
+        core.get_votes_id().require_auth();
+        Treasury::sync_protocol_config(&env, &core.get_protocol_config());
+
+        Treasury::withdraw(&env, dao_id.clone(), token_id.clone(), to.clone(), amount);
+        env.events().publish(
+            (TREASURY, TRANSFERRED),
+            TreasuryTransferredEventData {
+                dao_id,
+                token_id,
+                to,
+                amount,
+            },
+        );
+    }
+}
@@ -0,0 +1,281 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, Ledger, LedgerInfo},
+    token, vec, Address, Bytes, BytesN, Env, IntoVal, Symbol,
+};
+
+use crate::{
+    core_contract::{Client as CoreContractClient, ProtocolConfig, WASM as CoreWASM},
+    types::Treasury,
+    TreasuryContract, TreasuryContractClient,
+};
+
+mod votes_contract {
+    soroban_sdk::contractimport!(file = "../../wasm/elio_votes.wasm");
+}
+
+mod assets_contract {
+    soroban_sdk::contractimport!(file = "../../wasm/elio_assets.wasm");
+}
+
+const PROPOSAL_DURATION: u32 = 10_000;
+
+pub const MAX_I128: i128 = 170_141_183_460_469_231_731_687_303_715_884_105_727;
+
+struct Clients {
+    core: CoreContractClient<'static>,
+    votes: votes_contract::Client<'static>,
+    treasury: TreasuryContractClient<'static>,
+    native_asset: token::Client<'static>,
+}
+
+impl Clients {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths_allowing_non_root_auth();
+
+        env.budget().reset_unlimited();
+
+        let core_id = env.register_contract_wasm(None, CoreWASM);
+        let votes_id = env.register_contract_wasm(None, votes_contract::WASM);
+        let treasury_id = env.register_contract(None, TreasuryContract);
+
+        let core = CoreContractClient::new(&env, &core_id);
+        let votes = votes_contract::Client::new(&env, &votes_id);
+        let treasury = TreasuryContractClient::new(&env, &treasury_id);
+
+        let native_asset_id = env.register_stellar_asset_contract(Address::random(&env));
+        let native_asset = token::Client::new(&env, &native_asset_id);
+
+        core.init(&votes_id, &native_asset_id, &Address::random(&env));
+        votes.init(&core_id);
+        treasury.init(&core_id);
+
+        Self {
+            core,
+            votes,
+            treasury,
+            native_asset,
+        }
+    }
+}
+
+/// A token that burns a tenth of every transfer
+#[contract]
+struct FeeToken;
+
+#[contractimpl]
+impl FeeToken {
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        let balance = Self::balance(env.clone(), to.clone());
+        env.storage().persistent().set(&to, &(balance + amount));
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage().persistent().get(&id).unwrap_or(0)
+    }
+
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        let from_balance = Self::balance(env.clone(), from.clone());
+        let to_balance = Self::balance(env.clone(), to.clone());
+        env.storage().persistent().set(&from, &(from_balance - amount));
+        env.storage().persistent().set(&to, &(to_balance + amount - amount / 10));
+    }
+}
+
+fn set_sequence(env: &Env, sequence_number: u32) {
+    env.ledger().set(LedgerInfo {
+        timestamp: 12345,
+        protocol_version: 20,
+        sequence_number,
+        network_id: Default::default(),
+        base_reserve: 10,
+        min_temp_entry_expiration: 10,
+        min_persistent_entry_expiration: 10,
+        max_entry_expiration: 5_200_000,
+    });
+}
+
+#[test]
+fn deposit() {
+    let Clients { core, treasury, native_asset, .. } = Clients::new();
+    let env = &treasury.env;
+
+    let dao_owner = Address::random(env);
+    token::StellarAssetClient::new(env, &native_asset.address).mint(&dao_owner, &MAX_I128);
+    let dao = core.create_dao(&"DIV".into_val(env), &"Deep Ink Ventures".into_val(env), &dao_owner);
+    treasury.deposit(&dao.id, &native_asset.address, &dao_owner, &1_000);
+    treasury.deposit(&dao.id, &native_asset.address, &dao_owner, &500);
+
+    assert_eq!(treasury.balance(&dao.id, &native_asset.address), 1_500);
+    assert_eq!(native_asset.balance(&treasury.address), 1_500);
+}
+
+#[test]
+fn deposit_credits_received_amount() {
+    let Clients { core, treasury, native_asset, .. } = Clients::new();
+    let env = &treasury.env;
+
+    let dao_owner = Address::random(env);
+    token::StellarAssetClient::new(env, &native_asset.address).mint(&dao_owner, &MAX_I128);
+    let dao = core.create_dao(&"DIV".into_val(env), &"Deep Ink Ventures".into_val(env), &dao_owner);
+    let fee_token = FeeTokenClient::new(env, &env.register_contract(None, FeeToken));
+    fee_token.mint(&dao_owner, &1_000);
+
+    assert_eq!(treasury.deposit(&dao.id, &fee_token.address, &dao_owner, &1_000), 900);
+    assert_eq!(treasury.balance(&dao.id, &fee_token.address), 900);
+    assert_eq!(fee_token.balance(&treasury.address), 900);
+}
+
+#[test]
+fn balance_is_kept_per_dao() {
+    let Clients { core, treasury, native_asset, .. } = Clients::new();
+    let env = &treasury.env;
+
+    let dao_owner = Address::random(env);
+    token::StellarAssetClient::new(env, &native_asset.address).mint(&dao_owner, &MAX_I128);
+    let dao = core.create_dao(&"DIV".into_val(env), &"Deep Ink Ventures".into_val(env), &dao_owner);
+    treasury.deposit(&dao.id, &native_asset.address, &dao_owner, &1_000);
+    let other_dao_id: Bytes = "OTHER".into_val(env);
+
+    assert_eq!(treasury.balance(&other_dao_id, &native_asset.address), 0);
+}
+
+#[test]
+fn balances_are_bumped_by_protocol_config() {
+    let Clients { core, treasury, native_asset, .. } = Clients::new();
+    let env = &treasury.env;
+
+    let mut config = core.get_protocol_config();
+    config.bump_ledgers = 1_000_000;
+    core.set_protocol_config(&config, &core.get_protocol_admin());
+
+    let dao_owner = Address::random(env);
+    token::StellarAssetClient::new(env, &native_asset.address).mint(&dao_owner, &MAX_I128);
+    let dao = core.create_dao(&"DIV".into_val(env), &"Deep Ink Ventures".into_val(env), &dao_owner);
+    treasury.deposit(&dao.id, &native_asset.address, &dao_owner, &1_000);
+
+    let kept: ProtocolConfig = env.as_contract(&treasury.address, || {
+        env.storage().instance().get(&Treasury::ProtocolConfig).unwrap()
+    });
+    assert_eq!(kept.bump_ledgers, 1_000_000);
+}
+
+#[test]
+#[should_panic(expected = "#1")]
+fn deposit_requires_dao() {
+    let Clients { treasury, native_asset, .. } = Clients::new();
+    let env = &treasury.env;
+
+    treasury.deposit(&"DIV".into_val(env), &native_asset.address, &Address::random(env), &1_000);
+}
+
+#[test]
+#[should_panic(expected = "#1")]
+fn deposit_requires_positive_amount() {
+    let Clients { core, treasury, native_asset, .. } = Clients::new();
+    let env = &treasury.env;
+
+    let dao_owner = Address::random(env);
+    token::StellarAssetClient::new(env, &native_asset.address).mint(&dao_owner, &MAX_I128);
+    let dao = core.create_dao(&"DIV".into_val(env), &"Deep Ink Ventures".into_val(env), &dao_owner);
+    treasury.deposit(&dao.id, &native_asset.address, &dao_owner, &0);
+}
+
+#[test]
+fn transfer_by_proposal() {
+    let Clients { core, votes, treasury, native_asset } = Clients::new();
+    let env = &treasury.env;
+    set_sequence(env, 100);
+
+    let dao_owner = Address::random(env);
+    token::StellarAssetClient::new(env, &native_asset.address).mint(&dao_owner, &MAX_I128);
+    let dao = core.create_dao(&"DIV".into_val(env), &"Deep Ink Ventures".into_val(env), &dao_owner);
+    treasury.deposit(&dao.id, &native_asset.address, &dao_owner, &1_000);
+
+    let assets_wasm_hash = env.deployer().upload_contract_wasm(assets_contract::WASM);
+    let salt = BytesN::from_array(env, &[1; 32]);
+    core.issue_token(&dao.id, &dao_owner, &assets_wasm_hash, &salt, &0);
+    let asset = assets_contract::Client::new(env, &core.get_dao_asset_id(&dao.id));
+    asset.mint(&dao_owner, &dao_owner, &1_000_000);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao_owner);
+    let recipient = Address::random(env);
+
+    let proposal_id = votes.create_proposal(&dao.id, &dao_owner);
+    let action = votes_contract::ProposalAction {
+        contract_id: treasury.address.clone(),
+        func: Symbol::new(env, "transfer"),
+        args: (dao.id.clone(), native_asset.address.clone(), recipient.clone(), 400_i128).into_val(env),
+    };
+    votes.set_actions(&dao.id, &proposal_id, &vec![env, action], &dao_owner);
+    votes.vote(&dao.id, &proposal_id, &votes_contract::VoteChoice::InFavor, &dao_owner);
+    set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+    votes.finalize_proposal(&dao.id, &proposal_id);
+    votes.execute_proposal(&proposal_id);
+
+    assert_eq!(treasury.balance(&dao.id, &native_asset.address), 600);
+    assert_eq!(native_asset.balance(&recipient), 400);
+}
+
+#[test]
+fn transfer_requires_votes_contract() {
+    let Clients { core, treasury, native_asset, .. } = Clients::new();
+    let env = &treasury.env;
+
+    let dao_owner = Address::random(env);
+    token::StellarAssetClient::new(env, &native_asset.address).mint(&dao_owner, &MAX_I128);
+    let dao = core.create_dao(&"DIV".into_val(env), &"Deep Ink Ventures".into_val(env), &dao_owner);
+    treasury.deposit(&dao.id, &native_asset.address, &dao_owner, &1_000);
+    env.set_auths(&[]);
+
+    let result = treasury.try_transfer(&dao.id, &native_asset.address, &dao_owner, &400);
+    assert!(result.is_err());
+    assert_eq!(treasury.balance(&dao.id, &native_asset.address), 1_000);
+}
+
+#[test]
+#[should_panic(expected = "#2")]
+fn cannot_transfer_more_than_balance() {
+    let Clients { core, treasury, native_asset, .. } = Clients::new();
+    let env = &treasury.env;
+
+    let dao_owner = Address::random(env);
+    token::StellarAssetClient::new(env, &native_asset.address).mint(&dao_owner, &MAX_I128);
+    let dao = core.create_dao(&"DIV".into_val(env), &"Deep Ink Ventures".into_val(env), &dao_owner);
+    treasury.deposit(&dao.id, &native_asset.address, &dao_owner, &1_000);
+    treasury.transfer(&dao.id, &native_asset.address, &Address::random(env), &1_001);
+}
+
+#[test]
+#[should_panic(expected = "#31")]
+fn dao_with_funds_cannot_be_destroyed() {
+    let Clients { core, treasury, native_asset, .. } = Clients::new();
+    let env = &treasury.env;
+    core.set_treasury_id(&treasury.address, &core.get_protocol_admin());
+
+    let dao_owner = Address::random(env);
+    token::StellarAssetClient::new(env, &native_asset.address).mint(&dao_owner, &MAX_I128);
+    let dao = core.create_dao(&"DIV".into_val(env), &"Deep Ink Ventures".into_val(env), &dao_owner);
+    treasury.deposit(&dao.id, &native_asset.address, &dao_owner, &1_000);
+    core.destroy_dao(&dao.id, &dao_owner);
+}
+
+#[test]
+fn dao_without_funds_can_be_destroyed() {
+    let Clients { core, treasury, native_asset, .. } = Clients::new();
+    let env = &treasury.env;
+    core.set_treasury_id(&treasury.address, &core.get_protocol_admin());
+
+    let dao_owner = Address::random(env);
+    token::StellarAssetClient::new(env, &native_asset.address).mint(&dao_owner, &MAX_I128);
+    let dao = core.create_dao(&"DIV".into_val(env), &"Deep Ink Ventures".into_val(env), &dao_owner);
+    treasury.deposit(&dao.id, &native_asset.address, &dao_owner, &1_000);
+    assert!(treasury.has_balance(&dao.id));
+    treasury.transfer(&dao.id, &native_asset.address, &dao_owner, &1_000);
+    assert!(!treasury.has_balance(&dao.id));
+
+    core.destroy_dao(&dao.id, &dao_owner);
+}
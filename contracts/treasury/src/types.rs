@@ -0,0 +1,99 @@
+use soroban_sdk::{contracttype, panic_with_error, token, Address, Bytes, Env};
+
+use crate::core_contract::ProtocolConfig;
+use crate::error::TreasuryError;
+
+#[derive(Clone)]
+#[contracttype]
+pub enum Treasury {
+    Balance(Bytes, Address),
+    /// The number of tokens a DAO holds a balance of
+    Holdings(Bytes),
+    ProtocolConfig,
+}
+
+pub const A_WEEK_IN_LEDGERS: u32 = 100800;
+pub const BUMP_A_MONTH: u32 = 432000;
+pub const BUMP_A_MONTH_THRESHOLD: u32 = 432000 - A_WEEK_IN_LEDGERS;
+
+impl Treasury {
+    /// Keeps the protocol config read through the core to bump the balances without calling the core
+    pub fn sync_protocol_config(env: &Env, config: &ProtocolConfig) {
+        env.storage().instance().set(&Treasury::ProtocolConfig, config);
+    }
+
+    /// The bump threshold and ledgers of the protocol config as of the last deposit or transfer
+    fn get_bump(env: &Env) -> (u32, u32) {
+        match env.storage().instance().get::<_, ProtocolConfig>(&Treasury::ProtocolConfig) {
+            Some(config) => (config.bump_threshold, config.bump_ledgers),
+            None => (BUMP_A_MONTH_THRESHOLD, BUMP_A_MONTH),
+        }
+    }
+
+    pub fn read_balance(env: &Env, dao_id: Bytes, token_id: Address) -> i128 {
+        let key = Treasury::Balance(dao_id, token_id);
+        if !env.storage().persistent().has(&key) {
+            return 0;
+        }
+        let (threshold, ledgers) = Self::get_bump(env);
+        env.storage().persistent().bump(&key, threshold, ledgers);
+        env.storage().persistent().get(&key).unwrap()
+    }
+
+    fn write_balance(env: &Env, dao_id: Bytes, token_id: Address, amount: i128) {
+        let (threshold, ledgers) = Self::get_bump(env);
+        let holdings_key = Treasury::Holdings(dao_id.clone());
+        let holdings: u32 = env.storage().persistent().get(&holdings_key).unwrap_or(0);
+        match (Self::read_balance(env, dao_id.clone(), token_id.clone()), amount) {
+            (0, amount) if amount > 0 => env.storage().persistent().set(&holdings_key, &(holdings + 1)),
+            (balance, 0) if balance > 0 => env.storage().persistent().set(&holdings_key, &(holdings - 1)),
+            _ => (),
+        }
+        if env.storage().persistent().has(&holdings_key) {
+            env.storage().persistent().bump(&holdings_key, threshold, ledgers);
+        }
+
+        let key = Treasury::Balance(dao_id, token_id);
+        env.storage().persistent().set(&key, &amount);
+        env.storage().persistent().bump(&key, threshold, ledgers);
+    }
+
+    /// Checks if the treasury holds a balance of any token for a DAO
+    pub fn has_holdings(env: &Env, dao_id: Bytes) -> bool {
+        env.storage().persistent().get::<_, u32>(&Treasury::Holdings(dao_id)).unwrap_or(0) > 0
+    }
+
+    /// Credits what the treasury actually received, which is less than the amount for tokens
+    /// that charge a fee on transfer
+    pub fn deposit(env: &Env, dao_id: Bytes, token_id: Address, from: Address, amount: i128) -> i128 {
+        if amount <= 0 {
+            panic_with_error!(env, TreasuryError::InvalidAmount)
+        }
+        let token = token::Client::new(env, &token_id);
+        let contract = env.current_contract_address();
+        let held_before = token.balance(&contract);
+        token.transfer(&from, &contract, &amount);
+        let received = token.balance(&contract) - held_before;
+        if received <= 0 {
+            panic_with_error!(env, TreasuryError::InvalidAmount)
+        }
+
+        let balance = Self::read_balance(env, dao_id.clone(), token_id.clone());
+        Self::write_balance(env, dao_id, token_id, balance + received);
+        received
+    }
+
+    pub fn withdraw(env: &Env, dao_id: Bytes, token_id: Address, to: Address, amount: i128) {
+        if amount <= 0 {
+            panic_with_error!(env, TreasuryError::InvalidAmount)
+        }
+        let balance = Self::read_balance(env, dao_id.clone(), token_id.clone());
+        if balance < amount {
+            panic_with_error!(env, TreasuryError::InsufficientFunds)
+        }
+        Self::write_balance(env, dao_id, token_id.clone(), balance - amount);
+
+        let token = token::Client::new(env, &token_id);
+        token.transfer(&env.current_contract_address(), &to, &amount);
+    }
+}
@@ -16,4 +16,27 @@ pub enum VotesError {
     ConfigurationNotFound = 9,
     VoteAlreadyCast = 10,
     MetadataAlreadySet = 11,
+    ActionsAlreadySet = 12,
+    VotingAlreadyStarted = 13,
+    ActionOutOfScope = 14,
+    ProposalHasActions = 15,
+    ProposalHasNoActions = 16,
+    ProposalInTimelock = 17,
+    ProposalNotQueued = 18,
+    NotGuardian = 19,
+    VetoDisabled = 20,
+    VetoAlreadyCast = 21,
+    InvalidVoting = 22,
+    InvalidQuorum = 23,
+    NoDeposit = 24,
+    BallotNotFound = 25,
+    InvalidAction = 26,
+    NotProtocolAdmin = 27,
+    AlreadyMigrated = 28,
+    InvalidCancelRefund = 29,
+    ProposalExpired = 30,
+    VotingClosed = 31,
+    MissingRole = 32,
+    CheckpointRetentionTooShort = 33,
+    DaoHasOpenProposals = 34,
 }
\ No newline at end of file
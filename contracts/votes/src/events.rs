@@ -1,21 +1,35 @@
-use soroban_sdk::{contracttype, Address, Bytes, Symbol, symbol_short};
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, Symbol, Vec, symbol_short};
 
-use crate::types::{PropStatus};
+use crate::types::{PropStatus, ProposalAction, VoteChoice, Voting};
 
 pub const CORE: Symbol = symbol_short!("CORE");
 pub const PROPOSAL: Symbol = symbol_short!("PROPOSAL");
+pub const VOTES: Symbol = symbol_short!("VOTES");
 
 pub const CREATED: Symbol = symbol_short!("created");
 pub const METADATA_SET: Symbol = symbol_short!("meta_set");
+pub const ACTIONS_SET: Symbol = symbol_short!("acts_set");
 pub const VOTE_CAST: Symbol = symbol_short!("vote_cast");
 pub const FAULTED: Symbol = symbol_short!("faulted");
 pub const STATUS_UPDATE: Symbol = symbol_short!("state_upd");
 pub const CONF_SET: Symbol = symbol_short!("conf_set");
+pub const DEPOSIT_CLAIMED: Symbol = symbol_short!("dep_claim");
+pub const TIMELOCK_SET: Symbol = symbol_short!("tlock_set");
+pub const VOTING_SET: Symbol = symbol_short!("vtng_set");
+pub const QUORUM_SET: Symbol = symbol_short!("qrm_set");
+pub const VETO_CAST: Symbol = symbol_short!("veto_cast");
+pub const UPGRADED: Symbol = symbol_short!("upgraded");
+pub const MIGRATED: Symbol = symbol_short!("migrated");
+pub const CANCELLED: Symbol = symbol_short!("cancelled");
+pub const CANCEL_REFUND_SET: Symbol = symbol_short!("crfnd_set");
+pub const WITHHELD_CLAIMED: Symbol = symbol_short!("whld_clm");
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ProposalCreatedEventData {
     pub proposal_id: u32,
+    /// The number of the proposal within its DAO
+    pub number: u32,
     pub dao_id: Bytes,
     pub owner_id: Address,
 }
@@ -28,6 +42,13 @@ pub struct ProposalMetadataSetEventData {
     pub hash: Bytes,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalActionsSetEventData {
+    pub proposal_id: u32,
+    pub actions: Vec<ProposalAction>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ProposalConfigurationSetEventData {
@@ -36,12 +57,43 @@ pub struct ProposalConfigurationSetEventData {
     pub min_threshold_configuration: i128,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalTimelockSetEventData {
+    pub dao_id: Bytes,
+    pub execution_delay: u32,
+    pub guardians: Vec<Address>,
+    pub veto_threshold: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalVotingSetEventData {
+    pub dao_id: Bytes,
+    pub voting: Voting,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalQuorumSetEventData {
+    pub dao_id: Bytes,
+    pub quorum_bps: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct VoteCastEventData {
     pub proposal_id: u32,
     pub voter_id: Address,
-    pub in_favor: bool,
+    pub choice: VoteChoice,
+    pub voting_power: i128
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VetoCastEventData {
+    pub proposal_id: u32,
+    pub voter_id: Address,
     pub voting_power: i128
 }
 
@@ -52,9 +104,56 @@ pub struct ProposalFaultedEventData {
     pub reason: Bytes,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalCancelledEventData {
+    pub proposal_id: u32,
+    pub owner_id: Address,
+    pub refund: i128,
+    /// The withheld share of the reserve, it is kept for the DAO
+    pub withheld: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithheldReserveClaimedEventData {
+    pub dao_id: Bytes,
+    pub to: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalCancelRefundSetEventData {
+    pub dao_id: Bytes,
+    pub cancel_refund_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalDepositClaimedEventData {
+    pub proposal_id: u32,
+    pub owner_id: Address,
+    pub amount: i128,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ProposalStatusUpdateEventData {
     pub proposal_id: u32,
     pub status: PropStatus,
+    pub voting: Voting,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradedEventData {
+    pub wasm_hash: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigratedEventData {
+    pub from_version: u32,
+    pub to_version: u32,
 }
@@ -1,6 +1,6 @@
-use soroban_sdk::{Address, Bytes, Env, Vec};
+use soroban_sdk::{Address, Bytes, BytesN, Env, Vec};
 
-use crate::types::{ActiveProposal, Configuration, Metadata, Proposal};
+use crate::types::{ActiveProposal, Ballot, Configuration, Metadata, Proposal, ProposalAction, ProposalRecord, Tally, VoteChoice, Voting};
 
 pub trait VotesTrait {
     /// Initialize the contract
@@ -8,10 +8,28 @@ pub trait VotesTrait {
     /// - `core_id`: The address of the core-contract.
     fn init(env: Env, core_id: Address);
 
+    /// Replace the wasm of the contract, the storage is kept
+    ///
+    /// Call `migrate` afterwards if the new wasm changes the storage layout.
+    ///
+    /// - `new_wasm_hash`: The hash of the installed wasm.
+    /// - `protocol_admin`: The protocol admin of the core contract.
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>, protocol_admin: Address);
+
+    /// Gets the version of the storage layout
+    fn version(env: Env) -> u32;
+
+    /// Rewrite the storage of an older layout after an upgrade, returns the current version
+    ///
+    /// - `protocol_admin`: The protocol admin of the core contract.
+    fn migrate(env: Env, protocol_admin: Address) -> u32;
+
     /// Gets the core_id
     fn get_core_id(env: Env) -> Address;
 
-    /// Create a proposal
+    /// Create a proposal, returns its id
+    ///
+    /// The proposal is numbered within the DAO as well, see `get_proposal_number`.
     ///
     /// - `dao_id`: The id of the DAO where proposal is created.
     /// - `proposal_owner`: The address of owner of the proposal.
@@ -38,8 +56,32 @@ pub trait VotesTrait {
     /// - `proposal_id`: The id of the proposal
     fn get_metadata(env: Env, proposal_id: u32) -> Metadata;
 
+    /// Attach on-chain actions to a proposal, they are invoked by `execute_proposal` once accepted
+    ///
+    /// Actions can only be set once and before any voting power has been cast. Each action has to
+    /// pass the DAO id as its first argument, unless it targets the DAO's own asset.
+    ///
+    /// - `dao_id`: The dao id of the proposal
+    /// - `proposal_id`: The id of the proposal
+    /// - `actions`: The contract calls to invoke
+    /// - `proposal_owner`: The owner of the proposal
+    fn set_actions(
+        env: Env,
+        dao_id: Bytes,
+        proposal_id: u32,
+        actions: Vec<ProposalAction>,
+        proposal_owner: Address,
+    );
+
+    /// Gets the actions of a proposal
+    ///
+    /// - `proposal_id`: The id of the proposal
+    fn get_actions(env: Env, proposal_id: u32) -> Vec<ProposalAction>;
+
     /// Gets all proposals that are active
     ///
+    /// Running proposals stay active for the finalize window after their voting period.
+    ///
     /// - `dao_id`: The id of the DAO where proposal are stored.
     fn get_active_proposals(env: Env, dao_id: Bytes) -> Vec<ActiveProposal>;
 
@@ -48,12 +90,59 @@ pub trait VotesTrait {
     /// - `id`: The id of the proposal we are trying to fetch.
     fn get_archived_proposal(env: Env, id: u32) -> Proposal;
 
+    /// Gets the archived proposals of a DAO in the order they were archived
+    ///
+    /// - `dao_id`: The id of the DAO.
+    /// - `start`: The position of the first archived proposal to return.
+    /// - `limit`: The max number of proposals to return.
+    fn get_archived_proposals(env: Env, dao_id: Bytes, start: u32, limit: u32) -> Vec<ProposalRecord>;
+
+    /// Gets a proposal with its votes, no matter if it is active or archived
+    ///
+    /// - `proposal_id`: The id of the proposal.
+    fn get_proposal(env: Env, proposal_id: u32) -> ProposalRecord;
+
+    /// Gets the number of proposals created for a DAO
+    ///
+    /// Proposals are numbered per DAO starting at 1, so this is the number of the latest one.
+    ///
+    /// - `dao_id`: The id of the DAO.
+    fn get_proposal_count(env: Env, dao_id: Bytes) -> u32;
+
+    /// Gets the id of a proposal by its number within the DAO
+    ///
+    /// The number is how a DAO refers to its proposals, the functions that change a proposal and
+    /// the events take the id, which is unique across all DAOs. Resolve the number with this
+    /// function first.
+    ///
+    /// - `dao_id`: The id of the DAO.
+    /// - `number`: The number of the proposal within the DAO.
+    fn get_proposal_id(env: Env, dao_id: Bytes, number: u32) -> u32;
+
+    /// Gets the number of a proposal within its DAO
+    ///
+    /// - `proposal_id`: The id of the proposal.
+    fn get_proposal_number(env: Env, proposal_id: u32) -> u32;
+
+    /// Gets the final tally of an archived proposal
+    ///
+    /// - `proposal_id`: The id of the proposal.
+    fn get_tally(env: Env, proposal_id: u32) -> Tally;
+
+    /// Return the deposit of a proposal that expired without being finalized to its owner
+    ///
+    /// Proposals that are not finalized within the finalize window after their voting period
+    /// expire, they are archived on the next proposal of their DAO or on claiming the deposit.
+    ///
+    /// - `proposal_id`: The id of the expired proposal.
+    fn claim_deposit(env: Env, proposal_id: u32) -> i128;
+
     /// Set the configuration of the dao
     ///
     /// - `dao_id`: The id of the DAO we are trying to configure.
     /// - `proposal_duration`: The amount of blocks the proposal is active.
     /// - `min_threshold_configuration`: The min voting power required.
-    /// - `dao_owner`: The owner of the DAO. Required for validation.
+    /// - `dao_owner`: The owner of the DAO or an account with the admin role. Required for validation.
     fn set_configuration(
         env: Env,
         dao_id: Bytes,
@@ -62,6 +151,55 @@ pub trait VotesTrait {
         dao_owner: Address,
     ) -> Configuration;
 
+    /// Set the timelock of the dao
+    ///
+    /// Accepted proposals are queued for `execution_delay` ledgers after finalization. In that
+    /// window they can be cancelled by the guardian or by a veto vote of the token holders.
+    /// Vetoes count the voting power at the creation of the proposal, so the checkpoint retention
+    /// of the DAO asset has to cover the proposal duration, finalize window and execution delay.
+    ///
+    /// - `dao_id`: The id of the DAO we are trying to configure.
+    /// - `execution_delay`: The amount of blocks an accepted proposal is queued, 0 disables the queue.
+    /// - `guardians`: The accounts that are allowed to cancel queued proposals.
+    /// - `veto_threshold`: The veto voting power that cancels a queued proposal, 0 disables veto votes.
+    /// - `dao_owner`: The owner of the DAO or an account with the admin role. Required for validation.
+    fn set_timelock(
+        env: Env,
+        dao_id: Bytes,
+        execution_delay: u32,
+        guardians: Vec<Address>,
+        veto_threshold: i128,
+        dao_owner: Address,
+    ) -> Configuration;
+
+    /// Set the voting strategy of the dao
+    ///
+    /// The strategy is stored with each proposal on creation, so running proposals keep theirs.
+    /// The min voting power of the configuration applies to all strategies.
+    ///
+    /// - `dao_id`: The id of the DAO we are trying to configure.
+    /// - `voting`: The strategy that decides if a proposal is accepted.
+    /// - `dao_owner`: The owner of the DAO or an account with the admin role. Required for validation.
+    fn set_voting(env: Env, dao_id: Bytes, voting: Voting, dao_owner: Address) -> Configuration;
+
+    /// Set the quorum of the dao relative to the total supply of its asset
+    ///
+    /// The supply is taken at the ledger of each proposal. A quorum replaces the min voting power
+    /// of the configuration, 0 falls back to it. Like the voting strategy, the quorum is stored
+    /// with each proposal on creation.
+    ///
+    /// - `dao_id`: The id of the DAO we are trying to configure.
+    /// - `quorum_bps`: The share of the supply that has to vote, in basis points.
+    /// - `dao_owner`: The owner of the DAO or an account with the admin role. Required for validation.
+    fn set_quorum(env: Env, dao_id: Bytes, quorum_bps: u32, dao_owner: Address) -> Configuration;
+
+    /// Set the share of the reserve that is returned when a proposal is cancelled by its owner
+    ///
+    /// - `dao_id`: The id of the DAO we are trying to configure.
+    /// - `cancel_refund_bps`: The refunded share of the reserve, in basis points.
+    /// - `dao_owner`: The owner of the DAO or an account with the admin role. Required for validation.
+    fn set_cancel_refund(env: Env, dao_id: Bytes, cancel_refund_bps: u32, dao_owner: Address) -> Configuration;
+
     /// Gets the configuration of the dao
     ///
     /// - `dao_id`: The id of the DAO we are trying to get the configuration.
@@ -72,6 +210,27 @@ pub trait VotesTrait {
     /// - `dao_id`: The id of the DAO we are trying to check the configuration.
     fn has_configuration(env: Env, dao_id: Bytes) -> bool;
 
+    /// Checks if the dao has running proposals or accepted ones that are neither implemented nor
+    /// vetoed, a dao can't be destroyed while it has them
+    ///
+    /// - `dao_id`: The id of the dao.
+    fn has_open_proposals(env: Env, dao_id: Bytes) -> bool;
+
+    /// Reset the proposal lists and numbering of a dao that is destroyed, only the core contract can
+    /// call this
+    ///
+    /// The archived proposals can still be read by their id.
+    ///
+    /// - `dao_id`: The id of the destroyed dao.
+    fn remove_dao(env: Env, dao_id: Bytes);
+
+    /// Returns the number of ledgers the checkpoints of the DAO asset have to be kept for
+    ///
+    /// The proposal duration, the finalize window and the execution delay, 0 without configuration.
+    ///
+    /// - `dao_id`: The id of the DAO.
+    fn get_checkpoint_window(env: Env, dao_id: Bytes) -> u32;
+
     /// Removes the configuration of the dao
     ///
     /// - `dao_id`: The id of the DAO we are trying to remove the configuration.
@@ -79,18 +238,33 @@ pub trait VotesTrait {
 
     /// User casts his/her vote in for the proposal
     ///
+    /// Votes are accepted while the proposal is running and its voting period lasts.
+    ///
     /// - `dao_id`: The id of the DAO we are trying vote.
     /// - `proposal_id`: The id of the proposal we are trying to vote.
-    /// - `in_favor`: Boolean whether voter is in favor or not.
+    /// - `choice`: Whether the voter is in favor, against or abstains.
+    /// - `voter`: Address of the voter.
+    fn vote(env: Env, dao_id: Bytes, proposal_id: u32, choice: VoteChoice, voter: Address) -> i128 ;
+
+    /// Gets the vote of an address on a proposal, it is kept after the proposal is archived
+    ///
+    /// - `proposal_id`: The id of the proposal.
     /// - `voter`: Address of the voter.
-    fn vote(env: Env, dao_id: Bytes, proposal_id: u32, in_favor: bool, voter: Address) -> i128 ;
+    fn get_ballot(env: Env, proposal_id: u32, voter: Address) -> Ballot;
+
+    /// Gets the addresses that voted on a proposal in the order of their first vote
+    ///
+    /// - `proposal_id`: The id of the proposal.
+    /// - `start`: The position of the first voter to return.
+    /// - `limit`: The max number of voters to return.
+    fn get_voters(env: Env, proposal_id: u32, start: u32, limit: u32) -> Vec<Address>;
 
     /// Declare a proposal as faulty
     ///
     /// - `dao_id`: The dao id that will be declared faulty.
     /// - `proposal_id`: The id of the proposal to be declared faulty.
     /// - `reason`: The reason of declaring the proposal faulty.
-    /// - `dao_owner`: The owner of the DAO. Required for validation.
+    /// - `dao_owner`: The owner of the DAO or an account with the proposal moderator role. Required for validation.
     fn fault_proposal(
         env: Env,
         dao_id: Bytes,
@@ -105,9 +279,83 @@ pub trait VotesTrait {
     /// - `proposal_id`: The id of the proposal that will be declared finalized.
     fn finalize_proposal(env: Env, dao_id: Bytes, proposal_id: u32);
 
+    /// Withdraw a proposal as its owner before anyone voted on it, returns the refunded amount
+    ///
+    /// The proposal is archived as cancelled and the configured share of the reserve is returned,
+    /// the rest of the reserve is withheld for the DAO.
+    ///
+    /// - `dao_id`: The dao id of the proposal.
+    /// - `proposal_id`: The id of the proposal to cancel.
+    /// - `proposal_owner`: The owner of the proposal. Required for validation.
+    fn cancel_proposal(env: Env, dao_id: Bytes, proposal_id: u32, proposal_owner: Address) -> i128;
+
+    /// Retrieve the native tokens withheld for a dao from the reserves of cancelled proposals
+    ///
+    /// - `dao_id`: The id of the dao.
+    fn get_withheld_reserve(env: Env, dao_id: Bytes) -> i128;
+
+    /// Transfer the native tokens withheld for a dao from the reserves of cancelled proposals,
+    /// returns the transferred amount
+    ///
+    /// - `dao_id`: The id of the dao.
+    /// - `to`: The receiver of the withheld tokens, e.g. the treasury.
+    /// - `dao_owner`: The owner of the dao. Required for validation.
+    fn claim_withheld_reserve(env: Env, dao_id: Bytes, to: Address, dao_owner: Address) -> i128;
+
+    /// Cancel a proposal that is queued for execution
+    ///
+    /// - `proposal_id`: The id of the queued proposal.
+    /// - `guardian`: A guardian of the DAO. Required for validation.
+    fn cancel_queued_proposal(env: Env, proposal_id: u32, guardian: Address);
+
+    /// Cast a veto against a proposal that is queued for execution
+    ///
+    /// The voting power is taken from the snapshot of the proposal, once the vetoes exceed the
+    /// veto threshold the proposal is vetoed.
+    ///
+    /// - `proposal_id`: The id of the queued proposal.
+    /// - `voter`: Address of the voter.
+    fn veto(env: Env, proposal_id: u32, voter: Address) -> i128;
+
+    /// Gets the veto voting power cast against a proposal
+    ///
+    /// - `proposal_id`: The id of the proposal.
+    fn get_vetoes(env: Env, proposal_id: u32) -> i128;
+
     /// Declare a proposal as implemented
     ///
+    /// Proposals with actions can't be marked implemented, they have to be executed.
+    ///
     /// - `proposal_id`: The id of the proposal that will be declared marked implemented.
-    /// - `dao_owner`: The owner of the DAO. Required for validation.
+    /// - `dao_owner`: The owner of the DAO or an account with the admin role. Required for validation.
     fn mark_implemented(env: Env, proposal_id: u32, dao_owner: Address);
+
+    /// Invoke the actions of an accepted proposal and declare it implemented
+    ///
+    /// Anyone can execute an accepted proposal, the actions are invoked by the votes contract.
+    /// Queued proposals can be executed once their execution delay has passed.
+    ///
+    /// Actions of a DAO owned by the votes contract can target the owner functions of this
+    /// contract (`set_configuration`, `set_timelock`, `set_voting`, `set_quorum`,
+    /// `remove_configuration` and `fault_proposal`) with the votes contract as `dao_owner`.
+    ///
+    /// - `proposal_id`: The id of the proposal to execute.
+    fn execute_proposal(env: Env, proposal_id: u32);
+
+    /// Accept the ownership of a DAO on behalf of the votes contract
+    ///
+    /// The DAO owner has to propose the votes contract as the new owner in the core contract first.
+    /// Afterwards all owner functions of the DAO can only be used through accepted proposals.
+    ///
+    /// - `dao_id`: The id of the DAO to govern.
+    fn accept_dao_ownership(env: Env, dao_id: Bytes);
+
+    /// Execute an approved council action that targets the votes contract
+    ///
+    /// The action is taken from the council of the DAO in the core contract and applied with the
+    /// core contract as `dao_owner`, see the council in the core contract.
+    ///
+    /// - `dao_id`: The id of the DAO owned by a council.
+    /// - `action_id`: The id of the council action.
+    fn execute_council_action(env: Env, dao_id: Bytes, action_id: u32);
 }
@@ -1,6 +1,6 @@
 #![no_std]
 
-use soroban_sdk::{contractimpl, contract, Address, Bytes, Env, Symbol, Vec, panic_with_error, symbol_short};
+use soroban_sdk::{contractimpl, contract, Address, Bytes, BytesN, Env, Symbol, TryFromVal, Val, Vec, panic_with_error, symbol_short};
 
 mod core_contract {
     soroban_sdk::contractimport!(file = "../../wasm/elio_core.wasm");
@@ -10,6 +10,7 @@ mod core_contract {
 mod test;
 
 mod types;
+mod migration;
 
 mod interface;
 
@@ -17,15 +18,20 @@ mod hooks;
 mod events;
 mod error;
 
-use core_contract::Client as CoreContractClient;
+use core_contract::{Client as CoreContractClient, Role};
 use events::{
     ProposalFaultedEventData, ProposalMetadataSetEventData, CORE,
     CREATED, FAULTED, METADATA_SET, PROPOSAL, CONF_SET, ProposalConfigurationSetEventData,
+    ACTIONS_SET, ProposalActionsSetEventData, TIMELOCK_SET, ProposalTimelockSetEventData,
+    VETO_CAST, VetoCastEventData, VOTING_SET, ProposalVotingSetEventData, QUORUM_SET,
+    ProposalQuorumSetEventData, DEPOSIT_CLAIMED, ProposalDepositClaimedEventData, VOTES, UPGRADED,
+    UpgradedEventData, MIGRATED, MigratedEventData, CANCELLED, ProposalCancelledEventData,
+    CANCEL_REFUND_SET, ProposalCancelRefundSetEventData, WITHHELD_CLAIMED, WithheldReserveClaimedEventData,
 };
 use interface::VotesTrait;
-use types::{ActiveProposal, Metadata, Proposal};
+use types::{ActiveProposal, Metadata, Proposal, ProposalAction};
 use crate::error::VotesError;
-use crate::types::{Configuration};
+use crate::types::{Ballot, Configuration, ProposalRecord, Tally, VoteChoice, Voting};
 
 use crate::events::{ProposalCreatedEventData, VoteCastEventData, VOTE_CAST};
 use crate::hooks::on_before_mark_implemented;
@@ -42,6 +48,36 @@ impl VotesTrait for VotesContract {
             panic_with_error!(env, VotesError::CoreAlreadyInitialized)
         }
         env.storage().instance().set(&CORE, &core_id);
+        types::sync_protocol_config(&env, &core_id);
+        types::write_version(&env);
+    }
+
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>, protocol_admin: Address) {
+        verify_protocol_admin(&env, protocol_admin);
+        env.deployer().update_current_contract_wasm(new_wasm_hash.clone());
+        env.events().publish(
+            (VOTES, UPGRADED),
+            UpgradedEventData {
+                wasm_hash: new_wasm_hash,
+            },
+        );
+    }
+
+    fn version(env: Env) -> u32 {
+        types::read_version(&env)
+    }
+
+    fn migrate(env: Env, protocol_admin: Address) -> u32 {
+        verify_protocol_admin(&env, protocol_admin);
+        let from_version = types::migrate(&env);
+        env.events().publish(
+            (VOTES, MIGRATED),
+            MigratedEventData {
+                from_version,
+                to_version: types::STORAGE_VERSION,
+            },
+        );
+        types::STORAGE_VERSION
     }
 
     fn get_core_id(env: Env) -> Address {
@@ -61,6 +97,7 @@ impl VotesTrait for VotesContract {
             (PROPOSAL, CREATED),
             ProposalCreatedEventData {
                 proposal_id,
+                number: Proposal::get_number(&env, proposal_id),
                 dao_id,
                 owner_id: proposal_owner,
             },
@@ -98,6 +135,27 @@ impl VotesTrait for VotesContract {
         Metadata::get(&env, proposal_id)
     }
 
+    fn set_actions(
+        env: Env,
+        dao_id: Bytes,
+        proposal_id: u32,
+        actions: Vec<ProposalAction>,
+        proposal_owner: Address,
+    ) {
+        ProposalAction::set(&env, dao_id, proposal_id, actions.clone(), proposal_owner);
+        env.events().publish(
+            (PROPOSAL, ACTIONS_SET),
+            ProposalActionsSetEventData {
+                proposal_id,
+                actions,
+            },
+        );
+    }
+
+    fn get_actions(env: Env, proposal_id: u32) -> Vec<ProposalAction> {
+        ProposalAction::get(&env, proposal_id)
+    }
+
     fn get_active_proposals(env: Env, dao_id: Bytes) -> Vec<ActiveProposal> {
         Proposal::get_active(&env, dao_id)
     }
@@ -106,6 +164,43 @@ impl VotesTrait for VotesContract {
         Proposal::get_archived(&env, proposal_id)
     }
 
+    fn get_archived_proposals(env: Env, dao_id: Bytes, start: u32, limit: u32) -> Vec<ProposalRecord> {
+        Proposal::get_archived_page(&env, dao_id, start, limit)
+    }
+
+    fn get_proposal(env: Env, proposal_id: u32) -> ProposalRecord {
+        Proposal::get_record(&env, proposal_id)
+    }
+
+    fn get_proposal_count(env: Env, dao_id: Bytes) -> u32 {
+        Proposal::get_count(&env, dao_id)
+    }
+
+    fn get_proposal_id(env: Env, dao_id: Bytes, number: u32) -> u32 {
+        Proposal::get_id(&env, dao_id, number)
+    }
+
+    fn get_proposal_number(env: Env, proposal_id: u32) -> u32 {
+        Proposal::get_number(&env, proposal_id)
+    }
+
+    fn get_tally(env: Env, proposal_id: u32) -> Tally {
+        Proposal::get_tally(&env, proposal_id)
+    }
+
+    fn claim_deposit(env: Env, proposal_id: u32) -> i128 {
+        let amount = Proposal::claim_deposit(&env, proposal_id);
+        env.events().publish(
+            (PROPOSAL, DEPOSIT_CLAIMED),
+            ProposalDepositClaimedEventData {
+                proposal_id,
+                owner_id: Proposal::get_archived(&env, proposal_id).owner,
+                amount,
+            },
+        );
+        amount
+    }
+
     fn set_configuration(
         env: Env,
         dao_id: Bytes,
@@ -113,23 +208,35 @@ impl VotesTrait for VotesContract {
         min_threshold_configuration: i128,
         dao_owner: Address,
     ) -> Configuration {
-        verify_dao_owner(&env, &dao_id, dao_owner, Self::get_core_id(env.clone()));
-        let configuration = Configuration::set(
-            &env,
-            dao_id.clone(),
-            proposal_duration,
-            min_threshold_configuration,
-        );
-        env.events()
-            .publish(
-                (PROPOSAL, CONF_SET),
-                ProposalConfigurationSetEventData {
-                    dao_id,
-                    proposal_duration,
-                    min_threshold_configuration,
-                }
-            );
-        configuration
+        verify_role(&env, &dao_id, Role::Admin, dao_owner, Self::get_core_id(env.clone()));
+        Self::apply_configuration(&env, dao_id, proposal_duration, min_threshold_configuration)
+    }
+
+    fn set_timelock(
+        env: Env,
+        dao_id: Bytes,
+        execution_delay: u32,
+        guardians: Vec<Address>,
+        veto_threshold: i128,
+        dao_owner: Address,
+    ) -> Configuration {
+        verify_role(&env, &dao_id, Role::Admin, dao_owner, Self::get_core_id(env.clone()));
+        Self::apply_timelock(&env, dao_id, execution_delay, guardians, veto_threshold)
+    }
+
+    fn set_voting(env: Env, dao_id: Bytes, voting: Voting, dao_owner: Address) -> Configuration {
+        verify_role(&env, &dao_id, Role::Admin, dao_owner, Self::get_core_id(env.clone()));
+        Self::apply_voting(&env, dao_id, voting)
+    }
+
+    fn set_quorum(env: Env, dao_id: Bytes, quorum_bps: u32, dao_owner: Address) -> Configuration {
+        verify_role(&env, &dao_id, Role::Admin, dao_owner, Self::get_core_id(env.clone()));
+        Self::apply_quorum(&env, dao_id, quorum_bps)
+    }
+
+    fn set_cancel_refund(env: Env, dao_id: Bytes, cancel_refund_bps: u32, dao_owner: Address) -> Configuration {
+        verify_role(&env, &dao_id, Role::Admin, dao_owner, Self::get_core_id(env.clone()));
+        Self::apply_cancel_refund(&env, dao_id, cancel_refund_bps)
     }
 
     fn get_configuration(env: Env, dao_id: Bytes) -> Configuration {
@@ -140,14 +247,30 @@ impl VotesTrait for VotesContract {
         env.storage().persistent().has(&dao_id)
     }
 
+    fn has_open_proposals(env: Env, dao_id: Bytes) -> bool {
+        Proposal::has_open(&env, dao_id)
+    }
+
+    fn remove_dao(env: Env, dao_id: Bytes) {
+        Self::get_core_id(env.clone()).require_auth();
+        Proposal::remove_dao(&env, dao_id);
+    }
+
+    fn get_checkpoint_window(env: Env, dao_id: Bytes) -> u32 {
+        match Configuration::read(&env, &dao_id) {
+            Some(configuration) => configuration.checkpoint_window(),
+            None => 0,
+        }
+    }
+
     fn remove_configuration(env: Env, dao_id: Bytes, dao_owner: Address) {
         let core_id = Self::get_core_id(env.clone());
 
-        verify_dao_owner(&env, &dao_id, dao_owner, core_id);
+        verify_role(&env, &dao_id, Role::Admin, dao_owner, core_id);
         Configuration::remove(&env, dao_id)
     }
 
-    fn vote(env: Env, dao_id: Bytes, proposal_id: u32, in_favor: bool, voter: Address) -> i128 {
+    fn vote(env: Env, dao_id: Bytes, proposal_id: u32, choice: VoteChoice, voter: Address) -> i128 {
         voter.require_auth();
 
         let core_id = Self::get_core_id(env.clone());
@@ -155,19 +278,27 @@ impl VotesTrait for VotesContract {
 
         let asset_id = core.get_dao_asset_id(&dao_id);
 
-        let voting_power = Proposal::vote(&env, dao_id, proposal_id, in_favor, voter.clone(), asset_id);
+        let voting_power = Proposal::vote(&env, dao_id, proposal_id, choice, voter.clone(), asset_id);
         env.events().publish(
             (PROPOSAL, VOTE_CAST),
             VoteCastEventData {
                 proposal_id,
                 voter_id: voter,
-                in_favor,
+                choice,
                 voting_power
             },
         );
         voting_power
     }
 
+    fn get_ballot(env: Env, proposal_id: u32, voter: Address) -> Ballot {
+        Proposal::get_ballot(&env, proposal_id, voter)
+    }
+
+    fn get_voters(env: Env, proposal_id: u32, start: u32, limit: u32) -> Vec<Address> {
+        Proposal::get_voters(&env, proposal_id, start, limit)
+    }
+
     fn fault_proposal(
         env: Env,
         dao_id: Bytes,
@@ -176,41 +307,292 @@ impl VotesTrait for VotesContract {
         dao_owner: Address,
     ) {
         let core_id = Self::get_core_id(env.clone());
-        verify_dao_owner(&env, &dao_id, dao_owner, core_id);
+        verify_role(&env, &dao_id, Role::ProposalModerator, dao_owner, core_id);
+        Self::apply_fault(&env, dao_id, proposal_id, reason);
+    }
 
-        Proposal::set_faulty(&env, dao_id, proposal_id, reason.clone());
+    fn finalize_proposal(env: Env, dao_id: Bytes, proposal_id: u32) {
+        Proposal::finalize(&env, dao_id, proposal_id);
+    }
+
+    fn cancel_proposal(env: Env, dao_id: Bytes, proposal_id: u32, proposal_owner: Address) -> i128 {
+        let (refund, withheld) = Proposal::cancel(&env, dao_id, proposal_id, proposal_owner.clone());
         env.events().publish(
-            (PROPOSAL, FAULTED),
-            ProposalFaultedEventData {
+            (PROPOSAL, CANCELLED),
+            ProposalCancelledEventData {
                 proposal_id,
-                reason,
+                owner_id: proposal_owner,
+                refund,
+                withheld,
             },
         );
+        refund
     }
 
-    fn finalize_proposal(env: Env, dao_id: Bytes, proposal_id: u32) {
-        Proposal::finalize(&env, dao_id, proposal_id);
+    fn get_withheld_reserve(env: Env, dao_id: Bytes) -> i128 {
+        Proposal::get_withheld(&env, dao_id)
+    }
+
+    fn claim_withheld_reserve(env: Env, dao_id: Bytes, to: Address, dao_owner: Address) -> i128 {
+        verify_role(&env, &dao_id, Role::Admin, dao_owner, Self::get_core_id(env.clone()));
+        Self::apply_withheld_claim(&env, dao_id, to)
+    }
+
+    fn cancel_queued_proposal(env: Env, proposal_id: u32, guardian: Address) {
+        Proposal::cancel_queued(&env, proposal_id, guardian);
+    }
+
+    fn veto(env: Env, proposal_id: u32, voter: Address) -> i128 {
+        voter.require_auth();
+
+        let proposal = Proposal::get_archived(&env, proposal_id);
+        let core_id = Self::get_core_id(env.clone());
+        let core = core_contract::Client::new(&env, &core_id);
+
+        let asset_id = core.get_dao_asset_id(&proposal.dao_id);
+
+        let voting_power = Proposal::veto(&env, proposal_id, voter.clone(), asset_id);
+        env.events().publish(
+            (PROPOSAL, VETO_CAST),
+            VetoCastEventData {
+                proposal_id,
+                voter_id: voter,
+                voting_power
+            },
+        );
+        voting_power
+    }
+
+    fn get_vetoes(env: Env, proposal_id: u32) -> i128 {
+        Proposal::get_vetoes(&env, proposal_id)
     }
 
     fn mark_implemented(env: Env, proposal_id: u32, dao_owner: Address) {
         let proposal = Proposal::get_archived(&env, proposal_id);
 
         let core_id = Self::get_core_id(env.clone());
-        verify_dao_owner(&env, &proposal.dao_id, dao_owner, core_id);
+        verify_role(&env, &proposal.dao_id, Role::Admin, dao_owner, core_id);
+        if !ProposalAction::get(&env, proposal_id).is_empty() {
+            panic_with_error!(env, VotesError::ProposalHasActions)
+        }
         on_before_mark_implemented(&env, &proposal.dao_id, proposal_id);
 
         Proposal::mark_implemented(&env, proposal_id);
     }
+
+    fn execute_proposal(env: Env, proposal_id: u32) {
+        let proposal = Proposal::get_archived(&env, proposal_id);
+        let actions = ProposalAction::get(&env, proposal_id);
+        if actions.is_empty() {
+            panic_with_error!(env, VotesError::ProposalHasNoActions)
+        }
+        on_before_mark_implemented(&env, &proposal.dao_id, proposal_id);
+
+        Proposal::mark_implemented(&env, proposal_id);
+        for action in actions.into_iter() {
+            if action.contract_id == env.current_contract_address() {
+                Self::dispatch(&env, &proposal.dao_id, &action, &env.current_contract_address());
+            } else {
+                action.invoke(&env, &proposal.dao_id);
+            }
+        }
+    }
+
+    fn accept_dao_ownership(env: Env, dao_id: Bytes) {
+        let core = CoreContractClient::new(&env, &Self::get_core_id(env.clone()));
+        core.accept_ownership(&dao_id, &env.current_contract_address());
+    }
+
+    fn execute_council_action(env: Env, dao_id: Bytes, action_id: u32) {
+        let core_id = Self::get_core_id(env.clone());
+        let core = CoreContractClient::new(&env, &core_id);
+        let action = core.take_council_action(&dao_id, &action_id);
+        let action = ProposalAction {
+            contract_id: action.contract_id,
+            func: action.func,
+            args: action.args,
+        };
+        Self::dispatch(&env, &dao_id, &action, &core_id);
+    }
 }
 
-fn verify_dao_owner(env: &Env, dao_id: &Bytes, dao_owner: Address, core_id: Address) {
-    dao_owner.require_auth();
+impl VotesContract {
+    /// Applies an action that targets this contract on behalf of the owner of the DAO, which is
+    /// either this contract or the core contract for a council. Neither can call this contract
+    /// with the owner functions, so they are dispatched internally; the owner argument of the
+    /// action has to be the owner as well.
+    fn dispatch(env: &Env, dao_id: &Bytes, action: &ProposalAction, owner: &Address) {
+        action.check_scope(env, dao_id);
+        let core = CoreContractClient::new(env, &Self::get_core_id(env.clone()));
+        if &core.get_dao(dao_id).owner != owner {
+            panic_with_error!(env, VotesError::NotDaoOwner)
+        }
+        let owner_arg: Address = action_arg(env, action, action.args.len().saturating_sub(1));
+        if &owner_arg != owner {
+            panic_with_error!(env, VotesError::NotDaoOwner)
+        }
 
-    let core = core_contract::Client::new(env, &core_id);
+        let dao_id = dao_id.clone();
+        let func = action.func.clone();
+        if func == Symbol::new(env, "set_configuration") {
+            Self::apply_configuration(env, dao_id, action_arg(env, action, 1), action_arg(env, action, 2));
+        } else if func == Symbol::new(env, "set_timelock") {
+            Self::apply_timelock(
+                env,
+                dao_id,
+                action_arg(env, action, 1),
+                action_arg(env, action, 2),
+                action_arg(env, action, 3),
+            );
+        } else if func == Symbol::new(env, "set_voting") {
+            Self::apply_voting(env, dao_id, action_arg(env, action, 1));
+        } else if func == Symbol::new(env, "set_quorum") {
+            Self::apply_quorum(env, dao_id, action_arg(env, action, 1));
+        } else if func == Symbol::new(env, "set_cancel_refund") {
+            Self::apply_cancel_refund(env, dao_id, action_arg(env, action, 1));
+        } else if func == Symbol::new(env, "claim_withheld_reserve") {
+            Self::apply_withheld_claim(env, dao_id, action_arg(env, action, 1));
+        } else if func == Symbol::new(env, "remove_configuration") {
+            Configuration::remove(env, dao_id);
+        } else if func == Symbol::new(env, "fault_proposal") {
+            Self::apply_fault(env, dao_id, action_arg(env, action, 1), action_arg(env, action, 2));
+        } else {
+            panic_with_error!(env, VotesError::InvalidAction)
+        }
+    }
+
+    fn apply_configuration(
+        env: &Env,
+        dao_id: Bytes,
+        proposal_duration: u32,
+        min_threshold_configuration: i128,
+    ) -> Configuration {
+        let configuration = Configuration::set(
+            env,
+            dao_id.clone(),
+            proposal_duration,
+            min_threshold_configuration,
+        );
+        env.events()
+            .publish(
+                (PROPOSAL, CONF_SET),
+                ProposalConfigurationSetEventData {
+                    dao_id,
+                    proposal_duration,
+                    min_threshold_configuration,
+                }
+            );
+        configuration
+    }
+
+    fn apply_timelock(
+        env: &Env,
+        dao_id: Bytes,
+        execution_delay: u32,
+        guardians: Vec<Address>,
+        veto_threshold: i128,
+    ) -> Configuration {
+        let configuration = Configuration::set_timelock(
+            env,
+            dao_id.clone(),
+            execution_delay,
+            guardians.clone(),
+            veto_threshold,
+        );
+        env.events()
+            .publish(
+                (PROPOSAL, TIMELOCK_SET),
+                ProposalTimelockSetEventData {
+                    dao_id,
+                    execution_delay,
+                    guardians,
+                    veto_threshold,
+                }
+            );
+        configuration
+    }
+
+    fn apply_voting(env: &Env, dao_id: Bytes, voting: Voting) -> Configuration {
+        let configuration = Configuration::set_voting(env, dao_id.clone(), voting.clone());
+        env.events()
+            .publish(
+                (PROPOSAL, VOTING_SET),
+                ProposalVotingSetEventData {
+                    dao_id,
+                    voting,
+                }
+            );
+        configuration
+    }
+
+    fn apply_quorum(env: &Env, dao_id: Bytes, quorum_bps: u32) -> Configuration {
+        let configuration = Configuration::set_quorum(env, dao_id.clone(), quorum_bps);
+        env.events()
+            .publish(
+                (PROPOSAL, QUORUM_SET),
+                ProposalQuorumSetEventData {
+                    dao_id,
+                    quorum_bps,
+                }
+            );
+        configuration
+    }
+
+    fn apply_cancel_refund(env: &Env, dao_id: Bytes, cancel_refund_bps: u32) -> Configuration {
+        let configuration = Configuration::set_cancel_refund(env, dao_id.clone(), cancel_refund_bps);
+        env.events().publish(
+            (PROPOSAL, CANCEL_REFUND_SET),
+            ProposalCancelRefundSetEventData {
+                dao_id,
+                cancel_refund_bps,
+            },
+        );
+        configuration
+    }
 
-    let dao = core.get_dao(dao_id);
+    fn apply_withheld_claim(env: &Env, dao_id: Bytes, to: Address) -> i128 {
+        let amount = Proposal::claim_withheld(env, dao_id.clone(), to.clone());
+        env.events().publish(
+            (PROPOSAL, WITHHELD_CLAIMED),
+            WithheldReserveClaimedEventData { dao_id, to, amount },
+        );
+        amount
+    }
 
-    if dao_owner != dao.owner {
-        panic_with_error!(env, VotesError::NotDaoOwner)
+    fn apply_fault(env: &Env, dao_id: Bytes, proposal_id: u32, reason: Bytes) {
+        Proposal::set_faulty(env, dao_id, proposal_id, reason.clone());
+        env.events().publish(
+            (PROPOSAL, FAULTED),
+            ProposalFaultedEventData {
+                proposal_id,
+                reason,
+            },
+        );
     }
-}
\ No newline at end of file
+}
+
+fn action_arg<T: TryFromVal<Env, Val>>(env: &Env, action: &ProposalAction, index: u32) -> T {
+    match action.args.get(index).map(|arg| T::try_from_val(env, &arg)) {
+        Some(Ok(arg)) => arg,
+        _ => panic_with_error!(env, VotesError::InvalidAction),
+    }
+}
+
+fn verify_protocol_admin(env: &Env, account: Address) {
+    account.require_auth();
+
+    let core = CoreContractClient::new(env, &env.storage().instance().get(&CORE).unwrap());
+    if core.get_protocol_admin() != account {
+        panic_with_error!(env, VotesError::NotProtocolAdmin)
+    }
+}
+
+fn verify_role(env: &Env, dao_id: &Bytes, role: Role, account: Address, core_id: Address) {
+    account.require_auth();
+
+    let core = core_contract::Client::new(env, &core_id);
+
+    if !core.has_role(dao_id, &role, &account) {
+        panic_with_error!(env, VotesError::MissingRole)
+    }
+}
@@ -0,0 +1,115 @@
+use soroban_sdk::{contracttype, Address, Bytes, Env, IntoVal, Map, Symbol, TryFromVal, Val};
+
+use crate::types::{ActiveProposal, Ballot, Configuration, PropStatus, Proposal, VoteChoice, Voting};
+
+/// The configuration of version 0, before the timelock, the voting strategies, the quorum and the
+/// cancel refund
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigurationV0 {
+    pub proposal_duration: u32,
+    pub min_threshold_configuration: i128,
+}
+
+/// A proposal of version 0, before the voting strategy was kept with it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalV0 {
+    pub dao_id: Bytes,
+    pub ledger: u32,
+    pub owner: Address,
+    pub status: PropStatus,
+}
+
+/// An active proposal of version 0, before abstentions were counted
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ActiveProposalV0 {
+    pub id: u32,
+    pub in_favor: i128,
+    pub against: i128,
+    pub inner: ProposalV0,
+}
+
+/// The temporary vote of version 0, true if it was cast in favor
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VotingHistory {
+    Voting(Address, u32),
+}
+
+/// Values of version 0 lack the fields added since, the host can't decode them as the current
+/// types, so they are told apart by their fields
+fn has_field(env: &Env, val: &Val, field: &str) -> bool {
+    let fields: Map<Symbol, Val> = Map::try_from_val(env, val).unwrap();
+    fields.contains_key(Symbol::new(env, field))
+}
+
+/// Converts a configuration of version 0, the settings added since are off
+pub fn configuration(env: &Env, val: &Val) -> Option<Configuration> {
+    if has_field(env, val, "voting") {
+        return None;
+    }
+    let configuration = ConfigurationV0::try_from_val(env, val).unwrap();
+    Some(Configuration::with_defaults(
+        env,
+        configuration.proposal_duration,
+        configuration.min_threshold_configuration,
+    ))
+}
+
+/// Converts a proposal of version 0, which was decided by a simple majority
+pub fn proposal(env: &Env, val: &Val) -> Option<Proposal> {
+    if has_field(env, val, "voting") {
+        return None;
+    }
+    let proposal = ProposalV0::try_from_val(env, val).unwrap();
+    Some(Proposal {
+        dao_id: proposal.dao_id,
+        ledger: proposal.ledger,
+        owner: proposal.owner,
+        status: proposal.status,
+        voting: Voting::Majority,
+    })
+}
+
+/// Converts an active proposal of version 0, which has no abstentions
+pub fn active_proposal(env: &Env, val: &Val) -> Option<ActiveProposal> {
+    if has_field(env, val, "abstain") {
+        return None;
+    }
+    let proposal = ActiveProposalV0::try_from_val(env, val).unwrap();
+    Some(ActiveProposal {
+        id: proposal.id,
+        in_favor: proposal.in_favor,
+        against: proposal.against,
+        abstain: 0,
+        inner: Proposal {
+            dao_id: proposal.inner.dao_id,
+            ledger: proposal.inner.ledger,
+            owner: proposal.inner.owner,
+            status: proposal.inner.status,
+            voting: Voting::Majority,
+        },
+    })
+}
+
+/// Removes the temporary vote of version 0 of a voter and returns it as a ballot
+///
+/// The vote didn't keep its voting power, version 0 counted the balance at the ledger of the
+/// proposal.
+pub fn take_ballot(env: &Env, proposal_id: u32, voter: &Address, asset_id: &Address, ledger: u32) -> Option<Ballot> {
+    let key = VotingHistory::Voting(voter.clone(), proposal_id);
+    let in_favor: bool = env.storage().temporary().get(&key)?;
+    env.storage().temporary().remove(&key);
+    let voting_power: i128 = env.invoke_contract(
+        asset_id,
+        &Symbol::new(env, "get_balance_at"),
+        (voter.clone(), ledger).into_val(env),
+    );
+    Some(Ballot {
+        choice: if in_favor { VoteChoice::InFavor } else { VoteChoice::Against },
+        voting_power,
+        ledger,
+    })
+}
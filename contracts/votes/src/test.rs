@@ -1,13 +1,26 @@
 #![cfg(test)]
 
+extern crate std;
+
+use std::rc::Rc;
+
 use soroban_sdk::{
     testutils::{Address as _, Ledger, LedgerInfo},
-    token, Address, BytesN, Env, IntoVal,
+    symbol_short, token, vec,
+    xdr::{
+        AccountEntry, AccountEntryExt, AccountId, LedgerEntry, LedgerEntryData, LedgerEntryExt, LedgerKey,
+        LedgerKeyAccount, PublicKey, ScAddress, SequenceNumber, Thresholds, Uint256,
+    },
+    Address, Bytes, BytesN, Env, IntoVal, Symbol, TryFromVal, Val, Vec,
 };
 
 use crate::{
-    core_contract::{Client as CoreContractClient, Dao, WASM as CoreWASM},
-    types::{PropStatus, PROPOSAL_MAX_NR, RESERVE_AMOUNT, XLM},
+    core_contract::{Client as CoreContractClient, CouncilAction, Dao, Role, WASM as CoreWASM},
+    migration::{ActiveProposalV0, ConfigurationV0, ProposalV0, VotingHistory},
+    types::{
+        ActiveKey, ArchiveKey, Ballot, PropStatus, MAX_TTL, ProposalAction, Tally, VoteChoice, Voting, FINALIZE_WINDOW, MAX_BPS,
+        RESERVE_AMOUNT, XLM,
+    },
     VotesContract, VotesContractClient,
 };
 
@@ -16,8 +29,12 @@ mod assets_contract {
 }
 
 const PROPOSAL_DURATION: u32 = 10_000;
+const EXECUTION_DELAY: u32 = 1_000;
 const MINT: i128 = 10_000 * XLM;
 
+/// Voting power and choice of the voters of a proposal
+type Ballots<'a> = &'a [(i128, VoteChoice)];
+
 pub const MAX_I128: i128 = 170_141_183_460_469_231_731_687_303_715_884_105_727;
 
 struct Clients {
@@ -42,7 +59,7 @@ impl Clients {
         let native_asset_id = env.register_stellar_asset_contract(Address::random(&env));
         let native_asset_admin = token::StellarAssetClient::new(&env, &native_asset_id);
 
-        core.init(&votes_id, &native_asset_id);
+        core.init(&votes_id, &native_asset_id, &Address::random(&env));
         votes.init(&core_id);
 
         Self {
@@ -59,23 +76,23 @@ fn create_dao(core: &CoreContractClient<'static>, dao_owner: &Address) -> Dao {
     let id = "DIV".into_val(env);
     let name = "Deep Ink Ventures".into_val(env);
 
-    core.create_dao(&id, &name, &dao_owner)
+    core.create_dao(&id, &name, dao_owner)
 }
 
 fn mint_and_create_dao(clients: &Clients, dao_owner: &Address) -> Dao {
-    clients.native_asset_admin.mint(&dao_owner, &MAX_I128);
-    create_dao(&clients.core, &dao_owner)
+    clients.native_asset_admin.mint(dao_owner, &MAX_I128);
+    create_dao(&clients.core, dao_owner)
 }
 
 fn mint_and_create_dao_with_asset(clients: &Clients, dao_owner: &Address) -> Dao {
-    let dao = mint_and_create_dao(&clients, &dao_owner);
+    let dao = mint_and_create_dao(clients, dao_owner);
 
     let core = &clients.core;
     let env = &core.env;
 
     let assets_wasm_hash = env.deployer().upload_contract_wasm(assets_contract::WASM);
     let salt = BytesN::from_array(env, &[1; 32]);
-    core.issue_token(&dao.id, &dao_owner, &assets_wasm_hash, &salt);
+    core.issue_token(&dao.id, dao_owner, &assets_wasm_hash, &salt, &0);
 
     dao
 }
@@ -92,7 +109,7 @@ fn mint_and_create_dao_with_minted_asset(
 
     let asset_id = core.get_dao_asset_id(&dao.id);
     let asset = assets_contract::Client::new(env, &asset_id);
-    asset.mint(dao_owner, &supply);
+    asset.mint(dao_owner, dao_owner, &supply);
 
     dao
 }
@@ -107,10 +124,10 @@ fn create_dao_with_proposal(clients: &Clients, proposal_owner: &Address) -> (Dao
 
     let dao_owner = Address::random(env);
     native_asset_admin.mint(&dao_owner, &MAX_I128);
-    let dao = create_dao(&core, &dao_owner);
+    let dao = create_dao(core, &dao_owner);
 
     let native_asset_id = core.get_native_asset_id();
-    fund_account(&env, &native_asset_id,proposal_owner);
+    fund_account(env, &native_asset_id,proposal_owner);
 
     let proposal_duration: u32 = 10_000;
     let min_threshold_configuration: i128 = 1_000;
@@ -121,7 +138,7 @@ fn create_dao_with_proposal(clients: &Clients, proposal_owner: &Address) -> (Dao
         &dao.owner
     );
 
-    let proposal_id = votes.create_proposal(&dao.id, &proposal_owner);
+    let proposal_id = votes.create_proposal(&dao.id, proposal_owner);
 
     (dao, proposal_id)
 }
@@ -141,20 +158,20 @@ fn setup_accepted_proposal(clients: &Clients) -> (u32, Address) {
         max_entry_expiration: 5_200_000,
     });
     let owner = Address::random(env);
-    let (dao, proposal_id) = create_dao_with_proposal(&clients, &owner);
+    let (dao, proposal_id) = create_dao_with_proposal(clients, &owner);
 
     let assets_wasm_hash = env.deployer().upload_contract_wasm(assets_contract::WASM);
     let salt = BytesN::from_array(env, &[1; 32]);
-    core.issue_token(&dao.id, &dao.owner, &assets_wasm_hash, &salt);
+    core.issue_token(&dao.id, &dao.owner, &assets_wasm_hash, &salt, &0);
 
     let asset_id = core.get_dao_asset_id(&dao.id);
     let asset = assets_contract::Client::new(env, &asset_id);
 
     let supply = 1_000_000;
-    asset.mint(&dao.owner, &supply);
+    asset.mint(&dao.owner, &dao.owner, &supply);
 
     let voter = dao.owner.clone();
-    votes.vote(&dao.id, &proposal_id, &true, &voter);
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &voter);
 
     let proposal = votes
         .get_active_proposals(&dao.id)
@@ -180,9 +197,229 @@ fn setup_accepted_proposal(clients: &Clients) -> (u32, Address) {
     (proposal_id, dao.owner)
 }
 
+fn set_sequence(env: &Env, sequence_number: u32) {
+    env.ledger().set(LedgerInfo {
+        timestamp: 12345,
+        protocol_version: 20,
+        sequence_number,
+        network_id: Default::default(),
+        base_reserve: 10,
+        min_temp_entry_expiration: 10,
+        min_persistent_entry_expiration: 10,
+        max_entry_expiration: 5_200_000,
+    });
+}
+
+fn set_metadata_action(clients: &Clients, dao_id: &Bytes) -> ProposalAction {
+    let env = &clients.core.env;
+    let url: Bytes = "https://deep-ink.ventures".into_val(env);
+    let hash: Bytes = "e337ba02296d560d167b4c301505f1252c29bcf614893a806043d33fd3509181".into_val(env);
+    ProposalAction {
+        contract_id: clients.core.address.clone(),
+        func: Symbol::new(env, "set_metadata"),
+        args: (dao_id.clone(), url, hash, clients.votes.address.clone()).into_val(env),
+    }
+}
+
 fn fund_account(env: &Env, native_asset_id: &Address, address: &Address) {
-    let native_token = token::StellarAssetClient::new(&env, &native_asset_id);
-    native_token.mint(&address, &MINT);
+    let native_token = token::StellarAssetClient::new(env, native_asset_id);
+    native_token.mint(address, &MINT);
+}
+
+/// Creates a Stellar account holding the native asset, which can't be minted
+fn create_account(env: &Env, seed: u8) -> Address {
+    let account_id = AccountId(PublicKey::PublicKeyTypeEd25519(Uint256([seed; 32])));
+    let key = LedgerKey::Account(LedgerKeyAccount {
+        account_id: account_id.clone(),
+    });
+    let entry = LedgerEntry {
+        last_modified_ledger_seq: 0,
+        data: LedgerEntryData::Account(AccountEntry {
+            account_id: account_id.clone(),
+            balance: MINT as i64,
+            seq_num: SequenceNumber(0),
+            num_sub_entries: 0,
+            inflation_dest: None,
+            flags: 0,
+            home_domain: Default::default(),
+            thresholds: Thresholds([1, 0, 0, 0]),
+            signers: Default::default(),
+            ext: AccountEntryExt::V0,
+        }),
+        ext: LedgerEntryExt::V0,
+    };
+    env.host().add_ledger_entry(&Rc::new(key), &Rc::new(entry), None).unwrap();
+    Address::try_from_val(env, &ScAddress::Account(account_id)).unwrap()
+}
+
+#[test]
+fn upgrade_core_keeps_proposals() {
+    let clients = Clients::new();
+    let core = &clients.core;
+    let env = &core.env;
+    let (dao, proposal_id) = create_dao_with_proposal(&clients, &Address::random(env));
+
+    let wasm_hash = env.deployer().upload_contract_wasm(CoreWASM);
+    core.upgrade(&wasm_hash, &core.get_protocol_admin());
+    assert_eq!(core.get_dao(&dao.id), dao);
+    assert_eq!(clients.votes.get_active_proposals(&dao.id).get_unchecked(0).id, proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "#27")]
+fn upgrade_only_as_protocol_admin() {
+    let clients = Clients::new();
+    let env = &clients.votes.env;
+
+    let wasm_hash = env.deployer().upload_contract_wasm(CoreWASM);
+    clients.votes.upgrade(&wasm_hash, &Address::random(env));
+}
+
+#[test]
+#[should_panic(expected = "#28")]
+fn migrate_when_up_to_date() {
+    let clients = Clients::new();
+    let votes = &clients.votes;
+
+    assert_eq!(votes.version(), 1);
+    votes.migrate(&clients.core.get_protocol_admin());
+}
+
+#[test]
+fn migrate_from_version_0() {
+    let clients = Clients::new();
+    let votes = &clients.votes;
+    let env = &votes.env;
+
+    // version 0 kept neither a version nor the protocol config
+    env.as_contract(&votes.address, || {
+        env.storage().instance().remove(&symbol_short!("VERSION"));
+        env.storage().instance().remove(&symbol_short!("PROTOCOL"));
+    });
+    assert_eq!(votes.version(), 0);
+    assert_eq!(votes.migrate(&clients.core.get_protocol_admin()), 1);
+    assert_eq!(votes.version(), 1);
+    let protocol_config = env.as_contract(&votes.address, || {
+        env.storage().instance().has(&symbol_short!("PROTOCOL"))
+    });
+    assert!(protocol_config);
+}
+
+#[test]
+fn configuration_of_version_0_is_migrated() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+
+    let dao = mint_and_create_dao(clients, &Address::random(env));
+    let configuration = ConfigurationV0 {
+        proposal_duration: PROPOSAL_DURATION,
+        min_threshold_configuration: 1_000,
+    };
+    env.as_contract(&votes.address, || env.storage().persistent().set(&dao.id, &configuration));
+
+    let configuration = votes.get_configuration(&dao.id);
+    assert_eq!(configuration.proposal_duration, PROPOSAL_DURATION);
+    assert_eq!(configuration.min_threshold_configuration, 1_000);
+    assert_eq!(configuration.execution_delay, 0);
+    assert_eq!(configuration.voting, Voting::Majority);
+    assert_eq!(configuration.cancel_refund_bps, MAX_BPS);
+
+    // the migrated configuration is kept, later settings build on it
+    let configuration = votes.set_quorum(&dao.id, &1_000, &dao.owner);
+    assert_eq!(configuration.proposal_duration, PROPOSAL_DURATION);
+    assert_eq!(configuration.quorum_bps, 1_000);
+}
+
+#[test]
+fn proposals_of_version_0_are_migrated() {
+    let ref clients @ Clients { ref core, ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    env.budget().reset_unlimited();
+    set_sequence(env, 100);
+
+    let dao_owner = Address::random(env);
+    let dao = mint_and_create_dao_with_minted_asset(clients, &dao_owner, 1_000_000);
+    let asset = assets_contract::Client::new(env, &core.get_dao_asset_id(&dao.id));
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao_owner);
+    let voter = Address::random(env);
+    asset.xfer(&dao_owner, &voter, &400_000);
+    set_sequence(env, 200);
+    let proposal_id = votes.create_proposal(&dao.id, &dao_owner);
+    let archived_id = votes.create_proposal(&dao.id, &dao_owner);
+
+    // the voter voted in favor and one proposal was rejected with version 0
+    let proposal = ProposalV0 {
+        dao_id: dao.id.clone(),
+        ledger: 200,
+        owner: dao_owner.clone(),
+        status: PropStatus::Running,
+    };
+    env.as_contract(&votes.address, || {
+        let active = vec![
+            env,
+            ActiveProposalV0 {
+                id: proposal_id,
+                in_favor: 400_000,
+                against: 0,
+                inner: proposal.clone(),
+            },
+        ];
+        env.storage().persistent().set(&ActiveKey(dao.id.clone()), &active);
+        env.storage().persistent().set(
+            &ArchiveKey(archived_id),
+            &ProposalV0 {
+                status: PropStatus::Rejected,
+                ..proposal
+            },
+        );
+        env.storage().temporary().set(&VotingHistory::Voting(voter.clone(), proposal_id), &true);
+    });
+
+    let archived = votes.get_archived_proposal(&archived_id);
+    assert_eq!(archived.status, PropStatus::Rejected);
+    assert_eq!(archived.voting, Voting::Majority);
+    let active = votes.get_active_proposals(&dao.id).get_unchecked(0);
+    assert_eq!((active.in_favor, active.abstain), (400_000, 0));
+
+    // changing the vote replaces the vote of version 0
+    assert_eq!(votes.vote(&dao.id, &proposal_id, &VoteChoice::Against, &voter), 400_000);
+    let active = votes.get_active_proposals(&dao.id).get_unchecked(0);
+    assert_eq!((active.in_favor, active.against), (0, 400_000));
+    assert_eq!(votes.get_ballot(&proposal_id, &voter).choice, VoteChoice::Against);
+    assert_eq!(votes.get_voters(&proposal_id, &0, &10), vec![env, voter]);
+}
+
+#[test]
+fn proposals_are_numbered_per_dao() {
+    let clients = Clients::new();
+    let (core, votes) = (&clients.core, &clients.votes);
+    let env = &core.env;
+    let proposal_owner = Address::random(env);
+    let (dao, first_id) = create_dao_with_proposal(&clients, &proposal_owner);
+
+    let other_owner = Address::random(env);
+    clients.native_asset_admin.mint(&other_owner, &MAX_I128);
+    let other_dao = core.create_dao(&"ELIO".into_val(env), &"Elio DAO".into_val(env), &other_owner);
+    votes.set_configuration(&other_dao.id, &10_000, &1_000, &other_owner);
+    let other_id = votes.create_proposal(&other_dao.id, &proposal_owner);
+    let second_id = votes.create_proposal(&dao.id, &proposal_owner);
+
+    assert_eq!(votes.get_proposal_count(&dao.id), 2);
+    assert_eq!(votes.get_proposal_count(&other_dao.id), 1);
+    assert_eq!(votes.get_proposal_id(&dao.id, &1), first_id);
+    assert_eq!(votes.get_proposal_id(&dao.id, &2), second_id);
+    assert_eq!(votes.get_proposal_id(&other_dao.id, &1), other_id);
+    assert_eq!(votes.get_proposal_number(&other_id), 1);
+    assert_eq!(votes.get_proposal_number(&second_id), 2);
+}
+
+#[test]
+#[should_panic(expected = "#3")]
+fn get_proposal_id_of_unknown_number() {
+    let clients = Clients::new();
+    let (dao, _) = create_dao_with_proposal(&clients, &Address::random(&clients.core.env));
+
+    clients.votes.get_proposal_id(&dao.id, &2);
 }
 
 #[test]
@@ -216,7 +453,7 @@ fn active_proposals_are_managed() {
     );
 
     let owner = Address::random(env);
-    fund_account(&env, &core.get_native_asset_id(),&owner);
+    fund_account(env, &core.get_native_asset_id(),&owner);
     let proposal_1_id = votes.create_proposal(&dao.id, &owner);
 
     env.ledger().set(LedgerInfo {
@@ -245,7 +482,7 @@ fn active_proposals_are_managed() {
     env.ledger().set(LedgerInfo {
         timestamp: 12345,
         protocol_version: 20,
-        sequence_number: 100 + PROPOSAL_DURATION + 1,
+        sequence_number: 100 + PROPOSAL_DURATION + FINALIZE_WINDOW + 1,
         network_id: Default::default(),
         base_reserve: 10,
         min_temp_entry_expiration: 10,
@@ -266,7 +503,7 @@ fn max_number_of_proposals() {
     env.budget().reset_unlimited();
 
     let dao_owner = Address::random(env);
-    let dao = mint_and_create_dao(&clients, &dao_owner);
+    let dao = mint_and_create_dao(clients, &dao_owner);
 
     let proposal_duration: u32 = 10_000;
     let min_threshold_configuration: i128 = 1_000;
@@ -279,9 +516,9 @@ fn max_number_of_proposals() {
 
     let native_asset_id = &core.get_native_asset_id();
 
-    for _ in 0..=(PROPOSAL_MAX_NR - 1) {
-        let proposal_owner = &Address::random(&env);
-        fund_account(&env, &native_asset_id, &proposal_owner);
+    for _ in 0..core.get_protocol_config().max_active_proposals {
+        let proposal_owner = &Address::random(env);
+        fund_account(env, native_asset_id, proposal_owner);
         let _ = votes.create_proposal(&dao.id, proposal_owner);
     }
 }
@@ -294,7 +531,7 @@ fn error_on_max_number_of_proposals() {
     env.budget().reset_unlimited();
 
     let dao_owner = Address::random(env);
-    let dao = mint_and_create_dao(&clients, &dao_owner);
+    let dao = mint_and_create_dao(clients, &dao_owner);
 
     let proposal_duration: u32 = 10_000;
     let min_threshold_configuration: i128 = 1_000;
@@ -307,9 +544,9 @@ fn error_on_max_number_of_proposals() {
 
     let native_asset_id = &core.get_native_asset_id();
 
-    for _ in 0..=PROPOSAL_MAX_NR {
-        let proposal_owner = &Address::random(&env);
-        fund_account(&env, &native_asset_id, &proposal_owner);
+    for _ in 0..=core.get_protocol_config().max_active_proposals {
+        let proposal_owner = &Address::random(env);
+        fund_account(env, native_asset_id, proposal_owner);
         let _ = votes.create_proposal(&dao.id, proposal_owner);
     }
 }
@@ -321,7 +558,7 @@ fn set_metadata() {
     env.budget().reset_unlimited();
 
     let owner = Address::random(env);
-    let (dao, proposal_id) = create_dao_with_proposal(&clients, &owner);
+    let (dao, proposal_id) = create_dao_with_proposal(clients, &owner);
 
     let url = "https://deep-ink.ventures".into_val(env);
     let hash = "e337ba02296d560d167b4c301505f1252c29bcf614893a806043d33fd3509181".into_val(env);
@@ -339,7 +576,7 @@ fn set_metadata_only_owner() {
     let env = &votes.env;
 
     let owner = Address::random(env);
-    let (dao, proposal_id) = create_dao_with_proposal(&clients, &owner);
+    let (dao, proposal_id) = create_dao_with_proposal(clients, &owner);
 
     let url = "https://deep-ink.ventures".into_val(env);
     let hash = "e337ba02296d560d167b4c301505f1252c29bcf614893a806043d33fd3509181".into_val(env);
@@ -363,7 +600,7 @@ fn can_set_metadata_only_once() {
     let env = &votes.env;
 
     let owner = Address::random(env);
-    let (dao, proposal_id) = create_dao_with_proposal(&clients, &owner);
+    let (dao, proposal_id) = create_dao_with_proposal(clients, &owner);
 
     let url = "https://deep-ink.ventures".into_val(env);
     let hash = "e337ba02296d560d167b4c301505f1252c29bcf614893a806043d33fd3509181".into_val(env);
@@ -377,7 +614,7 @@ fn set_configuration() {
     let env = &votes.env;
 
     let owner = Address::random(env);
-    let dao = mint_and_create_dao(&clients, &owner);
+    let dao = mint_and_create_dao(clients, &owner);
 
     let proposal_duration: u32 = 10_000;
     let min_threshold_configuration: i128 = 1_000;
@@ -393,13 +630,13 @@ fn set_configuration() {
 }
 
 #[test]
-#[should_panic(expected = "#1")]
+#[should_panic(expected = "#32")]
 fn set_configuration_only_owner() {
     let ref clients @ Clients { ref votes, .. } = Clients::new();
     let env = &votes.env;
 
     let owner = Address::random(env);
-    let dao = mint_and_create_dao(&clients, &owner);
+    let dao = mint_and_create_dao(clients, &owner);
 
     let proposal_duration: u32 = 10_000;
     let min_threshold_configuration: i128 = 1_000;
@@ -413,13 +650,13 @@ fn set_configuration_only_owner() {
 }
 
 #[test]
-#[should_panic(expected = "#1")]
+#[should_panic(expected = "#32")]
 fn remove_configuration_only_owner() {
     let ref clients @ Clients { ref votes, .. } = Clients::new();
     let env = &votes.env;
 
     let owner = Address::random(env);
-    let dao = mint_and_create_dao(&clients, &owner);
+    let dao = mint_and_create_dao(clients, &owner);
 
     let proposal_duration: u32 = 10_000;
     let min_threshold_configuration: i128 = 1_000;
@@ -440,7 +677,7 @@ fn non_existing_configuration_panics() {
     let env = &votes.env;
 
     let owner = Address::random(env);
-    let dao = mint_and_create_dao(&clients, &owner);
+    let dao = mint_and_create_dao(clients, &owner);
 
     votes.get_configuration(&dao.id);
 }
@@ -464,7 +701,7 @@ fn mark_faulty() {
     env.budget().reset_unlimited();
 
     let owner = Address::random(env);
-    let (dao, proposal_id) = create_dao_with_proposal(&clients, &owner);
+    let (dao, proposal_id) = create_dao_with_proposal(clients, &owner);
 
     let reason = "bad".into_val(env);
     votes.fault_proposal(&dao.id, &proposal_id, &reason, &dao.owner);
@@ -476,19 +713,177 @@ fn mark_faulty() {
 }
 
 #[test]
-#[should_panic(expected = "#1")]
+fn cancel_proposal() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    let native_token = token::Client::new(env, &clients.core.get_native_asset_id());
+
+    let owner = Address::random(env);
+    let (dao, proposal_id) = create_dao_with_proposal(clients, &owner);
+    votes.set_cancel_refund(&dao.id, &7_500, &dao.owner);
+
+    assert_eq!(votes.cancel_proposal(&dao.id, &proposal_id, &owner), RESERVE_AMOUNT * 3 / 4);
+    assert_eq!(native_token.balance(&owner), MINT - RESERVE_AMOUNT / 4);
+    assert_eq!(votes.get_withheld_reserve(&dao.id), RESERVE_AMOUNT / 4);
+    assert_eq!(votes.get_archived_proposal(&proposal_id).status, PropStatus::Cancelled);
+    assert_eq!(votes.get_proposal(&proposal_id).inner.status, PropStatus::Cancelled);
+}
+
+#[test]
+fn claim_withheld_reserve() {
+    let clients = Clients::new();
+    let votes = &clients.votes;
+    let env = &votes.env;
+    let native_token = token::Client::new(env, &clients.core.get_native_asset_id());
+
+    let owner = Address::random(env);
+    let (dao, proposal_id) = create_dao_with_proposal(&clients, &owner);
+    votes.set_cancel_refund(&dao.id, &0, &dao.owner);
+    votes.cancel_proposal(&dao.id, &proposal_id, &owner);
+
+    let treasury = Address::random(env);
+    assert_eq!(votes.claim_withheld_reserve(&dao.id, &treasury, &dao.owner), RESERVE_AMOUNT);
+    assert_eq!(native_token.balance(&treasury), RESERVE_AMOUNT);
+    assert_eq!(votes.get_withheld_reserve(&dao.id), 0);
+}
+
+#[test]
+#[should_panic(expected = "#24")]
+fn claim_withheld_reserve_without_cancelled_proposals() {
+    let clients = Clients::new();
+    let votes = &clients.votes;
+    let env = &votes.env;
+
+    let (dao, _) = create_dao_with_proposal(&clients, &Address::random(env));
+    votes.claim_withheld_reserve(&dao.id, &Address::random(env), &dao.owner);
+}
+
+#[test]
+fn cancel_proposal_with_native_asset() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+    env.budget().reset_unlimited();
+
+    let core = CoreContractClient::new(&env, &env.register_contract_wasm(None, CoreWASM));
+    let votes = VotesContractClient::new(&env, &env.register_contract(None, VotesContract));
+    // the XDR of the native asset
+    let native_asset_id = env.deployer().with_stellar_asset(Bytes::from_array(&env, &[0; 4])).deploy();
+    let native_token = token::Client::new(&env, &native_asset_id);
+    core.init(&votes.address, &native_asset_id, &Address::random(&env));
+    votes.init(&core.address);
+
+    let dao_owner = create_account(&env, 1);
+    let owner = create_account(&env, 2);
+    let dao = create_dao(&core, &dao_owner);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao_owner);
+    votes.set_cancel_refund(&dao.id, &7_500, &dao_owner);
+    let proposal_id = votes.create_proposal(&dao.id, &owner);
+
+    assert_eq!(votes.cancel_proposal(&dao.id, &proposal_id, &owner), RESERVE_AMOUNT * 3 / 4);
+    assert_eq!(native_token.balance(&owner), MINT - RESERVE_AMOUNT / 4);
+    assert_eq!(native_token.balance(&votes.address), RESERVE_AMOUNT / 4);
+
+    votes.claim_withheld_reserve(&dao.id, &dao_owner, &dao_owner);
+    assert_eq!(native_token.balance(&votes.address), 0);
+}
+
+#[test]
+#[should_panic(expected = "#7")]
+fn cancel_proposal_only_owner() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    let (dao, proposal_id) = create_dao_with_proposal(clients, &Address::random(env));
+
+    votes.cancel_proposal(&dao.id, &proposal_id, &dao.owner);
+}
+
+#[test]
+#[should_panic(expected = "#13")]
+fn cancel_proposal_only_before_voting() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    let dao = mint_and_create_dao_with_minted_asset(clients, &Address::random(env), 1_000_000);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao.owner);
+
+    let owner = Address::random(env);
+    fund_account(env, &clients.core.get_native_asset_id(), &owner);
+    let proposal_id = votes.create_proposal(&dao.id, &owner);
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::Against, &dao.owner);
+
+    votes.cancel_proposal(&dao.id, &proposal_id, &owner);
+}
+
+#[test]
+fn cancel_proposal_with_configuration_of_version_0() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    let native_token = token::Client::new(env, &clients.core.get_native_asset_id());
+
+    let owner = Address::random(env);
+    let (dao, proposal_id) = create_dao_with_proposal(clients, &owner);
+    let configuration = ConfigurationV0 {
+        proposal_duration: PROPOSAL_DURATION,
+        min_threshold_configuration: 1_000,
+    };
+    env.as_contract(&votes.address, || env.storage().persistent().set(&dao.id, &configuration));
+
+    // configurations without a cancel refund return the whole reserve
+    assert_eq!(votes.cancel_proposal(&dao.id, &proposal_id, &owner), RESERVE_AMOUNT);
+    assert_eq!(native_token.balance(&owner), MINT);
+}
+
+#[test]
+#[should_panic(expected = "#29")]
+fn cancel_refund_is_at_most_the_reserve() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let (dao, _) = create_dao_with_proposal(clients, &Address::random(&votes.env));
+
+    votes.set_cancel_refund(&dao.id, &10_001, &dao.owner);
+}
+
+#[test]
+#[should_panic(expected = "#32")]
 fn mark_faulty_only_owner() {
     let ref clients @ Clients { ref votes, .. } = Clients::new();
     let env = &votes.env;
     env.budget().reset_unlimited();
 
     let owner = Address::random(env);
-    let (dao, proposal_id) = create_dao_with_proposal(&clients, &owner);
+    let (dao, proposal_id) = create_dao_with_proposal(clients, &owner);
 
     let reason = "bad".into_val(env);
     votes.fault_proposal(&dao.id, &proposal_id, &reason, &Address::random(env));
 }
 
+#[test]
+fn moderator_faults_proposal() {
+    let ref clients @ Clients { ref core, ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    env.budget().reset_unlimited();
+
+    let (dao, proposal_id) = create_dao_with_proposal(clients, &Address::random(env));
+    let moderator = Address::random(env);
+    core.grant_role(&dao.id, &Role::ProposalModerator, &moderator, &dao.owner);
+
+    let reason: Bytes = "bad".into_val(env);
+    votes.fault_proposal(&dao.id, &proposal_id, &reason, &moderator);
+    assert_eq!(votes.get_proposal(&proposal_id).inner.status, PropStatus::Faulty(reason));
+}
+
+#[test]
+#[should_panic(expected = "#32")]
+fn moderator_cannot_configure() {
+    let ref clients @ Clients { ref core, ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    env.budget().reset_unlimited();
+
+    let (dao, _) = create_dao_with_proposal(clients, &Address::random(env));
+    let moderator = Address::random(env);
+    core.grant_role(&dao.id, &Role::ProposalModerator, &moderator, &dao.owner);
+
+    votes.set_quorum(&dao.id, &5_000, &moderator);
+}
+
 #[test]
 fn vote() {
     let ref clients @ Clients { ref votes, .. } = Clients::new();
@@ -497,7 +892,7 @@ fn vote() {
 
     let dao_owner = Address::random(env);
     let supply = 1_000_000;
-    let dao = mint_and_create_dao_with_minted_asset(&clients, &dao_owner, supply);
+    let dao = mint_and_create_dao_with_minted_asset(clients, &dao_owner, supply);
 
     let proposal_duration: u32 = 10_000;
     let min_threshold_configuration: i128 = 1_000;
@@ -509,11 +904,11 @@ fn vote() {
     );
 
     let owner = Address::random(env);
-    fund_account(&env, &clients.core.get_native_asset_id(),&owner);
+    fund_account(env, &clients.core.get_native_asset_id(),&owner);
     let proposal_id = votes.create_proposal(&dao.id, &owner);
 
     let voter = dao.owner;
-    votes.vote(&dao.id, &proposal_id, &true, &voter);
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &voter);
     let proposal = votes
         .get_active_proposals(&dao.id)
         .get_unchecked(0);
@@ -531,7 +926,7 @@ fn vote_already_cast() {
 
     let dao_owner = Address::random(env);
     let supply = 1_000_000;
-    let dao = mint_and_create_dao_with_minted_asset(&clients, &dao_owner, supply);
+    let dao = mint_and_create_dao_with_minted_asset(clients, &dao_owner, supply);
 
     let proposal_duration: u32 = 10_000;
     let min_threshold_configuration: i128 = 1_000;
@@ -543,12 +938,61 @@ fn vote_already_cast() {
     );
 
     let owner = Address::random(env);
-    fund_account(&env, &clients.core.get_native_asset_id(),&owner);
+    fund_account(env, &clients.core.get_native_asset_id(),&owner);
     let proposal_id = votes.create_proposal(&dao.id, &owner);
 
     let voter = dao.owner;
-    votes.vote(&dao.id, &proposal_id, &true, &voter);
-    votes.vote(&dao.id, &proposal_id, &true, &voter);
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &voter);
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &voter);
+}
+
+#[test]
+fn vote_without_kept_protocol_config() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    set_sequence(env, 100);
+
+    let dao_owner = Address::random(env);
+    let dao = mint_and_create_dao_with_minted_asset(clients, &dao_owner, 1_000_000);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao_owner);
+    let proposal_id = votes.create_proposal(&dao.id, &dao_owner);
+
+    // contracts of the former layout didn't keep the protocol config
+    env.as_contract(&votes.address, || env.storage().instance().remove(&symbol_short!("PROTOCOL")));
+    assert_eq!(votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &dao_owner), 1_000_000);
+}
+
+#[test]
+#[should_panic(expected = "#31")]
+fn vote_after_voting_period() {
+    let clients = Clients::new();
+    let votes = &clients.votes;
+    let env = &votes.env;
+    set_sequence(env, 100);
+
+    let dao_owner = Address::random(env);
+    let dao = mint_and_create_dao_with_minted_asset(&clients, &dao_owner, 1_000_000);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao_owner);
+    let proposal_id = votes.create_proposal(&dao.id, &dao_owner);
+    set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &dao_owner);
+}
+
+#[test]
+#[should_panic(expected = "#5")]
+fn vote_on_faulty_proposal() {
+    let clients = Clients::new();
+    let votes = &clients.votes;
+    let env = &votes.env;
+
+    let dao_owner = Address::random(env);
+    let dao = mint_and_create_dao_with_minted_asset(&clients, &dao_owner, 1_000_000);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao_owner);
+    let proposal_id = votes.create_proposal(&dao.id, &dao_owner);
+    votes.fault_proposal(&dao.id, &proposal_id, &"bad".into_val(env), &dao_owner);
+
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &dao_owner);
 }
 
 #[test]
@@ -560,7 +1004,7 @@ fn can_change_vote() {
 
     let dao_owner = Address::random(env);
     let supply = 1_000_000;
-    let dao = mint_and_create_dao_with_minted_asset(&clients, &dao_owner, supply);
+    let dao = mint_and_create_dao_with_minted_asset(clients, &dao_owner, supply);
 
     let proposal_duration: u32 = 10_000;
     let min_threshold_configuration: i128 = 1_000;
@@ -572,21 +1016,77 @@ fn can_change_vote() {
     );
 
     let owner = Address::random(env);
-    fund_account(&env, &clients.core.get_native_asset_id(),&owner);
+    fund_account(env, &clients.core.get_native_asset_id(),&owner);
     let proposal_id = votes.create_proposal(&dao.id, &owner);
 
     let voter = dao.owner;
-    votes.vote(&dao.id, &proposal_id, &true, &voter);
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &voter);
     let proposal = votes
         .get_active_proposals(&dao.id)
         .get_unchecked(0);
     assert_eq!(proposal.in_favor, supply);
-    votes.vote(&dao.id, &proposal_id, &false, &voter);
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::Against, &voter);
     let proposal = votes
         .get_active_proposals(&dao.id)
         .get_unchecked(0);
     assert_eq!(proposal.against, supply);
     assert_eq!(proposal.in_favor, 0);
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::Abstain, &voter);
+    let proposal = votes
+        .get_active_proposals(&dao.id)
+        .get_unchecked(0);
+    assert_eq!(proposal.abstain, supply);
+    assert_eq!(proposal.against, 0);
+}
+
+#[test]
+fn ballots_are_kept() {
+    let clients = Clients::new();
+    let (core, votes) = (&clients.core, &clients.votes);
+    let env = &votes.env;
+    env.budget().reset_unlimited();
+    set_sequence(env, 100);
+
+    let dao_owner = Address::random(env);
+    let dao = mint_and_create_dao_with_minted_asset(&clients, &dao_owner, 1_000_000);
+    let asset = assets_contract::Client::new(env, &core.get_dao_asset_id(&dao.id));
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao_owner);
+    let voter = Address::random(env);
+    asset.xfer(&dao_owner, &voter, &400_000);
+
+    let proposal_id = votes.create_proposal(&dao.id, &dao_owner);
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &voter);
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::Against, &dao_owner);
+    set_sequence(env, 200);
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::Abstain, &voter);
+
+    set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+    votes.finalize_proposal(&dao.id, &proposal_id);
+
+    let ballot = votes.get_ballot(&proposal_id, &voter);
+    assert_eq!(
+        ballot,
+        Ballot {
+            choice: VoteChoice::Abstain,
+            voting_power: 400_000,
+            ledger: 200,
+        }
+    );
+    assert_eq!(votes.get_ballot(&proposal_id, &dao_owner).voting_power, 600_000);
+    assert_eq!(votes.get_voters(&proposal_id, &0, &10), vec![env, voter, dao_owner.clone()]);
+    assert_eq!(votes.get_voters(&proposal_id, &1, &1), vec![env, dao_owner]);
+}
+
+#[test]
+#[should_panic(expected = "#25")]
+fn non_existing_ballot_panics() {
+    let clients = Clients::new();
+    let votes = &clients.votes;
+    let env = &votes.env;
+    env.budget().reset_unlimited();
+
+    let (_, proposal_id) = create_dao_with_proposal(&clients, &Address::random(env));
+    votes.get_ballot(&proposal_id, &Address::random(env));
 }
 
 #[test]
@@ -605,7 +1105,7 @@ fn rejected_finalize() {
         max_entry_expiration: 5_200_000,
     });
     let owner = Address::random(env);
-    let (dao, proposal_id) = create_dao_with_proposal(&clients, &owner);
+    let (dao, proposal_id) = create_dao_with_proposal(clients, &owner);
 
     let proposal_duration: u32 = 10_000;
 
@@ -645,7 +1145,7 @@ fn accepted_finalize() {
 
     let dao_owner = Address::random(env);
     let supply = 1_000_000;
-    let dao = mint_and_create_dao_with_minted_asset(&clients, &dao_owner, supply);
+    let dao = mint_and_create_dao_with_minted_asset(clients, &dao_owner, supply);
 
     let proposal_duration: u32 = 10_000;
     let min_threshold_configuration: i128 = 1_000;
@@ -658,7 +1158,7 @@ fn accepted_finalize() {
 
     let proposal_id = votes.create_proposal(&dao.id, &dao_owner);
 
-    votes.vote(&dao.id, &proposal_id, &true, &dao.owner);
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &dao.owner);
 
     votes.env.ledger().set(LedgerInfo {
         timestamp: 12345,
@@ -677,6 +1177,83 @@ fn accepted_finalize() {
     assert_eq!(proposal.status, PropStatus::Accepted);
 }
 
+#[test]
+fn archived_proposals_are_indexed() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    env.budget().reset_unlimited();
+    set_sequence(env, 100);
+
+    let dao_owner = Address::random(env);
+    let dao = mint_and_create_dao_with_minted_asset(clients, &dao_owner, 1_000_000);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao.owner);
+
+    let faulty_id = votes.create_proposal(&dao.id, &dao_owner);
+    let accepted_id = votes.create_proposal(&dao.id, &dao_owner);
+    votes.vote(&dao.id, &accepted_id, &VoteChoice::InFavor, &dao.owner);
+
+    let active = votes.get_proposal(&accepted_id);
+    assert_eq!(active.tally.in_favor, 1_000_000);
+    assert_eq!(active.tally.finalized_at, 0);
+    assert_eq!(active.inner.status, PropStatus::Running);
+
+    votes.fault_proposal(&dao.id, &faulty_id, &"bad".into_val(env), &dao.owner);
+    set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+    votes.finalize_proposal(&dao.id, &accepted_id);
+
+    let archived = votes.get_archived_proposals(&dao.id, &0, &10);
+    assert_eq!(archived.len(), 2);
+    assert_eq!(archived.get_unchecked(0).id, faulty_id);
+    assert_eq!(archived.get_unchecked(1).id, accepted_id);
+
+    let page = votes.get_archived_proposals(&dao.id, &1, &10);
+    assert_eq!(page.len(), 1);
+    let accepted = page.get_unchecked(0);
+    assert_eq!(accepted, votes.get_proposal(&accepted_id));
+    assert_eq!(accepted.tally.in_favor, 1_000_000);
+    assert_eq!(accepted.tally.finalized_at, 100 + PROPOSAL_DURATION + 1);
+    assert_eq!(accepted.inner.status, PropStatus::Accepted);
+
+    assert_eq!(votes.get_archived_proposals(&dao.id, &2, &10).len(), 0);
+}
+
+#[test]
+fn archived_proposal_of_version_0_has_no_votes() {
+    let clients = Clients::new();
+    let votes = &clients.votes;
+    let env = &votes.env;
+    let owner = Address::random(env);
+    let (dao, proposal_id) = create_dao_with_proposal(&clients, &owner);
+
+    // version 0 archived proposals without their tally
+    let proposal = ProposalV0 {
+        dao_id: dao.id,
+        ledger: env.ledger().sequence(),
+        owner,
+        status: PropStatus::Rejected,
+    };
+    env.as_contract(&votes.address, || env.storage().persistent().set(&ArchiveKey(proposal_id), &proposal));
+
+    let record = votes.get_proposal(&proposal_id);
+    assert_eq!(record.inner.status, PropStatus::Rejected);
+    assert_eq!(
+        record.tally,
+        Tally {
+            in_favor: 0,
+            against: 0,
+            abstain: 0,
+            finalized_at: 0,
+        }
+    );
+}
+
+#[test]
+#[should_panic(expected = "#3")]
+fn get_unknown_proposal() {
+    let Clients { votes, .. } = Clients::new();
+    votes.get_proposal(&1);
+}
+
 #[test]
 fn mark_implemented() {
     let clients = Clients::new();
@@ -691,7 +1268,19 @@ fn mark_implemented() {
 }
 
 #[test]
-#[should_panic(expected = "#1")]
+fn accepted_proposals_are_open_until_implemented() {
+    let clients = Clients::new();
+    let (proposal_id, dao_owner) = setup_accepted_proposal(&clients);
+    let votes = clients.votes;
+    let dao_id = votes.get_archived_proposal(&proposal_id).dao_id;
+
+    assert!(votes.has_open_proposals(&dao_id));
+    votes.mark_implemented(&proposal_id, &dao_owner);
+    assert!(!votes.has_open_proposals(&dao_id));
+}
+
+#[test]
+#[should_panic(expected = "#32")]
 fn mark_implemented_only_owner() {
     let clients = Clients::new();
     let (proposal_id, _dao_owner) = setup_accepted_proposal(&clients);
@@ -708,29 +1297,74 @@ fn reserves_token_on_proposal_creation() {
 
     let owner = Address::random(env);
     let native_asset_id = &clients.core.get_native_asset_id();
-    let native_token = token::Client::new(&env, &native_asset_id);
+    let native_token = token::Client::new(env, native_asset_id);
 
-    create_dao_with_proposal(&clients, &owner);
+    create_dao_with_proposal(clients, &owner);
 
     // Checks if balance deducted after proposal creation
     let current_balance = native_token.balance(&owner);
-    assert_eq!(current_balance, &MINT - &RESERVE_AMOUNT);
+    assert_eq!(current_balance, MINT - RESERVE_AMOUNT);
 }
 
 #[test]
-fn return_tokens_when_faulty() {
-    let ref clients @ Clients { ref votes, .. } = Clients::new();
+fn reserve_follows_protocol_config() {
+    let ref clients @ Clients { ref core, ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    env.budget().reset_unlimited();
+    let native_token = token::Client::new(env, &core.get_native_asset_id());
+
+    let mut config = core.get_protocol_config();
+    config.proposal_reserve = 50 * XLM;
+    core.set_protocol_config(&config, &core.get_protocol_admin());
+
+    let owner = Address::random(env);
+    let (dao, proposal_id) = create_dao_with_proposal(clients, &owner);
+    assert_eq!(native_token.balance(&owner), MINT - 50 * XLM);
+
+    // the recorded reserve is returned, not the current one
+    config.proposal_reserve = 200 * XLM;
+    core.set_protocol_config(&config, &core.get_protocol_admin());
+    votes.fault_proposal(&dao.id, &proposal_id, &"bad".into_val(env), &dao.owner);
+    assert_eq!(native_token.balance(&owner), MINT);
+}
+
+#[test]
+fn proposals_with_the_max_bump() {
+    let clients = Clients::new();
+    let (core, votes) = (&clients.core, &clients.votes);
+    let env = &votes.env;
+    env.budget().reset_unlimited();
+    env.ledger().with_mut(|ledger| ledger.max_entry_expiration = MAX_TTL + 1);
+
+    let mut config = core.get_protocol_config();
+    config.bump_ledgers = MAX_TTL;
+    core.set_protocol_config(&config, &core.get_protocol_admin());
+
+    let dao_owner = Address::random(env);
+    let dao = mint_and_create_dao_with_minted_asset(&clients, &dao_owner, 1_000_000);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao_owner);
+    let proposal_id = votes.create_proposal(&dao.id, &dao_owner);
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &dao_owner);
+
+    set_sequence(env, env.ledger().sequence() + PROPOSAL_DURATION + 1);
+    votes.finalize_proposal(&dao.id, &proposal_id);
+    assert_eq!(votes.get_archived_proposal(&proposal_id).status, PropStatus::Accepted);
+}
+
+#[test]
+fn return_tokens_when_faulty() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
     let env = &votes.env;
     env.budget().reset_unlimited();
 
     let owner = Address::random(env);
     let native_asset_id = &clients.core.get_native_asset_id();
-    let native_token = token::Client::new(&env, &native_asset_id);
-    let (dao, proposal_id) = create_dao_with_proposal(&clients, &owner);
+    let native_token = token::Client::new(env, native_asset_id);
+    let (dao, proposal_id) = create_dao_with_proposal(clients, &owner);
 
     // Checks if balance deducted after proposal creation
     let current_balance = native_token.balance(&owner);
-    assert_eq!(current_balance, &MINT - &RESERVE_AMOUNT);
+    assert_eq!(current_balance, MINT - RESERVE_AMOUNT);
 
     let reason = "bad".into_val(env);
     votes.fault_proposal(&dao.id, &proposal_id, &reason, &dao.owner);
@@ -757,13 +1391,13 @@ fn returns_tokens_on_finalize() {
     });
     let owner = Address::random(env);
     let native_asset_id = &clients.core.get_native_asset_id();
-    let native_token = token::Client::new(&env, &native_asset_id);
+    let native_token = token::Client::new(env, native_asset_id);
 
-    let (dao, proposal_id) = create_dao_with_proposal(&clients, &owner);
+    let (dao, proposal_id) = create_dao_with_proposal(clients, &owner);
 
     // Checks if balance deducted after proposal creation
     let current_balance = native_token.balance(&owner);
-    assert_eq!(current_balance, &MINT - &RESERVE_AMOUNT);
+    assert_eq!(current_balance, MINT - RESERVE_AMOUNT);
 
     let proposal_duration: u32 = 10_000;
 
@@ -784,4 +1418,880 @@ fn returns_tokens_on_finalize() {
     // Check if reserved token has been returned
     let current_balance = native_token.balance(&owner);
     assert_eq!(&current_balance, &MINT)
-}
\ No newline at end of file
+}
+
+#[test]
+fn expired_proposal_is_archived() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    env.budget().reset_unlimited();
+    set_sequence(env, 100);
+
+    let owner = Address::random(env);
+    let native_token = token::Client::new(env, &clients.core.get_native_asset_id());
+    let (dao, proposal_id) = create_dao_with_proposal(clients, &owner);
+
+    set_sequence(env, 100 + PROPOSAL_DURATION + FINALIZE_WINDOW);
+    assert_eq!(votes.get_active_proposals(&dao.id).len(), 1);
+
+    set_sequence(env, 100 + PROPOSAL_DURATION + FINALIZE_WINDOW + 1);
+    assert_eq!(votes.get_active_proposals(&dao.id).len(), 0);
+    assert_eq!(votes.get_proposal(&proposal_id).inner.status, PropStatus::Expired);
+
+    assert_eq!(votes.claim_deposit(&proposal_id), RESERVE_AMOUNT);
+    assert_eq!(native_token.balance(&owner), MINT);
+
+    let proposal = votes.get_archived_proposal(&proposal_id);
+    assert_eq!(proposal.status, PropStatus::Expired);
+    assert_eq!(votes.get_tally(&proposal_id).in_favor, 0);
+}
+
+#[test]
+fn expired_proposal_is_archived_on_next_proposal() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    env.budget().reset_unlimited();
+    set_sequence(env, 100);
+
+    let (dao, proposal_id) = create_dao_with_proposal(clients, &Address::random(env));
+
+    set_sequence(env, 100 + PROPOSAL_DURATION + FINALIZE_WINDOW + 1);
+    votes.create_proposal(&dao.id, &dao.owner);
+
+    assert_eq!(votes.get_archived_proposal(&proposal_id).status, PropStatus::Expired);
+    assert_eq!(votes.get_active_proposals(&dao.id).len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "#24")]
+fn claim_deposit_only_once() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    env.budget().reset_unlimited();
+    set_sequence(env, 100);
+
+    let (_, proposal_id) = create_dao_with_proposal(clients, &Address::random(env));
+    set_sequence(env, 100 + PROPOSAL_DURATION + FINALIZE_WINDOW + 1);
+
+    votes.claim_deposit(&proposal_id);
+    votes.claim_deposit(&proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "#24")]
+fn claim_deposit_of_running_proposal() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    env.budget().reset_unlimited();
+    set_sequence(env, 100);
+
+    let (_, proposal_id) = create_dao_with_proposal(clients, &Address::random(env));
+    set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+
+    votes.claim_deposit(&proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "#30")]
+fn cannot_finalize_expired_proposal() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    env.budget().reset_unlimited();
+    set_sequence(env, 100);
+
+    let (dao, proposal_id) = create_dao_with_proposal(clients, &Address::random(env));
+    set_sequence(env, 100 + PROPOSAL_DURATION + FINALIZE_WINDOW + 1);
+
+    votes.finalize_proposal(&dao.id, &proposal_id);
+}
+
+#[test]
+fn finalize_after_active_proposals_are_loaded() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    env.budget().reset_unlimited();
+    set_sequence(env, 100);
+
+    let owner = Address::random(env);
+    let (dao, proposal_id) = create_dao_with_proposal(clients, &owner);
+
+    // other accounts load and extend the active proposals once the voting period is over
+    set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+    assert_eq!(votes.get_active_proposals(&dao.id).len(), 1);
+    votes.create_proposal(&dao.id, &dao.owner);
+    assert_eq!(votes.get_proposal(&proposal_id).inner.status, PropStatus::Running);
+
+    votes.finalize_proposal(&dao.id, &proposal_id);
+    assert_eq!(votes.get_archived_proposal(&proposal_id).status, PropStatus::Rejected);
+}
+
+#[test]
+fn execute_proposal() {
+    let ref clients @ Clients { ref core, ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    set_sequence(env, 100);
+
+    let proposal_owner = Address::random(env);
+    let dao = mint_and_create_dao_with_minted_asset(clients, &proposal_owner, 1_000_000);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &proposal_owner);
+    core.propose_owner(&dao.id, &votes.address, &100, &proposal_owner);
+    votes.accept_dao_ownership(&dao.id);
+    let proposal_id = votes.create_proposal(&dao.id, &proposal_owner);
+
+    let actions = vec![env, set_metadata_action(clients, &dao.id)];
+    votes.set_actions(&dao.id, &proposal_id, &actions, &proposal_owner);
+    assert_eq!(votes.get_actions(&proposal_id), actions);
+
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &proposal_owner);
+    set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+    votes.finalize_proposal(&dao.id, &proposal_id);
+    votes.execute_proposal(&proposal_id);
+
+    assert_eq!(votes.get_archived_proposal(&proposal_id).status, PropStatus::Implemented);
+    assert_eq!(core.get_metadata(&dao.id).url, "https://deep-ink.ventures".into_val(env));
+}
+
+#[test]
+fn execute_proposal_mints_dao_asset() {
+    let ref clients @ Clients { ref core, ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    set_sequence(env, 100);
+
+    let proposal_owner = Address::random(env);
+    let dao = mint_and_create_dao_with_minted_asset(clients, &proposal_owner, 1_000_000);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &proposal_owner);
+    core.propose_owner(&dao.id, &votes.address, &100, &proposal_owner);
+    votes.accept_dao_ownership(&dao.id);
+    let proposal_id = votes.create_proposal(&dao.id, &proposal_owner);
+
+    let asset = assets_contract::Client::new(env, &core.get_dao_asset_id(&dao.id));
+    let recipient = Address::random(env);
+    let actions = vec![env, ProposalAction {
+        contract_id: asset.address.clone(),
+        func: Symbol::new(env, "mint"),
+        args: (votes.address.clone(), recipient.clone(), 5_000_i128).into_val(env),
+    }];
+    votes.set_actions(&dao.id, &proposal_id, &actions, &proposal_owner);
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &proposal_owner);
+    set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+    votes.finalize_proposal(&dao.id, &proposal_id);
+    votes.execute_proposal(&proposal_id);
+
+    assert_eq!(asset.balance(&recipient), 5_000);
+}
+
+/// Executes a proposal of a governed DAO with an action that targets the votes contract
+fn execute_governance_action(clients: &Clients, func: &str, args: Vec<Val>) -> Dao {
+    let (core, votes) = (&clients.core, &clients.votes);
+    let env = &votes.env;
+    set_sequence(env, 100);
+
+    let proposal_owner = Address::random(env);
+    let dao = mint_and_create_dao_with_minted_asset(clients, &proposal_owner, 1_000_000);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &proposal_owner);
+    core.propose_owner(&dao.id, &votes.address, &100, &proposal_owner);
+    votes.accept_dao_ownership(&dao.id);
+    let proposal_id = votes.create_proposal(&dao.id, &proposal_owner);
+    assert_eq!(core.get_dao(&dao.id).owner, votes.address);
+
+    let mut action_args = vec![env, dao.id.into_val(env)];
+    action_args.append(&args);
+    let actions = vec![env, ProposalAction {
+        contract_id: votes.address.clone(),
+        func: Symbol::new(env, func),
+        args: action_args,
+    }];
+    votes.set_actions(&dao.id, &proposal_id, &actions, &proposal_owner);
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &proposal_owner);
+    set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+    votes.finalize_proposal(&dao.id, &proposal_id);
+    votes.execute_proposal(&proposal_id);
+    dao
+}
+
+#[test]
+fn governance_sets_configuration() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    let args = vec![env, 5_000_u32.into_val(env), votes.address.into_val(env)];
+    let dao = execute_governance_action(clients, "set_quorum", args);
+
+    assert_eq!(votes.get_configuration(&dao.id).quorum_bps, 5_000);
+}
+
+#[test]
+#[should_panic(expected = "#1")]
+fn governance_actions_name_the_votes_contract_as_owner() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    let args = vec![env, 5_000_u32.into_val(env), Address::random(env).into_val(env)];
+    execute_governance_action(clients, "set_quorum", args);
+}
+
+#[test]
+#[should_panic(expected = "#26")]
+fn governance_actions_are_limited_to_owner_functions() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    let args = vec![env, votes.address.into_val(env)];
+    execute_governance_action(clients, "create_proposal", args);
+}
+
+#[test]
+fn council_sets_configuration() {
+    let ref clients @ Clients { ref core, ref votes, .. } = Clients::new();
+    let env = &votes.env;
+
+    let dao_owner = Address::random(env);
+    let dao = mint_and_create_dao(clients, &dao_owner);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao_owner);
+    let signers = vec![env, Address::random(env), Address::random(env)];
+    core.set_council(&dao.id, &signers, &2, &dao_owner);
+
+    let action = CouncilAction {
+        contract_id: votes.address.clone(),
+        func: Symbol::new(env, "set_quorum"),
+        args: (dao.id.clone(), 5_000_u32, core.address.clone()).into_val(env),
+    };
+    let action_id = core.propose_council_action(&dao.id, &action, &signers.get_unchecked(0));
+    core.approve_council_action(&dao.id, &action_id, &signers.get_unchecked(1));
+    votes.execute_council_action(&dao.id, &action_id);
+
+    assert_eq!(votes.get_configuration(&dao.id).quorum_bps, 5_000);
+    assert!(core.get_council_action(&dao.id, &action_id).executed);
+}
+
+#[test]
+#[should_panic(expected = "#6")]
+fn execute_proposal_only_when_accepted() {
+    let ref clients @ Clients { ref core, ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    set_sequence(env, 100);
+
+    let proposal_owner = Address::random(env);
+    let dao = mint_and_create_dao_with_minted_asset(clients, &proposal_owner, 1_000_000);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &proposal_owner);
+    core.propose_owner(&dao.id, &votes.address, &100, &proposal_owner);
+    votes.accept_dao_ownership(&dao.id);
+    let proposal_id = votes.create_proposal(&dao.id, &proposal_owner);
+
+    let actions = vec![env, set_metadata_action(clients, &dao.id)];
+    votes.set_actions(&dao.id, &proposal_id, &actions, &proposal_owner);
+
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::Against, &proposal_owner);
+    set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+    votes.finalize_proposal(&dao.id, &proposal_id);
+    votes.execute_proposal(&proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "#16")]
+fn execute_proposal_requires_actions() {
+    let clients = Clients::new();
+    let (proposal_id, _dao_owner) = setup_accepted_proposal(&clients);
+
+    clients.votes.execute_proposal(&proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "#15")]
+fn cannot_mark_implemented_with_actions() {
+    let ref clients @ Clients { ref core, ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    set_sequence(env, 100);
+
+    let proposal_owner = Address::random(env);
+    let dao = mint_and_create_dao_with_minted_asset(clients, &proposal_owner, 1_000_000);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &proposal_owner);
+    core.propose_owner(&dao.id, &votes.address, &100, &proposal_owner);
+    votes.accept_dao_ownership(&dao.id);
+    let proposal_id = votes.create_proposal(&dao.id, &proposal_owner);
+
+    let actions = vec![env, set_metadata_action(clients, &dao.id)];
+    votes.set_actions(&dao.id, &proposal_id, &actions, &proposal_owner);
+
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &proposal_owner);
+    set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+    votes.finalize_proposal(&dao.id, &proposal_id);
+    votes.mark_implemented(&proposal_id, &votes.address);
+}
+
+#[test]
+#[should_panic(expected = "#7")]
+fn set_actions_only_owner() {
+    let ref clients @ Clients { ref core, ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    set_sequence(env, 100);
+
+    let proposal_owner = Address::random(env);
+    let dao = mint_and_create_dao_with_minted_asset(clients, &proposal_owner, 1_000_000);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &proposal_owner);
+    core.propose_owner(&dao.id, &votes.address, &100, &proposal_owner);
+    votes.accept_dao_ownership(&dao.id);
+    let proposal_id = votes.create_proposal(&dao.id, &proposal_owner);
+
+    let actions = vec![env, set_metadata_action(clients, &dao.id)];
+    votes.set_actions(&dao.id, &proposal_id, &actions, &Address::random(env));
+}
+
+#[test]
+#[should_panic(expected = "#13")]
+fn cannot_set_actions_after_voting_started() {
+    let ref clients @ Clients { ref core, ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    set_sequence(env, 100);
+
+    let proposal_owner = Address::random(env);
+    let dao = mint_and_create_dao_with_minted_asset(clients, &proposal_owner, 1_000_000);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &proposal_owner);
+    core.propose_owner(&dao.id, &votes.address, &100, &proposal_owner);
+    votes.accept_dao_ownership(&dao.id);
+    let proposal_id = votes.create_proposal(&dao.id, &proposal_owner);
+
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &proposal_owner);
+    let actions = vec![env, set_metadata_action(clients, &dao.id)];
+    votes.set_actions(&dao.id, &proposal_id, &actions, &proposal_owner);
+}
+
+#[test]
+#[should_panic(expected = "#14")]
+fn actions_are_scoped_to_the_dao() {
+    let ref clients @ Clients { ref core, ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    set_sequence(env, 100);
+
+    let proposal_owner = Address::random(env);
+    let dao = mint_and_create_dao_with_minted_asset(clients, &proposal_owner, 1_000_000);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &proposal_owner);
+    core.propose_owner(&dao.id, &votes.address, &100, &proposal_owner);
+    votes.accept_dao_ownership(&dao.id);
+    let proposal_id = votes.create_proposal(&dao.id, &proposal_owner);
+
+    let other_dao_id: Bytes = "OTHER".into_val(env);
+    let actions: Vec<ProposalAction> = vec![env, set_metadata_action(clients, &other_dao_id)];
+    votes.set_actions(&dao.id, &proposal_id, &actions, &proposal_owner);
+}
+
+#[test]
+fn set_timelock() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    let (dao, _) = create_dao_with_proposal(clients, &Address::random(env));
+    let guardian = Address::random(env);
+
+    votes.set_timelock(&dao.id, &EXECUTION_DELAY, &vec![env, guardian.clone()], &1_000, &dao.owner);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &2_000, &dao.owner);
+
+    let configuration = votes.get_configuration(&dao.id);
+    assert_eq!(configuration.min_threshold_configuration, 2_000);
+    assert_eq!(configuration.execution_delay, EXECUTION_DELAY);
+    assert_eq!(configuration.guardians, vec![env, guardian]);
+    assert_eq!(configuration.veto_threshold, 1_000);
+}
+
+#[test]
+#[should_panic(expected = "#32")]
+fn set_timelock_only_owner() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    let (dao, _) = create_dao_with_proposal(clients, &Address::random(env));
+
+    votes.set_timelock(&dao.id, &EXECUTION_DELAY, &Vec::new(env), &0, &Address::random(env));
+}
+
+#[test]
+fn accepted_proposal_is_queued() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    set_sequence(env, 100);
+
+    let dao = mint_and_create_dao_with_minted_asset(clients, &Address::random(env), 1_000_000);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao.owner);
+    votes.set_timelock(&dao.id, &EXECUTION_DELAY, &vec![env, Address::random(env)], &0, &dao.owner);
+
+    let proposal_id = votes.create_proposal(&dao.id, &dao.owner);
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &dao.owner);
+    set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+    votes.finalize_proposal(&dao.id, &proposal_id);
+
+    let queued_until = 100 + PROPOSAL_DURATION + 1 + EXECUTION_DELAY;
+    assert_eq!(votes.get_archived_proposal(&proposal_id).status, PropStatus::Queued(queued_until));
+
+    set_sequence(env, queued_until);
+    votes.mark_implemented(&proposal_id, &dao.owner);
+    assert_eq!(votes.get_archived_proposal(&proposal_id).status, PropStatus::Implemented);
+}
+
+#[test]
+#[should_panic(expected = "#17")]
+fn cannot_mark_implemented_during_timelock() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    set_sequence(env, 100);
+
+    let dao = mint_and_create_dao_with_minted_asset(clients, &Address::random(env), 1_000_000);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao.owner);
+    votes.set_timelock(&dao.id, &EXECUTION_DELAY, &vec![env, Address::random(env)], &0, &dao.owner);
+
+    let proposal_id = votes.create_proposal(&dao.id, &dao.owner);
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &dao.owner);
+    set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+    votes.finalize_proposal(&dao.id, &proposal_id);
+
+    votes.mark_implemented(&proposal_id, &dao.owner);
+}
+
+#[test]
+fn guardian_cancels_queued_proposal() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    let guardian = Address::random(env);
+    set_sequence(env, 100);
+
+    let dao = mint_and_create_dao_with_minted_asset(clients, &Address::random(env), 1_000_000);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao.owner);
+    votes.set_timelock(&dao.id, &EXECUTION_DELAY, &vec![env, guardian.clone()], &0, &dao.owner);
+
+    let proposal_id = votes.create_proposal(&dao.id, &dao.owner);
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &dao.owner);
+    set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+    votes.finalize_proposal(&dao.id, &proposal_id);
+
+    votes.cancel_queued_proposal(&proposal_id, &guardian);
+    assert_eq!(votes.get_archived_proposal(&proposal_id).status, PropStatus::Vetoed);
+}
+
+#[test]
+#[should_panic(expected = "#19")]
+fn cancel_queued_proposal_only_guardian() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    set_sequence(env, 100);
+
+    let dao = mint_and_create_dao_with_minted_asset(clients, &Address::random(env), 1_000_000);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao.owner);
+    votes.set_timelock(&dao.id, &EXECUTION_DELAY, &vec![env, Address::random(env)], &0, &dao.owner);
+
+    let proposal_id = votes.create_proposal(&dao.id, &dao.owner);
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &dao.owner);
+    set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+    votes.finalize_proposal(&dao.id, &proposal_id);
+
+    votes.cancel_queued_proposal(&proposal_id, &Address::random(env));
+}
+
+#[test]
+fn veto_cancels_queued_proposal() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    set_sequence(env, 100);
+
+    let dao = mint_and_create_dao_with_minted_asset(clients, &Address::random(env), 1_000_000);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao.owner);
+    votes.set_timelock(&dao.id, &EXECUTION_DELAY, &vec![env, Address::random(env)], &1_000, &dao.owner);
+
+    let proposal_id = votes.create_proposal(&dao.id, &dao.owner);
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &dao.owner);
+    set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+    votes.finalize_proposal(&dao.id, &proposal_id);
+
+    assert_eq!(votes.veto(&proposal_id, &dao.owner), 1_000_000);
+    assert_eq!(votes.get_vetoes(&proposal_id), 1_000_000);
+    assert_eq!(votes.get_archived_proposal(&proposal_id).status, PropStatus::Vetoed);
+}
+
+#[test]
+fn veto_outlasts_transfers_within_checkpoint_retention() {
+    let ref clients @ Clients { ref core, ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    set_sequence(env, 100);
+
+    let dao = mint_and_create_dao_with_minted_asset(clients, &Address::random(env), 1_000_000);
+    let asset = assets_contract::Client::new(env, &core.get_dao_asset_id(&dao.id));
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao.owner);
+    votes.set_timelock(&dao.id, &EXECUTION_DELAY, &Vec::new(env), &1_000, &dao.owner);
+    let checkpoint_window = votes.get_checkpoint_window(&dao.id);
+    asset.set_checkpoint_retention(&dao.owner, &checkpoint_window);
+
+    let proposal_id = votes.create_proposal(&dao.id, &dao.owner);
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &dao.owner);
+    set_sequence(env, 100 + PROPOSAL_DURATION + FINALIZE_WINDOW);
+    votes.finalize_proposal(&dao.id, &proposal_id);
+
+    // the transfer at the end of the timelock prunes the checkpoints before the window
+    set_sequence(env, 100 + checkpoint_window - 1);
+    asset.xfer(&dao.owner, &Address::random(env), &1_000_000);
+
+    assert_eq!(votes.veto(&proposal_id, &dao.owner), 1_000_000);
+    assert_eq!(votes.get_archived_proposal(&proposal_id).status, PropStatus::Vetoed);
+}
+
+#[test]
+#[should_panic(expected = "#33")]
+fn timelock_is_covered_by_checkpoint_retention() {
+    let ref clients @ Clients { ref core, ref votes, .. } = Clients::new();
+    let env = &votes.env;
+
+    let dao = mint_and_create_dao_with_minted_asset(clients, &Address::random(env), 1_000_000);
+    let asset = assets_contract::Client::new(env, &core.get_dao_asset_id(&dao.id));
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao.owner);
+    asset.set_checkpoint_retention(&dao.owner, &votes.get_checkpoint_window(&dao.id));
+
+    votes.set_timelock(&dao.id, &EXECUTION_DELAY, &Vec::new(env), &1_000, &dao.owner);
+}
+
+#[test]
+#[should_panic(expected = "#20")]
+fn veto_requires_threshold() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    set_sequence(env, 100);
+
+    let dao = mint_and_create_dao_with_minted_asset(clients, &Address::random(env), 1_000_000);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao.owner);
+    votes.set_timelock(&dao.id, &EXECUTION_DELAY, &vec![env, Address::random(env)], &0, &dao.owner);
+
+    let proposal_id = votes.create_proposal(&dao.id, &dao.owner);
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &dao.owner);
+    set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+    votes.finalize_proposal(&dao.id, &proposal_id);
+
+    votes.veto(&proposal_id, &dao.owner);
+}
+
+#[test]
+#[should_panic(expected = "#18")]
+fn cannot_veto_after_timelock() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    set_sequence(env, 100);
+
+    let dao = mint_and_create_dao_with_minted_asset(clients, &Address::random(env), 1_000_000);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao.owner);
+    votes.set_timelock(&dao.id, &EXECUTION_DELAY, &vec![env, Address::random(env)], &1_000, &dao.owner);
+
+    let proposal_id = votes.create_proposal(&dao.id, &dao.owner);
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &dao.owner);
+    set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+    votes.finalize_proposal(&dao.id, &proposal_id);
+
+    set_sequence(env, 100 + PROPOSAL_DURATION + 1 + EXECUTION_DELAY);
+    votes.veto(&proposal_id, &dao.owner);
+}
+
+#[test]
+fn set_voting() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    let (dao, _) = create_dao_with_proposal(clients, &Address::random(env));
+    assert_eq!(votes.get_configuration(&dao.id).voting, Voting::Majority);
+
+    votes.set_voting(&dao.id, &Voting::Unanimity, &dao.owner);
+    assert_eq!(votes.get_configuration(&dao.id).voting, Voting::Unanimity);
+}
+
+#[test]
+#[should_panic(expected = "#22")]
+fn supermajority_has_to_exceed_majority() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    let (dao, _) = create_dao_with_proposal(clients, &Address::random(env));
+
+    votes.set_voting(&dao.id, &Voting::Supermajority(1, 2), &dao.owner);
+}
+
+#[test]
+fn majority_voting() {
+    let cases: [(Ballots, PropStatus); 2] = [
+        (&[(6_000, VoteChoice::InFavor), (4_000, VoteChoice::Against)], PropStatus::Accepted),
+        (&[(5_000, VoteChoice::InFavor), (5_000, VoteChoice::Against)], PropStatus::Rejected),
+    ];
+    for (ballots, status) in cases {
+        let ref clients @ Clients { ref core, ref votes, .. } = Clients::new();
+        let env = &votes.env;
+        set_sequence(env, 100);
+
+        let dao_owner = Address::random(env);
+        let dao = mint_and_create_dao_with_minted_asset(clients, &dao_owner, 1_000_000);
+        let asset = assets_contract::Client::new(env, &core.get_dao_asset_id(&dao.id));
+        votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao_owner);
+        votes.set_voting(&dao.id, &Voting::Majority, &dao_owner);
+        votes.set_quorum(&dao.id, &0, &dao_owner);
+
+        let mut voters = Vec::new(env);
+        for (voting_power, choice) in ballots {
+            let voter = Address::random(env);
+            asset.xfer(&dao_owner, &voter, voting_power);
+            voters.push_back((voter, *choice));
+        }
+
+        let proposal_id = votes.create_proposal(&dao.id, &dao_owner);
+        for (voter, choice) in voters.iter() {
+            votes.vote(&dao.id, &proposal_id, &choice, &voter);
+        }
+        set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+        votes.finalize_proposal(&dao.id, &proposal_id);
+        assert_eq!(votes.get_archived_proposal(&proposal_id).status, status);
+    }
+}
+
+#[test]
+fn supermajority_voting() {
+    let cases: [(Ballots, PropStatus); 4] = [
+        (&[(6_000, VoteChoice::InFavor), (4_000, VoteChoice::Against)], PropStatus::Rejected),
+        (&[(6_666, VoteChoice::InFavor), (3_334, VoteChoice::Against)], PropStatus::Rejected),
+        (&[(6_667, VoteChoice::InFavor), (3_333, VoteChoice::Against)], PropStatus::Accepted),
+        (&[(7_000, VoteChoice::InFavor), (3_000, VoteChoice::Against)], PropStatus::Accepted),
+    ];
+    for (ballots, status) in cases {
+        let ref clients @ Clients { ref core, ref votes, .. } = Clients::new();
+        let env = &votes.env;
+        set_sequence(env, 100);
+
+        let dao_owner = Address::random(env);
+        let dao = mint_and_create_dao_with_minted_asset(clients, &dao_owner, 1_000_000);
+        let asset = assets_contract::Client::new(env, &core.get_dao_asset_id(&dao.id));
+        votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao_owner);
+        votes.set_voting(&dao.id, &Voting::Supermajority(2, 3), &dao_owner);
+        votes.set_quorum(&dao.id, &0, &dao_owner);
+
+        let mut voters = Vec::new(env);
+        for (voting_power, choice) in ballots {
+            let voter = Address::random(env);
+            asset.xfer(&dao_owner, &voter, voting_power);
+            voters.push_back((voter, *choice));
+        }
+
+        let proposal_id = votes.create_proposal(&dao.id, &dao_owner);
+        for (voter, choice) in voters.iter() {
+            votes.vote(&dao.id, &proposal_id, &choice, &voter);
+        }
+        set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+        votes.finalize_proposal(&dao.id, &proposal_id);
+        assert_eq!(votes.get_archived_proposal(&proposal_id).status, status);
+    }
+}
+
+#[test]
+fn absolute_majority_voting() {
+    let cases: [(Ballots, PropStatus); 3] = [
+        (&[(400_000, VoteChoice::InFavor)], PropStatus::Rejected),
+        (&[(500_000, VoteChoice::InFavor)], PropStatus::Rejected),
+        (&[(500_001, VoteChoice::InFavor)], PropStatus::Accepted),
+    ];
+    for (ballots, status) in cases {
+        let ref clients @ Clients { ref core, ref votes, .. } = Clients::new();
+        let env = &votes.env;
+        set_sequence(env, 100);
+
+        let dao_owner = Address::random(env);
+        let dao = mint_and_create_dao_with_minted_asset(clients, &dao_owner, 1_000_000);
+        let asset = assets_contract::Client::new(env, &core.get_dao_asset_id(&dao.id));
+        votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao_owner);
+        votes.set_voting(&dao.id, &Voting::AbsoluteMajority, &dao_owner);
+        votes.set_quorum(&dao.id, &0, &dao_owner);
+
+        let mut voters = Vec::new(env);
+        for (voting_power, choice) in ballots {
+            let voter = Address::random(env);
+            asset.xfer(&dao_owner, &voter, voting_power);
+            voters.push_back((voter, *choice));
+        }
+
+        let proposal_id = votes.create_proposal(&dao.id, &dao_owner);
+        for (voter, choice) in voters.iter() {
+            votes.vote(&dao.id, &proposal_id, &choice, &voter);
+        }
+        set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+        votes.finalize_proposal(&dao.id, &proposal_id);
+        assert_eq!(votes.get_archived_proposal(&proposal_id).status, status);
+    }
+}
+
+#[test]
+fn voting_with_max_supply() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    set_sequence(env, 100);
+
+    let dao_owner = Address::random(env);
+    let dao = mint_and_create_dao_with_minted_asset(clients, &dao_owner, MAX_I128);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao_owner);
+
+    votes.set_voting(&dao.id, &Voting::AbsoluteMajority, &dao_owner);
+    let absolute_id = votes.create_proposal(&dao.id, &dao_owner);
+    votes.vote(&dao.id, &absolute_id, &VoteChoice::InFavor, &dao_owner);
+
+    votes.set_voting(&dao.id, &Voting::Supermajority(2, 3), &dao_owner);
+    let supermajority_id = votes.create_proposal(&dao.id, &dao_owner);
+    votes.vote(&dao.id, &supermajority_id, &VoteChoice::InFavor, &dao_owner);
+
+    set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+    votes.finalize_proposal(&dao.id, &absolute_id);
+    votes.finalize_proposal(&dao.id, &supermajority_id);
+
+    assert_eq!(votes.get_archived_proposal(&absolute_id).status, PropStatus::Accepted);
+    assert_eq!(votes.get_archived_proposal(&supermajority_id).status, PropStatus::Accepted);
+}
+
+#[test]
+fn unanimity_voting() {
+    let cases: [(Ballots, PropStatus); 2] = [
+        (&[(10_000, VoteChoice::InFavor), (1, VoteChoice::Against)], PropStatus::Rejected),
+        (&[(10_000, VoteChoice::InFavor)], PropStatus::Accepted),
+    ];
+    for (ballots, status) in cases {
+        let ref clients @ Clients { ref core, ref votes, .. } = Clients::new();
+        let env = &votes.env;
+        set_sequence(env, 100);
+
+        let dao_owner = Address::random(env);
+        let dao = mint_and_create_dao_with_minted_asset(clients, &dao_owner, 1_000_000);
+        let asset = assets_contract::Client::new(env, &core.get_dao_asset_id(&dao.id));
+        votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao_owner);
+        votes.set_voting(&dao.id, &Voting::Unanimity, &dao_owner);
+        votes.set_quorum(&dao.id, &0, &dao_owner);
+
+        let mut voters = Vec::new(env);
+        for (voting_power, choice) in ballots {
+            let voter = Address::random(env);
+            asset.xfer(&dao_owner, &voter, voting_power);
+            voters.push_back((voter, *choice));
+        }
+
+        let proposal_id = votes.create_proposal(&dao.id, &dao_owner);
+        for (voter, choice) in voters.iter() {
+            votes.vote(&dao.id, &proposal_id, &choice, &voter);
+        }
+        set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+        votes.finalize_proposal(&dao.id, &proposal_id);
+        assert_eq!(votes.get_archived_proposal(&proposal_id).status, status);
+    }
+}
+
+#[test]
+fn quorum_of_supply() {
+    let cases: [(Ballots, PropStatus); 2] = [
+        (&[(300_000, VoteChoice::InFavor), (199_999, VoteChoice::Against)], PropStatus::Rejected),
+        (&[(300_000, VoteChoice::InFavor), (200_000, VoteChoice::Against)], PropStatus::Accepted),
+    ];
+    for (ballots, status) in cases {
+        let ref clients @ Clients { ref core, ref votes, .. } = Clients::new();
+        let env = &votes.env;
+        set_sequence(env, 100);
+
+        let dao_owner = Address::random(env);
+        let dao = mint_and_create_dao_with_minted_asset(clients, &dao_owner, 1_000_000);
+        let asset = assets_contract::Client::new(env, &core.get_dao_asset_id(&dao.id));
+        votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao_owner);
+        votes.set_voting(&dao.id, &Voting::Majority, &dao_owner);
+        votes.set_quorum(&dao.id, &5_000, &dao_owner);
+
+        let mut voters = Vec::new(env);
+        for (voting_power, choice) in ballots {
+            let voter = Address::random(env);
+            asset.xfer(&dao_owner, &voter, voting_power);
+            voters.push_back((voter, *choice));
+        }
+
+        let proposal_id = votes.create_proposal(&dao.id, &dao_owner);
+        for (voter, choice) in voters.iter() {
+            votes.vote(&dao.id, &proposal_id, &choice, &voter);
+        }
+        set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+        votes.finalize_proposal(&dao.id, &proposal_id);
+        assert_eq!(votes.get_archived_proposal(&proposal_id).status, status);
+    }
+}
+
+#[test]
+fn quorum_is_kept_with_proposal() {
+    let ref clients @ Clients { ref core, ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    set_sequence(env, 100);
+
+    let dao_owner = Address::random(env);
+    let dao = mint_and_create_dao_with_minted_asset(clients, &dao_owner, 1_000_000);
+    let asset = assets_contract::Client::new(env, &core.get_dao_asset_id(&dao.id));
+    asset.xfer(&dao_owner, &Address::random(env), &400_000);
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao_owner);
+    votes.set_quorum(&dao.id, &5_000, &dao_owner);
+
+    let proposal_id = votes.create_proposal(&dao.id, &dao_owner);
+    votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &dao_owner);
+
+    // raising the quorum above the cast votes doesn't affect the running proposal
+    votes.set_quorum(&dao.id, &MAX_BPS, &dao_owner);
+    set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+    votes.finalize_proposal(&dao.id, &proposal_id);
+
+    assert_eq!(votes.get_archived_proposal(&proposal_id).status, PropStatus::Accepted);
+}
+
+#[test]
+#[should_panic(expected = "#23")]
+fn quorum_is_capped() {
+    let ref clients @ Clients { ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    let (dao, _) = create_dao_with_proposal(clients, &Address::random(env));
+
+    votes.set_quorum(&dao.id, &10_001, &dao.owner);
+}
+
+#[test]
+fn abstain_counts_towards_quorum_only() {
+    let cases: [(u32, Ballots, PropStatus); 2] = [
+        (5_000, &[(300_000, VoteChoice::InFavor), (200_000, VoteChoice::Abstain)], PropStatus::Accepted),
+        (0, &[(4_000, VoteChoice::InFavor), (4_000, VoteChoice::Against), (10_000, VoteChoice::Abstain)], PropStatus::Rejected),
+    ];
+    for (quorum_bps, ballots, status) in cases {
+        let ref clients @ Clients { ref core, ref votes, .. } = Clients::new();
+        let env = &votes.env;
+        set_sequence(env, 100);
+
+        let dao_owner = Address::random(env);
+        let dao = mint_and_create_dao_with_minted_asset(clients, &dao_owner, 1_000_000);
+        let asset = assets_contract::Client::new(env, &core.get_dao_asset_id(&dao.id));
+        votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao_owner);
+        votes.set_voting(&dao.id, &Voting::Majority, &dao_owner);
+        votes.set_quorum(&dao.id, &quorum_bps, &dao_owner);
+
+        let mut voters = Vec::new(env);
+        for (voting_power, choice) in ballots {
+            let voter = Address::random(env);
+            asset.xfer(&dao_owner, &voter, voting_power);
+            voters.push_back((voter, *choice));
+        }
+
+        let proposal_id = votes.create_proposal(&dao.id, &dao_owner);
+        for (voter, choice) in voters.iter() {
+            votes.vote(&dao.id, &proposal_id, &choice, &voter);
+        }
+        set_sequence(env, 100 + PROPOSAL_DURATION + 1);
+        votes.finalize_proposal(&dao.id, &proposal_id);
+        assert_eq!(votes.get_archived_proposal(&proposal_id).status, status);
+    }
+}
+
+#[test]
+fn vote_with_delegated_power() {
+    let ref clients @ Clients { ref core, ref votes, .. } = Clients::new();
+    let env = &votes.env;
+    set_sequence(env, 100);
+
+    let dao_owner = Address::random(env);
+    let dao = mint_and_create_dao_with_minted_asset(clients, &dao_owner, 1_000_000);
+    let asset = assets_contract::Client::new(env, &core.get_dao_asset_id(&dao.id));
+    votes.set_configuration(&dao.id, &PROPOSAL_DURATION, &1_000, &dao_owner);
+
+    let delegate = Address::random(env);
+    asset.delegate(&dao_owner, &delegate);
+    let proposal_id = votes.create_proposal(&dao.id, &dao_owner);
+
+    // delegating after the proposal was created doesn't change its voting power
+    set_sequence(env, 101);
+    asset.delegate(&dao_owner, &dao_owner);
+
+    assert_eq!(votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &delegate), 1_000_000);
+    assert_eq!(votes.vote(&dao.id, &proposal_id, &VoteChoice::InFavor, &dao_owner), 0);
+}
@@ -1,20 +1,71 @@
-use soroban_sdk::{contracttype, Address, Bytes, Env, IntoVal, Symbol, Vec, token, panic_with_error, symbol_short};
+use soroban_sdk::{contracttype, Address, Bytes, Env, IntoVal, Symbol, TryFromVal, Val, Vec, token, panic_with_error, symbol_short};
 
 mod core_contract {
     soroban_sdk::contractimport!(file = "../../wasm/elio_core.wasm");
 }
-use core_contract::Client as CoreContractClient;
+use core_contract::{Client as CoreContractClient, ProtocolConfig};
 
 use crate::error::VotesError;
+use crate::migration;
 
 use crate::events::{ProposalStatusUpdateEventData, STATUS_UPDATE, PROPOSAL, CORE};
 use crate::hooks::{on_vote, on_before_proposal_creation, on_before_set_metadata, on_set_configuration, on_before_fault_proposal, on_before_finalize_proposal};
 
 #[contracttype]
-struct ActiveKey(Bytes);
+pub(crate) struct ActiveKey(pub Bytes);
 
 #[contracttype]
-struct ArchiveKey(u32);
+pub(crate) struct ArchiveKey(pub u32);
+
+/// Per proposal storage, the variant name keeps the keys apart from the tuple struct keys
+#[derive(Clone)]
+#[contracttype]
+enum ProposalArtifact {
+    Actions(u32),
+    Vetoes(u32),
+    Veto(u32, Address),
+    Tally(u32),
+    /// The reserve paid on creation, it is returned on finalization
+    Reserve(u32),
+    /// The reserve of an expired proposal that can be claimed
+    Deposit(u32),
+    Dao(u32),
+    Ballot(u32, Address),
+    VoterCount(u32),
+    Voter(u32, u32),
+    /// The number of the proposal within its DAO
+    Number(u32),
+    /// The quorum of the DAO when the proposal was created
+    Quorum(u32),
+}
+
+/// Per DAO numbering of the proposals, the number of proposals created and the id of each number
+///
+/// The variant names have to differ from the ones of `ArchiveIndex` to keep the keys apart.
+#[derive(Clone)]
+#[contracttype]
+enum DaoProposals {
+    NumberCount(Bytes),
+    NumberedId(Bytes, u32),
+    /// The number of accepted proposals that are neither implemented nor vetoed
+    PendingCount(Bytes),
+}
+
+/// The shares of the reserves withheld from the owners of cancelled proposals of a DAO, they
+/// are kept until the DAO claims them
+#[derive(Clone)]
+#[contracttype]
+enum DaoReserve {
+    Withheld(Bytes),
+}
+
+/// Per DAO index of the archived proposals in the order they were archived
+#[derive(Clone)]
+#[contracttype]
+enum ArchiveIndex {
+    Count(Bytes),
+    Entry(Bytes, u32),
+}
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -23,6 +74,8 @@ pub struct Proposal {
     pub ledger: u32,
     pub owner: Address,
     pub status: PropStatus,
+    /// The voting strategy of the DAO when the proposal was created
+    pub voting: Voting,
 }
 
 #[contracttype]
@@ -31,9 +84,36 @@ pub struct ActiveProposal {
     pub id: u32,
     pub in_favor: i128,
     pub against: i128,
+    pub abstain: i128,
     pub inner: Proposal,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Tally {
+    pub in_favor: i128,
+    pub against: i128,
+    pub abstain: i128,
+    /// The ledger the proposal was archived at, 0 while it is active
+    pub finalized_at: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalRecord {
+    pub id: u32,
+    pub tally: Tally,
+    pub inner: Proposal,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalAction {
+    pub contract_id: Address,
+    pub func: Symbol,
+    pub args: Vec<Val>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum PropStatus {
@@ -42,37 +122,129 @@ pub enum PropStatus {
     Rejected,
     Faulty(Bytes),
     Implemented,
+    /// Accepted, but can't be executed before the given ledger
+    Queued(u32),
+    Vetoed,
+    /// The voting period ended without the proposal being finalized
+    Expired,
+    /// Withdrawn by its owner before anyone voted
+    Cancelled,
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Voting {
+    /// More votes in favor than against
     Majority,
+    /// The votes in favor make up at least the given ratio (numerator, denominator) of all votes
+    Supermajority(u32, u32),
+    /// More votes in favor than half of the total supply at the ledger of the proposal
+    AbsoluteMajority,
+    /// Votes in favor, but not a single vote against
+    Unanimity,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VoteChoice {
+    InFavor,
+    Against,
+    /// Counts towards the quorum, but not towards the majority
+    Abstain,
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub enum VotingHistory {
-    Voting(Address, u32),
+pub struct Ballot {
+    pub choice: VoteChoice,
+    pub voting_power: i128,
+    /// The ledger the vote was cast or last changed at
+    pub ledger: u32,
 }
 
 pub const XLM: i128 = 10_000_000;
+/// The reserve of proposals created before the reserves were recorded
 pub const RESERVE_AMOUNT: i128 = 100 * XLM;
-pub const PROPOSAL_MAX_NR: u32 = 25;
+pub const MAX_BPS: u32 = 10_000;
+/// The max ledgers an entry can be bumped by, the max entry expiration of the network (180 days)
+/// includes the current ledger
+pub const MAX_TTL: u32 = 3_110_400 - 1;
+/// The ledgers after the voting period in which a proposal can be finalized, about a day,
+/// afterwards it expires
+pub const FINALIZE_WINDOW: u32 = 17_280;
 
-pub const A_WEEK_IN_LEDGERS: u32 = 100800;
-pub const BUMP_A_MONTH: u32 = 432000;
-pub const BUMP_A_MONTH_THRESHOLD: u32 = 432000 - A_WEEK_IN_LEDGERS;
+/// The layout of the storage. Version 0 is the layout before versioning, its configurations,
+/// proposals and votes are rewritten when they are read, see `migration`.
+pub const STORAGE_VERSION: u32 = 1;
 
 const PROP_ID: Symbol = symbol_short!("PROP_ID");
+const PROTOCOL: Symbol = symbol_short!("PROTOCOL");
+const VERSION: Symbol = symbol_short!("VERSION");
+
+pub fn read_version(env: &Env) -> u32 {
+    env.storage().instance().get(&VERSION).unwrap_or(0)
+}
+
+pub fn write_version(env: &Env) {
+    env.storage().instance().set(&VERSION, &STORAGE_VERSION);
+}
+
+/// Rewrites the storage of an older layout after an upgrade, returns the previous version
+pub fn migrate(env: &Env) -> u32 {
+    let from_version = read_version(env);
+    if from_version >= STORAGE_VERSION {
+        panic_with_error!(env, VotesError::AlreadyMigrated)
+    }
+    if from_version < 1 {
+        // version 0 read the protocol config through the core on every call
+        sync_protocol_config(env, &env.storage().instance().get(&CORE).unwrap());
+    }
+    write_version(env);
+    from_version
+}
+
+/// The protocol config of the core contract, kept here to bump the storage without calling the core,
+/// it is read through the core until it is kept
+pub fn protocol_config(env: &Env) -> ProtocolConfig {
+    match env.storage().instance().get(&PROTOCOL) {
+        Some(config) => config,
+        None => sync_protocol_config(env, &env.storage().instance().get(&CORE).unwrap()),
+    }
+}
+
+/// Reads the protocol config through the core contract and keeps it
+pub fn sync_protocol_config(env: &Env, core_id: &Address) -> ProtocolConfig {
+    let config = CoreContractClient::new(env, core_id).get_protocol_config();
+    env.storage().instance().set(&PROTOCOL, &config);
+    config
+}
+
+impl ActiveProposal {
+    /// A proposal is active during its voting period and, as long as it is running, during the
+    /// finalize window after it
+    fn is_active(&self, env: &Env, proposal_duration: u32) -> bool {
+        let voting_end = self.inner.ledger + proposal_duration;
+        env.ledger().sequence() <= voting_end
+            || (self.inner.status == PropStatus::Running && env.ledger().sequence() <= voting_end + FINALIZE_WINDOW)
+    }
+
+    fn tally(&mut self, choice: VoteChoice) -> &mut i128 {
+        match choice {
+            VoteChoice::InFavor => &mut self.in_favor,
+            VoteChoice::Against => &mut self.against,
+            VoteChoice::Abstain => &mut self.abstain,
+        }
+    }
+}
 
 impl Proposal {
 
     pub fn create(env: &Env, dao_id: Bytes, owner: Address, core_id: Address) -> u32 {
         owner.require_auth();
 
-        let mut proposals = Self::get_active(env, dao_id.clone());
-        if proposals.len() == PROPOSAL_MAX_NR {
+        let config = sync_protocol_config(env, &core_id);
+        let mut proposals = Self::prune_active(env, dao_id.clone());
+        if proposals.len() >= config.max_active_proposals {
             panic_with_error!(env, VotesError::MaxProposalsReached)
         }
 
@@ -83,46 +255,115 @@ impl Proposal {
         let native_asset_id = core.get_native_asset_id();
         let native_token = token::Client::new(env, &native_asset_id);
         let contract = env.current_contract_address();
-        native_token.transfer(&owner, &contract, &RESERVE_AMOUNT);
+        native_token.transfer(&owner, &contract, &config.proposal_reserve);
 
+        let configuration = Configuration::get(env, dao_id.clone());
         let id = env.storage().instance().get(&PROP_ID).unwrap_or(0);
         proposals.push_back(ActiveProposal {
             id,
             in_favor: 0,
             against: 0,
+            abstain: 0,
             inner: Proposal {
                 dao_id: dao_id.clone(),
                 ledger: env.ledger().sequence(),
                 status: PropStatus::Running,
                 owner,
+                voting: configuration.voting.clone(),
             },
         });
         let key = ActiveKey(dao_id.clone());
+        let proposal_duration = configuration.proposal_duration;
 
         env.storage().persistent().set(&key, &proposals);
         env.storage().instance().set(&PROP_ID, &(id + 1));
 
-        env.storage().instance().bump(BUMP_A_MONTH_THRESHOLD, BUMP_A_MONTH);
-        env.storage().persistent().bump(&key, BUMP_A_MONTH_THRESHOLD, BUMP_A_MONTH + Configuration::get(env, dao_id).proposal_duration);
+        // proposals are numbered per DAO starting at 1, the global id is kept for the other functions
+        let count_key = DaoProposals::NumberCount(dao_id.clone());
+        let number = env.storage().persistent().get::<DaoProposals, u32>(&count_key).unwrap_or(0) + 1;
+        let id_key = DaoProposals::NumberedId(dao_id.clone(), number);
+        let number_key = ProposalArtifact::Number(id);
+        env.storage().persistent().set(&count_key, &number);
+        env.storage().persistent().set(&id_key, &id);
+        env.storage().persistent().set(&number_key, &number);
+
+        let dao_key = ProposalArtifact::Dao(id);
+        env.storage().persistent().set(&dao_key, &dao_id);
+        let reserve_key = ProposalArtifact::Reserve(id);
+        env.storage().persistent().set(&reserve_key, &config.proposal_reserve);
+        let quorum_key = ProposalArtifact::Quorum(id);
+        env.storage().persistent().set(&quorum_key, &Quorum::of(&configuration));
+
+        env.storage().instance().bump(config.bump_threshold, config.bump_ledgers);
+        env.storage().persistent().bump(&key, config.bump_threshold, bump_ledgers(&config, proposal_duration));
+        env.storage().persistent().bump(&dao_key, config.bump_threshold, bump_ledgers(&config, proposal_duration));
+        env.storage().persistent().bump(&reserve_key, config.bump_threshold, bump_ledgers(&config, proposal_duration));
+        env.storage().persistent().bump(&quorum_key, config.bump_threshold, bump_ledgers(&config, proposal_duration));
+        env.storage().persistent().bump(&count_key, config.bump_threshold, config.bump_ledgers);
+        env.storage().persistent().bump(&id_key, config.bump_threshold, bump_ledgers(&config, proposal_duration));
+        env.storage().persistent().bump(&number_key, config.bump_threshold, bump_ledgers(&config, proposal_duration));
         id
     }
 
-    pub fn get_active(env: &Env, dao_id: Bytes) -> Vec<ActiveProposal> {
-        let key = ActiveKey(dao_id.clone());
+    /// Gets the number of proposals created for a DAO, which is the number of its latest proposal
+    pub fn get_count(env: &Env, dao_id: Bytes) -> u32 {
+        let config = protocol_config(env);
+        let key = DaoProposals::NumberCount(dao_id);
         if !env.storage().persistent().has(&key) {
-            return Vec::new(env);
+            return 0;
+        }
+        env.storage().persistent().bump(&key, config.bump_threshold, config.bump_ledgers);
+        env.storage().persistent().get(&key).unwrap()
+    }
+
+    /// Gets the global id of the proposal with the given number within a DAO
+    pub fn get_id(env: &Env, dao_id: Bytes, number: u32) -> u32 {
+        let config = protocol_config(env);
+        let key = DaoProposals::NumberedId(dao_id, number);
+        if !env.storage().persistent().has(&key) {
+            panic_with_error!(env, VotesError::ProposalNotFound)
+        }
+        env.storage().persistent().bump(&key, config.bump_threshold, config.bump_ledgers);
+        env.storage().persistent().get(&key).unwrap()
+    }
+
+    /// Gets the number of a proposal within its DAO
+    pub fn get_number(env: &Env, proposal_id: u32) -> u32 {
+        let config = protocol_config(env);
+        let key = ProposalArtifact::Number(proposal_id);
+        if !env.storage().persistent().has(&key) {
+            panic_with_error!(env, VotesError::ProposalNotFound)
         }
-        let active_proposals: Vec<ActiveProposal> = env.storage().persistent().get(&key).unwrap();
-        env.storage().persistent().bump(&key, BUMP_A_MONTH_THRESHOLD, BUMP_A_MONTH + Configuration::get(env, dao_id.clone()).proposal_duration);
+        env.storage().persistent().bump(&key, config.bump_threshold, config.bump_ledgers);
+        env.storage().persistent().get(&key).unwrap()
+    }
+
+    /// Gets the proposals of a DAO that are active, without archiving the lapsed ones
+    pub fn get_active(env: &Env, dao_id: Bytes) -> Vec<ActiveProposal> {
+        let proposal_duration = Configuration::get(env, dao_id.clone()).proposal_duration;
         let mut filtered_proposals: Vec<ActiveProposal> = Vec::new(env);
+        for proposal in Self::load_active(env, dao_id).into_iter() {
+            if proposal.is_active(env, proposal_duration) {
+                filtered_proposals.push_back(proposal);
+            }
+        }
+        filtered_proposals
+    }
 
+    /// Removes the proposals that are no longer active from the list of a DAO, the ones that
+    /// were not finalized within the finalize window are archived as expired
+    fn prune_active(env: &Env, dao_id: Bytes) -> Vec<ActiveProposal> {
+        let key = ActiveKey(dao_id.clone());
+        let active_proposals = Self::load_active(env, dao_id.clone());
         let proposal_duration = Configuration::get(env, dao_id).proposal_duration;
+        let mut filtered_proposals: Vec<ActiveProposal> = Vec::new(env);
 
-        // filter out outdated proposals
         let len = active_proposals.len();
         for proposal in active_proposals.into_iter() {
-            if env.ledger().sequence() <= proposal.inner.ledger + proposal_duration {
+            if proposal.is_active(env, proposal_duration) {
                 filtered_proposals.push_back(proposal);
+            } else if proposal.inner.status == PropStatus::Running {
+                Self::expire(env, proposal);
             }
         }
         if filtered_proposals.len() < len {
@@ -132,74 +373,312 @@ impl Proposal {
         filtered_proposals
     }
 
-    pub fn get_archived(env: &Env, proposal_id: u32) -> Proposal {
-        let key = ArchiveKey(proposal_id);
+    /// Gets the stored list of active proposals of a DAO, including the ones that lapsed since
+    fn load_active(env: &Env, dao_id: Bytes) -> Vec<ActiveProposal> {
+        let config = protocol_config(env);
+        let key = ActiveKey(dao_id.clone());
+        if !env.storage().persistent().has(&key) {
+            return Vec::new(env);
+        }
+        let active_proposals = Self::read_active(env, &key).unwrap();
+        env.storage().persistent().bump(&key, config.bump_threshold, bump_ledgers(&config, Configuration::get(env, dao_id).proposal_duration));
+        active_proposals
+    }
+
+    /// Reads the list of active proposals of a DAO, a list of version 0 is rewritten
+    fn read_active(env: &Env, key: &ActiveKey) -> Option<Vec<ActiveProposal>> {
+        let stored: Vec<Val> = env.storage().persistent().get(key)?;
+        let mut active_proposals = Vec::new(env);
+        let mut rewritten = false;
+        for val in stored.iter() {
+            match migration::active_proposal(env, &val) {
+                Some(proposal) => {
+                    rewritten = true;
+                    active_proposals.push_back(proposal);
+                }
+                None => active_proposals.push_back(ActiveProposal::try_from_val(env, &val).unwrap()),
+            }
+        }
+        if rewritten {
+            env.storage().persistent().set(key, &active_proposals);
+        }
+        Some(active_proposals)
+    }
+
+    /// Archives a proposal that nobody finalized in time, the deposit can be claimed afterwards
+    fn expire(env: &Env, mut p: ActiveProposal) {
+        let config = protocol_config(env);
+        p.inner.status = PropStatus::Expired;
+        Self::archive(env, &p, 0);
+
+        let deposit_key = ProposalArtifact::Deposit(p.id);
+        env.storage().persistent().set(&deposit_key, &take_reserve(env, p.id));
+        env.storage().persistent().bump(&deposit_key, config.bump_threshold, config.bump_ledgers);
+        env.events().publish(
+            (PROPOSAL, STATUS_UPDATE),
+            ProposalStatusUpdateEventData {
+                proposal_id: p.id,
+                status: p.inner.status,
+                voting: p.inner.voting,
+            },
+        );
+    }
+
+    fn archive(env: &Env, p: &ActiveProposal, execution_delay: u32) {
+        let config = protocol_config(env);
+        let archive_key = ArchiveKey(p.id);
+        env.storage().persistent().set(&archive_key, &p.inner);
+        env.storage().persistent().bump(&archive_key, config.bump_threshold, bump_ledgers(&config, execution_delay));
+
+        let tally_key = ProposalArtifact::Tally(p.id);
+        let tally = Tally {
+            in_favor: p.in_favor,
+            against: p.against,
+            abstain: p.abstain,
+            finalized_at: env.ledger().sequence(),
+        };
+        env.storage().persistent().set(&tally_key, &tally);
+        env.storage().persistent().bump(&tally_key, config.bump_threshold, bump_ledgers(&config, execution_delay));
+
+        let dao_id = p.inner.dao_id.clone();
+        let count_key = ArchiveIndex::Count(dao_id.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let entry_key = ArchiveIndex::Entry(dao_id, count);
+        env.storage().persistent().set(&entry_key, &p.id);
+        env.storage().persistent().set(&count_key, &(count + 1));
+        env.storage().persistent().bump(&entry_key, config.bump_threshold, bump_ledgers(&config, execution_delay));
+        env.storage().persistent().bump(&count_key, config.bump_threshold, config.bump_ledgers);
+    }
+
+    /// Gets a proposal with its votes, no matter if it is active or archived
+    ///
+    /// Proposals archived with version 0 kept no tally, their votes read as 0.
+    pub fn get_record(env: &Env, proposal_id: u32) -> ProposalRecord {
+        if env.storage().persistent().has(&ArchiveKey(proposal_id)) {
+            let tally = if env.storage().persistent().has(&ProposalArtifact::Tally(proposal_id)) {
+                Self::get_tally(env, proposal_id)
+            } else {
+                Tally {
+                    in_favor: 0,
+                    against: 0,
+                    abstain: 0,
+                    finalized_at: 0,
+                }
+            };
+            return ProposalRecord {
+                id: proposal_id,
+                tally,
+                inner: Self::get_archived(env, proposal_id),
+            };
+        }
+        let dao_id: Bytes = match env.storage().persistent().get(&ProposalArtifact::Dao(proposal_id)) {
+            Some(dao_id) => dao_id,
+            None => panic_with_error!(env, VotesError::ProposalNotFound),
+        };
+        let proposal_duration = Configuration::get(env, dao_id.clone()).proposal_duration;
+        for mut p in Self::load_active(env, dao_id).into_iter() {
+            if p.id == proposal_id {
+                // a lapsed proposal is only archived on the next write, but it is expired already
+                if p.inner.status == PropStatus::Running && !p.is_active(env, proposal_duration) {
+                    p.inner.status = PropStatus::Expired;
+                }
+                return ProposalRecord {
+                    id: p.id,
+                    tally: Tally {
+                        in_favor: p.in_favor,
+                        against: p.against,
+                        abstain: p.abstain,
+                        finalized_at: 0,
+                    },
+                    inner: p.inner,
+                };
+            }
+        }
+        panic_with_error!(env, VotesError::ProposalNotFound)
+    }
+
+    /// Gets up to `limit` archived proposals of a DAO, starting at the `start`th archived one
+    ///
+    /// Proposals whose archive entry expired are skipped.
+    pub fn get_archived_page(env: &Env, dao_id: Bytes, start: u32, limit: u32) -> Vec<ProposalRecord> {
+        let count: u32 = env.storage().persistent().get(&ArchiveIndex::Count(dao_id.clone())).unwrap_or(0);
+        let end = count.min(start.saturating_add(limit));
+        let mut records = Vec::new(env);
+        for i in start..end {
+            let proposal_id: u32 = match env.storage().persistent().get(&ArchiveIndex::Entry(dao_id.clone(), i)) {
+                Some(proposal_id) => proposal_id,
+                None => continue,
+            };
+            if env.storage().persistent().has(&ArchiveKey(proposal_id)) {
+                records.push_back(Self::get_record(env, proposal_id));
+            }
+        }
+        records
+    }
+
+    pub fn get_tally(env: &Env, proposal_id: u32) -> Tally {
+        let config = protocol_config(env);
+        let key = ProposalArtifact::Tally(proposal_id);
         if env.storage().persistent().has(&key) {
-            env.storage().persistent().bump(&key, BUMP_A_MONTH_THRESHOLD, BUMP_A_MONTH);
+            env.storage().persistent().bump(&key, config.bump_threshold, config.bump_ledgers);
             return env.storage().persistent().get(&key).unwrap();
         }
         panic_with_error!(env, VotesError::ProposalNotFound)
     }
 
+    /// Returns the deposit of an expired proposal to its owner
+    pub fn claim_deposit(env: &Env, proposal_id: u32) -> i128 {
+        let key = ProposalArtifact::Deposit(proposal_id);
+        if !env.storage().persistent().has(&key) && !env.storage().persistent().has(&ArchiveKey(proposal_id)) {
+            // archives the proposal if it lapsed, which records its deposit
+            if let Some(dao_id) = env.storage().persistent().get(&ProposalArtifact::Dao(proposal_id)) {
+                Self::prune_active(env, dao_id);
+            }
+        }
+        let deposit: i128 = match env.storage().persistent().get(&key) {
+            Some(deposit) => deposit,
+            None => panic_with_error!(env, VotesError::NoDeposit),
+        };
+        let proposal = Self::get_archived(env, proposal_id);
+        env.storage().persistent().remove(&key);
+        refund(env, &proposal.owner, deposit);
+        deposit
+    }
+
+    pub fn get_archived(env: &Env, proposal_id: u32) -> Proposal {
+        let config = protocol_config(env);
+        let key = ArchiveKey(proposal_id);
+        if let Some(proposal) = Self::read_archived(env, &key) {
+            env.storage().persistent().bump(&key, config.bump_threshold, config.bump_ledgers);
+            return proposal;
+        }
+        panic_with_error!(env, VotesError::ProposalNotFound)
+    }
+
+    /// Reads an archived proposal, a proposal of version 0 is rewritten
+    fn read_archived(env: &Env, key: &ArchiveKey) -> Option<Proposal> {
+        let stored: Val = env.storage().persistent().get(key)?;
+        match migration::proposal(env, &stored) {
+            Some(proposal) => {
+                env.storage().persistent().set(key, &proposal);
+                Some(proposal)
+            }
+            None => Some(Proposal::try_from_val(env, &stored).unwrap()),
+        }
+    }
+
     pub fn vote(
         env: &Env,
         dao_id: Bytes,
         proposal_id: u32,
-        in_favor: bool,
+        choice: VoteChoice,
         voter: Address,
         asset_id: Address,
     ) -> i128 {
-        // Check if voter has already voted and has the same vote.
-        let vote_key = VotingHistory::Voting(voter.clone(), proposal_id);
-        let has_key = env.storage().temporary().has(&vote_key);
-        if has_key && in_favor == env.storage().temporary().get::<VotingHistory, bool>(&vote_key).unwrap() {
-            panic_with_error!(env, VotesError::VoteAlreadyCast)
-        }
+        let config = protocol_config(env);
+        let ballot_key = ProposalArtifact::Ballot(proposal_id, voter.clone());
         let key = ActiveKey(dao_id.clone());
-        let mut active_proposals: Vec<ActiveProposal> = env.storage().persistent().get(&key).unwrap();
-        env.storage().persistent().bump(&key, BUMP_A_MONTH_THRESHOLD, BUMP_A_MONTH + Configuration::get(env, dao_id.clone()).proposal_duration);
+        let proposal_duration = Configuration::get(env, dao_id.clone()).proposal_duration;
+        let mut active_proposals = Self::read_active(env, &key).unwrap();
+        env.storage().persistent().bump(&key, config.bump_threshold, bump_ledgers(&config, proposal_duration));
 
         for (i, mut p) in active_proposals.clone().into_iter().enumerate() {
             if p.id == proposal_id {
+                if p.inner.status != PropStatus::Running {
+                    panic_with_error!(env, VotesError::ProposalNotRunning)
+                }
+                if env.ledger().sequence() > p.inner.ledger + proposal_duration {
+                    panic_with_error!(env, VotesError::VotingClosed)
+                }
+                // Check if voter has already voted and has the same vote, a vote of version 0
+                // becomes a ballot, but its voter isn't listed yet
+                let listed_ballot: Option<Ballot> = env.storage().persistent().get(&ballot_key);
+                let is_listed = listed_ballot.is_some();
+                let previous_ballot = listed_ballot
+                    .or_else(|| migration::take_ballot(env, proposal_id, &voter, &asset_id, p.inner.ledger));
+                if let Some(ref ballot) = previous_ballot {
+                    if ballot.choice == choice {
+                        panic_with_error!(env, VotesError::VoteAlreadyCast)
+                    }
+                }
                 let voting_power_pre_hook: i128 = env.invoke_contract(
                     &asset_id,
-                    &Symbol::new(env, "get_balance_at"),
+                    &Symbol::new(env, "get_votes_at"),
                     (voter.clone(), p.inner.ledger).into_val(env),
                 );
                 let voting_power = on_vote(env, &dao_id, &proposal_id, &voter, voting_power_pre_hook);
 
-                if in_favor {
-                    p.in_favor += voting_power;
-                    if has_key { p.against -= voting_power}
-                } else {
-                    p.against += voting_power;
-                    if has_key { p.in_favor -= voting_power}
+                if let Some(ballot) = previous_ballot {
+                    *p.tally(ballot.choice) -= ballot.voting_power;
                 }
+                if !is_listed {
+                    Self::add_voter(env, proposal_id, voter, proposal_duration);
+                }
+                *p.tally(choice) += voting_power;
                 active_proposals.set(i as u32, p);
                 env.storage().persistent().set(&key, &active_proposals);
-                env.storage().temporary().set(&vote_key, &in_favor);
-                env.storage().temporary().bump(&vote_key, 0, Configuration::get(env, dao_id.clone()).proposal_duration);
+
+                let ballot = Ballot {
+                    choice,
+                    voting_power,
+                    ledger: env.ledger().sequence(),
+                };
+                env.storage().persistent().set(&ballot_key, &ballot);
+                env.storage().persistent().bump(&ballot_key, config.bump_threshold, bump_ledgers(&config, proposal_duration));
                 return voting_power
             }
         }
         panic_with_error!(env, VotesError::ProposalNotFound)
     }
 
+    fn add_voter(env: &Env, proposal_id: u32, voter: Address, proposal_duration: u32) {
+        let config = protocol_config(env);
+        let count_key = ProposalArtifact::VoterCount(proposal_id);
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let voter_key = ProposalArtifact::Voter(proposal_id, count);
+        env.storage().persistent().set(&voter_key, &voter);
+        env.storage().persistent().set(&count_key, &(count + 1));
+        env.storage().persistent().bump(&voter_key, config.bump_threshold, bump_ledgers(&config, proposal_duration));
+        env.storage().persistent().bump(&count_key, config.bump_threshold, bump_ledgers(&config, proposal_duration));
+    }
+
+    pub fn get_ballot(env: &Env, proposal_id: u32, voter: Address) -> Ballot {
+        let config = protocol_config(env);
+        let key = ProposalArtifact::Ballot(proposal_id, voter);
+        if env.storage().persistent().has(&key) {
+            env.storage().persistent().bump(&key, config.bump_threshold, config.bump_ledgers);
+            return env.storage().persistent().get(&key).unwrap();
+        }
+        panic_with_error!(env, VotesError::BallotNotFound)
+    }
+
+    /// Gets up to `limit` voters of a proposal in the order of their first vote, starting at the `start`th
+    pub fn get_voters(env: &Env, proposal_id: u32, start: u32, limit: u32) -> Vec<Address> {
+        let count: u32 = env.storage().persistent().get(&ProposalArtifact::VoterCount(proposal_id)).unwrap_or(0);
+        let end = count.min(start.saturating_add(limit));
+        let mut voters = Vec::new(env);
+        for i in start..end {
+            if let Some(voter) = env.storage().persistent().get(&ProposalArtifact::Voter(proposal_id, i)) {
+                voters.push_back(voter);
+            }
+        }
+        voters
+    }
+
     pub fn set_faulty(env: &Env, dao_id: Bytes, proposal_id: u32, reason: Bytes) {
         on_before_fault_proposal(env, &dao_id, proposal_id, &reason);
         let key = ActiveKey(dao_id);
-        let mut active_proposals: Vec<ActiveProposal> = env.storage().persistent().get(&key).unwrap();
+        let mut active_proposals = Proposal::read_active(env, &key).unwrap();
         for (i, mut p) in active_proposals.clone().into_iter().enumerate() {
             if p.id == proposal_id {
+                if p.inner.status != PropStatus::Running {
+                    panic_with_error!(env, VotesError::ProposalNotRunning)
+                }
                 p.inner.status = PropStatus::Faulty(reason);
+                Self::archive(env, &p, 0);
 
                 // return reserved tokens
-                let core_id = env.storage().instance().get(&CORE).unwrap();
-                let core = CoreContractClient::new(env, &core_id);
-                let native_asset_id = core.get_native_asset_id();
-                let native_token = token::Client::new(env, &native_asset_id);
-                let contract = env.current_contract_address();
-                native_token.transfer(&contract, &p.inner.owner, &RESERVE_AMOUNT);
+                refund(env, &p.inner.owner, take_reserve(env, p.id));
 
                 active_proposals.set(i as u32, p);
                 env.storage().persistent().set(&key, &active_proposals);
@@ -209,14 +688,124 @@ impl Proposal {
         panic_with_error!(env, VotesError::ProposalNotFound)
     }
 
+    /// Withdraws a proposal as its owner before anyone voted, returns the refunded and the withheld
+    /// share of the reserve
+    ///
+    /// The proposal is archived as cancelled, the withheld share is kept for the DAO to claim.
+    pub fn cancel(env: &Env, dao_id: Bytes, proposal_id: u32, owner: Address) -> (i128, i128) {
+        owner.require_auth();
+
+        let key = ActiveKey(dao_id.clone());
+        let mut active_proposals = match Proposal::read_active(env, &key) {
+            Some(active_proposals) => active_proposals,
+            None => panic_with_error!(env, VotesError::ProposalNotFound),
+        };
+        for (i, mut p) in active_proposals.clone().into_iter().enumerate() {
+            if p.id == proposal_id {
+                if p.inner.owner != owner {
+                    panic_with_error!(env, VotesError::NotProposalOwner)
+                }
+                if p.inner.status != PropStatus::Running {
+                    panic_with_error!(env, VotesError::ProposalNotRunning)
+                }
+                if p.in_favor != 0 || p.against != 0 || p.abstain != 0 {
+                    panic_with_error!(env, VotesError::VotingAlreadyStarted)
+                }
+                p.inner.status = PropStatus::Cancelled;
+                Self::archive(env, &p, 0);
+
+                let cancel_refund_bps = Configuration::get(env, dao_id.clone()).cancel_refund_bps;
+                let reserve = take_reserve(env, p.id);
+                let refund_amount = reserve * cancel_refund_bps as i128 / MAX_BPS as i128;
+                if refund_amount > 0 {
+                    refund(env, &owner, refund_amount);
+                }
+                let withheld = reserve - refund_amount;
+                if withheld > 0 {
+                    Self::withhold(env, &dao_id, withheld);
+                }
+                env.events().publish(
+                    (PROPOSAL, STATUS_UPDATE),
+                    ProposalStatusUpdateEventData {
+                        proposal_id,
+                        status: p.inner.status.clone(),
+                        voting: p.inner.voting.clone(),
+                    },
+                );
+
+                active_proposals.set(i as u32, p);
+                env.storage().persistent().set(&key, &active_proposals);
+                return (refund_amount, withheld);
+            }
+        }
+        panic_with_error!(env, VotesError::ProposalNotFound)
+    }
+
+    /// Proposals accepted before they were counted are not part of the count
+    fn count_pending(env: &Env, dao_id: Bytes, change: i32) {
+        let config = protocol_config(env);
+        let key = DaoProposals::PendingCount(dao_id);
+        let count: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &count.saturating_add_signed(change));
+        env.storage().persistent().bump(&key, config.bump_threshold, config.bump_ledgers);
+    }
+
+    /// Checks if a DAO has running proposals, lapsed ones that are not archived yet included, or
+    /// accepted ones that are neither implemented nor vetoed
+    pub fn has_open(env: &Env, dao_id: Bytes) -> bool {
+        let pending: u32 = env.storage().persistent().get(&DaoProposals::PendingCount(dao_id.clone())).unwrap_or(0);
+        let active_proposals = match Proposal::read_active(env, &ActiveKey(dao_id)) {
+            Some(active_proposals) => active_proposals,
+            None => Vec::new(env),
+        };
+        pending > 0 || active_proposals.iter().any(|p| p.inner.status == PropStatus::Running)
+    }
+
+    /// Resets the proposal lists and the numbering of a destroyed DAO, so a DAO created with its id
+    /// starts without them
+    ///
+    /// The archived proposals are kept and can still be read by their id.
+    pub fn remove_dao(env: &Env, dao_id: Bytes) {
+        if Self::has_open(env, dao_id.clone()) || Self::get_withheld(env, dao_id.clone()) > 0 {
+            panic_with_error!(env, VotesError::DaoHasOpenProposals)
+        }
+        env.storage().persistent().remove(&ActiveKey(dao_id.clone()));
+        env.storage().persistent().remove(&ArchiveIndex::Count(dao_id.clone()));
+        env.storage().persistent().remove(&DaoProposals::NumberCount(dao_id.clone()));
+        env.storage().persistent().remove(&DaoProposals::PendingCount(dao_id));
+    }
+
+    fn withhold(env: &Env, dao_id: &Bytes, amount: i128) {
+        let config = protocol_config(env);
+        let key = DaoReserve::Withheld(dao_id.clone());
+        let withheld = Self::get_withheld(env, dao_id.clone());
+        env.storage().persistent().set(&key, &(withheld + amount));
+        env.storage().persistent().bump(&key, config.bump_threshold, config.bump_ledgers);
+    }
+
+    pub fn get_withheld(env: &Env, dao_id: Bytes) -> i128 {
+        env.storage().persistent().get(&DaoReserve::Withheld(dao_id)).unwrap_or(0)
+    }
+
+    /// Transfers the withheld shares of the reserves of the cancelled proposals of a DAO
+    pub fn claim_withheld(env: &Env, dao_id: Bytes, to: Address) -> i128 {
+        let withheld = Self::get_withheld(env, dao_id.clone());
+        if withheld == 0 {
+            panic_with_error!(env, VotesError::NoDeposit)
+        }
+        env.storage().persistent().remove(&DaoReserve::Withheld(dao_id));
+        refund(env, &to, withheld);
+        withheld
+    }
+
     pub fn finalize(env: &Env, dao_id: Bytes, proposal_id: u32) {
+        let config = protocol_config(env);
         on_before_finalize_proposal(env, &dao_id, proposal_id);
         let key = ActiveKey(dao_id.clone());
-        let configuration = Configuration::get(env, dao_id);
+        let configuration = Configuration::get(env, dao_id.clone());
         let proposal_duration = configuration.proposal_duration;
-        let min_threshold_configuration = configuration.min_threshold_configuration;
-        let mut active_proposals: Vec<ActiveProposal> = env.storage().persistent().get(&key).unwrap();
-        env.storage().persistent().bump(&key, BUMP_A_MONTH_THRESHOLD, BUMP_A_MONTH);
+        let mut active_proposals = Proposal::read_active(env, &key).unwrap();
+        env.storage().persistent().bump(&key, config.bump_threshold, config.bump_ledgers);
 
         for (i, mut p) in active_proposals.clone().into_iter().enumerate() {
             if p.id == proposal_id {
@@ -226,21 +815,32 @@ impl Proposal {
                 if p.inner.status != PropStatus::Running {
                    panic_with_error!(env, VotesError::ProposalNotRunning)
                 }
-                p.inner.status = if p.in_favor > p.against && min_threshold_configuration < (p.in_favor + p.against) {
-                    PropStatus::Accepted
+                if !p.is_active(env, proposal_duration) {
+                    panic_with_error!(env, VotesError::ProposalExpired)
+                }
+                // proposals created before the quorum was kept with them use the current one
+                let quorum = env.storage().persistent().get(&ProposalArtifact::Quorum(proposal_id))
+                    .unwrap_or_else(|| Quorum::of(&configuration));
+                let votes_cast = p.in_favor.saturating_add(p.against).saturating_add(p.abstain);
+                let accepted = quorum.is_reached(env, &dao_id, p.inner.ledger, votes_cast)
+                    && p.inner.voting.is_accepted(env, &dao_id, p.inner.ledger, p.in_favor, p.against);
+                p.inner.status = if accepted {
+                    if configuration.execution_delay > 0 {
+                        PropStatus::Queued(env.ledger().sequence() + configuration.execution_delay)
+                    } else {
+                        PropStatus::Accepted
+                    }
                 } else {
                     PropStatus::Rejected
                 };
 
-                env.storage().persistent().set(&ArchiveKey(proposal_id), &p.inner);
+                Self::archive(env, &p, configuration.execution_delay);
+                if accepted {
+                    Self::count_pending(env, dao_id.clone(), 1);
+                }
 
                 // return reserved tokens
-                let core_id = env.storage().instance().get(&CORE).unwrap();
-                let core = CoreContractClient::new(env, &core_id);
-                let native_asset_id = core.get_native_asset_id();
-                let native_token = token::Client::new(env, &native_asset_id);
-                let contract = env.current_contract_address();
-                native_token.transfer(&contract, &p.inner.owner, &RESERVE_AMOUNT);
+                refund(env, &p.inner.owner, take_reserve(env, p.id));
 
                 active_proposals.set(i as u32, p.clone());
                 env.storage().persistent().set(&key, &active_proposals);
@@ -249,6 +849,7 @@ impl Proposal {
                     ProposalStatusUpdateEventData {
                         proposal_id,
                         status: p.inner.status,
+                        voting: p.inner.voting,
                     },
                 );
                 return;
@@ -258,15 +859,22 @@ impl Proposal {
     }
 
     pub fn mark_implemented(env: &Env, proposal_id: u32) {
+        let config = protocol_config(env);
         let key = ArchiveKey(proposal_id);
-        let mut proposal: Proposal = env.storage().persistent().get(&key).unwrap();
-        env.storage().persistent().bump(&key, BUMP_A_MONTH_THRESHOLD, BUMP_A_MONTH);
+        let mut proposal = Self::read_archived(env, &key).unwrap();
+        env.storage().persistent().bump(&key, config.bump_threshold, config.bump_ledgers);
 
-        if proposal.status != PropStatus::Accepted {
-            panic_with_error!(env, VotesError::UnacceptedProposal)
+        match proposal.status {
+            PropStatus::Accepted => (),
+            PropStatus::Queued(ledger) if env.ledger().sequence() < ledger => {
+                panic_with_error!(env, VotesError::ProposalInTimelock)
+            }
+            PropStatus::Queued(_) => (),
+            _ => panic_with_error!(env, VotesError::UnacceptedProposal),
         }
 
         proposal.status = PropStatus::Implemented;
+        Self::count_pending(env, proposal.dao_id.clone(), -1);
 
         env.storage().persistent().set(&key, &proposal);
         env.events().publish(
@@ -274,9 +882,174 @@ impl Proposal {
             ProposalStatusUpdateEventData {
                 proposal_id,
                 status: proposal.status,
+                voting: proposal.voting,
             },
         );
     }
+
+    /// Loads a proposal that is still within its execution delay
+    fn get_queued(env: &Env, proposal_id: u32) -> Proposal {
+        let proposal = Self::get_archived(env, proposal_id);
+        match proposal.status {
+            PropStatus::Queued(ledger) if env.ledger().sequence() < ledger => proposal,
+            _ => panic_with_error!(env, VotesError::ProposalNotQueued),
+        }
+    }
+
+    pub fn cancel_queued(env: &Env, proposal_id: u32, guardian: Address) {
+        guardian.require_auth();
+
+        let proposal = Self::get_queued(env, proposal_id);
+        let configuration = Configuration::get(env, proposal.dao_id.clone());
+        if !configuration.guardians.contains(&guardian) {
+            panic_with_error!(env, VotesError::NotGuardian)
+        }
+        Self::set_vetoed(env, proposal_id, proposal);
+    }
+
+    pub fn veto(env: &Env, proposal_id: u32, voter: Address, asset_id: Address) -> i128 {
+        let config = protocol_config(env);
+        let proposal = Self::get_queued(env, proposal_id);
+        let configuration = Configuration::get(env, proposal.dao_id.clone());
+        if configuration.veto_threshold == 0 {
+            panic_with_error!(env, VotesError::VetoDisabled)
+        }
+
+        let veto_key = ProposalArtifact::Veto(proposal_id, voter.clone());
+        if env.storage().persistent().has(&veto_key) {
+            panic_with_error!(env, VotesError::VetoAlreadyCast)
+        }
+
+        // the snapshot of the proposal is used, so tokens can't be moved around to veto twice
+        let voting_power_pre_hook: i128 = env.invoke_contract(
+            &asset_id,
+            &Symbol::new(env, "get_votes_at"),
+            (voter.clone(), proposal.ledger).into_val(env),
+        );
+        let voting_power = on_vote(env, &proposal.dao_id, &proposal_id, &voter, voting_power_pre_hook);
+
+        let vetoes_key = ProposalArtifact::Vetoes(proposal_id);
+        let vetoes = env.storage().persistent().get::<ProposalArtifact, i128>(&vetoes_key).unwrap_or(0) + voting_power;
+        env.storage().persistent().set(&vetoes_key, &vetoes);
+        env.storage().persistent().set(&veto_key, &true);
+        env.storage().persistent().bump(&vetoes_key, config.bump_threshold, config.bump_ledgers);
+        env.storage().persistent().bump(&veto_key, config.bump_threshold, config.bump_ledgers);
+
+        if vetoes > configuration.veto_threshold {
+            Self::set_vetoed(env, proposal_id, proposal);
+        }
+        voting_power
+    }
+
+    pub fn get_vetoes(env: &Env, proposal_id: u32) -> i128 {
+        env.storage().persistent().get(&ProposalArtifact::Vetoes(proposal_id)).unwrap_or(0)
+    }
+
+    fn set_vetoed(env: &Env, proposal_id: u32, mut proposal: Proposal) {
+        proposal.status = PropStatus::Vetoed;
+        Self::count_pending(env, proposal.dao_id.clone(), -1);
+        env.storage().persistent().set(&ArchiveKey(proposal_id), &proposal);
+        env.events().publish(
+            (PROPOSAL, STATUS_UPDATE),
+            ProposalStatusUpdateEventData {
+                proposal_id,
+                status: proposal.status,
+                voting: proposal.voting,
+            },
+        );
+    }
+}
+
+/// Removes the recorded reserve of a proposal to return it
+fn take_reserve(env: &Env, proposal_id: u32) -> i128 {
+    let key = ProposalArtifact::Reserve(proposal_id);
+    let reserve = env.storage().persistent().get(&key).unwrap_or(RESERVE_AMOUNT);
+    env.storage().persistent().remove(&key);
+    reserve
+}
+
+/// Transfers reserved native tokens back to the owner of a proposal, or to the DAO it was withheld for
+fn refund(env: &Env, owner: &Address, amount: i128) {
+    let core_id = env.storage().instance().get(&CORE).unwrap();
+    let core = CoreContractClient::new(env, &core_id);
+    let native_asset_id = core.get_native_asset_id();
+    let native_token = token::Client::new(env, &native_asset_id);
+    let contract = env.current_contract_address();
+    native_token.transfer(&contract, owner, &amount);
+}
+
+/// The bump of an entry that has to outlive the bump of the protocol config by `ledgers`,
+/// within the max TTL
+fn bump_ledgers(config: &ProtocolConfig, ledgers: u32) -> u32 {
+    config.bump_ledgers.saturating_add(ledgers).min(MAX_TTL)
+}
+
+/// The total supply of the DAO asset at the given ledger
+fn total_supply_at(env: &Env, dao_id: &Bytes, ledger: u32) -> i128 {
+    let core_id = env.storage().instance().get(&CORE).unwrap();
+    let core = CoreContractClient::new(env, &core_id);
+    env.invoke_contract(
+        &core.get_dao_asset_id(dao_id),
+        &Symbol::new(env, "get_total_supply_at"),
+        (ledger,).into_val(env),
+    )
+}
+
+impl Voting {
+    pub fn validate(&self, env: &Env) {
+        if let Voting::Supermajority(numerator, denominator) = self {
+            // a supermajority has to be more than a simple majority and can't exceed all votes
+            if numerator > denominator || *numerator as u64 * 2 <= *denominator as u64 {
+                panic_with_error!(env, VotesError::InvalidVoting)
+            }
+        }
+    }
+
+    pub fn is_accepted(&self, env: &Env, dao_id: &Bytes, ledger: u32, in_favor: i128, against: i128) -> bool {
+        match self {
+            Voting::Majority => in_favor > against,
+            Voting::Supermajority(numerator, denominator) => {
+                in_favor > 0 && reaches_share(in_favor, in_favor.saturating_add(against), *numerator, *denominator)
+            }
+            // more than half, the halved supply is rounded down
+            Voting::AbsoluteMajority => in_favor > total_supply_at(env, dao_id, ledger) / 2,
+            Voting::Unanimity => in_favor > 0 && against == 0,
+        }
+    }
+}
+
+/// The quorum of the DAO when a proposal was created
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Quorum {
+    quorum_bps: u32,
+    min_threshold_configuration: i128,
+}
+
+impl Quorum {
+    fn of(configuration: &Configuration) -> Self {
+        Quorum {
+            quorum_bps: configuration.quorum_bps,
+            min_threshold_configuration: configuration.min_threshold_configuration,
+        }
+    }
+
+    /// Checks if the votes cast on a proposal of the given ledger reach the quorum
+    fn is_reached(&self, env: &Env, dao_id: &Bytes, ledger: u32, votes_cast: i128) -> bool {
+        if self.quorum_bps == 0 {
+            return self.min_threshold_configuration < votes_cast;
+        }
+        reaches_share(votes_cast, total_supply_at(env, dao_id, ledger), self.quorum_bps, MAX_BPS)
+    }
+}
+
+/// Checks if `part` makes up at least `numerator / denominator` of `whole`, without multiplying
+/// the amounts, so they can't overflow. The ratio must not exceed 1.
+fn reaches_share(part: i128, whole: i128, numerator: u32, denominator: u32) -> bool {
+    let (numerator, denominator) = (numerator as i128, denominator as i128);
+    // the share of `whole` rounded up, split into its whole multiples of the denominator and the rest
+    let share = whole / denominator * numerator + (whole % denominator * numerator + denominator - 1) / denominator;
+    part >= share
 }
 
 #[contracttype]
@@ -301,7 +1074,7 @@ impl Metadata {
         owner.require_auth();
 
         let key = ActiveKey(dao_id.clone());
-        let active_proposals: Vec<ActiveProposal> = env.storage().persistent().get(&key).unwrap();
+        let active_proposals = Proposal::read_active(env, &key).unwrap();
         for p in active_proposals.into_iter() {
             if p.id == proposal_id {
                 if p.inner.owner != owner {
@@ -328,11 +1101,100 @@ impl Metadata {
     }
 }
 
+impl ProposalAction {
+    pub fn set(
+        env: &Env,
+        dao_id: Bytes,
+        proposal_id: u32,
+        actions: Vec<ProposalAction>,
+        owner: Address,
+    ) {
+        let config = protocol_config(env);
+        owner.require_auth();
+
+        let key = ActiveKey(dao_id.clone());
+        let active_proposals = Proposal::read_active(env, &key).unwrap();
+        for p in active_proposals.into_iter() {
+            if p.id == proposal_id {
+                if p.inner.owner != owner {
+                    panic_with_error!(env, VotesError::NotProposalOwner)
+                }
+                if p.inner.status != PropStatus::Running {
+                    panic_with_error!(env, VotesError::ProposalNotRunning)
+                }
+                // voters have to know what they are voting on
+                if p.in_favor != 0 || p.against != 0 || p.abstain != 0 {
+                    panic_with_error!(env, VotesError::VotingAlreadyStarted)
+                }
+                let actions_key = ProposalArtifact::Actions(proposal_id);
+                if env.storage().persistent().has(&actions_key) {
+                    panic_with_error!(env, VotesError::ActionsAlreadySet)
+                }
+                for action in actions.iter() {
+                    action.check_scope(env, &dao_id);
+                }
+                env.storage().persistent().set(&actions_key, &actions);
+                env.storage().persistent().bump(&actions_key, config.bump_threshold, config.bump_ledgers);
+                return;
+            }
+        }
+        panic_with_error!(env, VotesError::ProposalNotFound)
+    }
+
+    pub fn get(env: &Env, proposal_id: u32) -> Vec<Self> {
+        let config = protocol_config(env);
+        let key = ProposalArtifact::Actions(proposal_id);
+        if !env.storage().persistent().has(&key) {
+            return Vec::new(env);
+        }
+        env.storage().persistent().bump(&key, config.bump_threshold, config.bump_ledgers);
+        env.storage().persistent().get(&key).unwrap()
+    }
+
+    /// Invokes the action with the votes contract as the invoker
+    pub fn invoke(&self, env: &Env, dao_id: &Bytes) {
+        self.check_scope(env, dao_id);
+        env.invoke_contract::<Val>(&self.contract_id, &self.func, self.args.clone());
+    }
+
+    /// Every contract that trusts the votes contract as an owner would otherwise be reachable
+    /// from the proposals of all DAOs, so an action has to name the DAO of its proposal as the
+    /// first argument. The only exception is the DAO's own asset.
+    pub fn check_scope(&self, env: &Env, dao_id: &Bytes) {
+        if let Some(arg) = self.args.first() {
+            if let Ok(id) = Bytes::try_from_val(env, &arg) {
+                if &id == dao_id {
+                    return;
+                }
+            }
+        }
+        let core_id = env.storage().instance().get(&CORE).unwrap();
+        let core = CoreContractClient::new(env, &core_id);
+        match core.try_get_dao_asset_id(dao_id) {
+            Ok(Ok(asset_id)) if asset_id == self.contract_id => (),
+            _ => panic_with_error!(env, VotesError::ActionOutOfScope),
+        }
+    }
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Configuration {
     pub proposal_duration: u32,
     pub min_threshold_configuration: i128,
+    /// Ledgers an accepted proposal stays queued before it can be executed
+    pub execution_delay: u32,
+    /// Accounts that can cancel queued proposals
+    pub guardians: Vec<Address>,
+    /// Veto voting power required to cancel a queued proposal, 0 disables veto votes
+    pub veto_threshold: i128,
+    /// Decides if a proposal is accepted, applies to proposals created afterwards
+    pub voting: Voting,
+    /// Quorum in basis points of the total supply at the ledger of a proposal, replaces the min
+    /// voting power if set
+    pub quorum_bps: u32,
+    /// Share of the reserve in basis points that is returned when a proposal is cancelled
+    pub cancel_refund_bps: u32,
 }
 
 impl Configuration {
@@ -342,23 +1204,137 @@ impl Configuration {
         proposal_duration: u32,
         min_threshold_configuration: i128,
     ) -> Self {
-        let configuration = Configuration {
-            proposal_duration,
-            min_threshold_configuration,
+        let configuration = match Self::read(env, &dao_id) {
+            Some(configuration) => Configuration {
+                proposal_duration,
+                min_threshold_configuration,
+                ..configuration
+            },
+            None => Self::with_defaults(env, proposal_duration, min_threshold_configuration),
         };
+        configuration.check_checkpoint_retention(env, &dao_id);
         env.storage().persistent().set(&dao_id, &configuration);
         on_set_configuration(env, &dao_id, proposal_duration);
         configuration
     }
 
+    pub fn set_timelock(
+        env: &Env,
+        dao_id: Bytes,
+        execution_delay: u32,
+        guardians: Vec<Address>,
+        veto_threshold: i128,
+    ) -> Self {
+        let configuration = Configuration {
+            execution_delay,
+            guardians,
+            veto_threshold,
+            ..Self::get(env, dao_id.clone())
+        };
+        configuration.check_checkpoint_retention(env, &dao_id);
+        env.storage().persistent().set(&dao_id, &configuration);
+        configuration
+    }
+
+    pub fn set_voting(env: &Env, dao_id: Bytes, voting: Voting) -> Self {
+        voting.validate(env);
+        let configuration = Configuration {
+            voting,
+            ..Self::get(env, dao_id.clone())
+        };
+        env.storage().persistent().set(&dao_id, &configuration);
+        configuration
+    }
+
+    pub fn set_quorum(env: &Env, dao_id: Bytes, quorum_bps: u32) -> Self {
+        if quorum_bps > MAX_BPS {
+            panic_with_error!(env, VotesError::InvalidQuorum)
+        }
+        let configuration = Configuration {
+            quorum_bps,
+            ..Self::get(env, dao_id.clone())
+        };
+        env.storage().persistent().set(&dao_id, &configuration);
+        configuration
+    }
+
+    pub fn set_cancel_refund(env: &Env, dao_id: Bytes, cancel_refund_bps: u32) -> Self {
+        if cancel_refund_bps > MAX_BPS {
+            panic_with_error!(env, VotesError::InvalidCancelRefund)
+        }
+        let configuration = Configuration {
+            cancel_refund_bps,
+            ..Self::get(env, dao_id.clone())
+        };
+        env.storage().persistent().set(&dao_id, &configuration);
+        configuration
+    }
+
     pub fn get(env: &Env, dao_id: Bytes) -> Self {
-        if !env.storage().persistent().has(&dao_id) {
-            panic_with_error!(env, VotesError::ConfigurationNotFound)
+        match Self::read(env, &dao_id) {
+            Some(configuration) => configuration,
+            None => panic_with_error!(env, VotesError::ConfigurationNotFound),
+        }
+    }
+
+    /// Reads the configuration of a DAO, a configuration of version 0 is rewritten
+    pub fn read(env: &Env, dao_id: &Bytes) -> Option<Self> {
+        let stored: Val = env.storage().persistent().get(dao_id)?;
+        match migration::configuration(env, &stored) {
+            Some(configuration) => {
+                env.storage().persistent().set(dao_id, &configuration);
+                Some(configuration)
+            }
+            None => Some(Self::try_from_val(env, &stored).unwrap()),
+        }
+    }
+
+    /// A configuration without timelock, quorum and a majority vote, cancelled proposals get
+    /// their whole reserve back
+    pub fn with_defaults(env: &Env, proposal_duration: u32, min_threshold_configuration: i128) -> Self {
+        Configuration {
+            proposal_duration,
+            min_threshold_configuration,
+            execution_delay: 0,
+            guardians: Vec::new(env),
+            veto_threshold: 0,
+            voting: Voting::Majority,
+            quorum_bps: 0,
+            cancel_refund_bps: MAX_BPS,
         }
-        env.storage().persistent().get(&dao_id).unwrap()
     }
 
     pub fn remove(env: &Env, dao_id: Bytes) {
         env.storage().persistent().remove(&dao_id)
     }
+
+    /// The ledgers a proposal reads the checkpoints of its creation ledger for: while it runs,
+    /// until it is finalized and while it is queued for vetoes
+    pub fn checkpoint_window(&self) -> u32 {
+        self.proposal_duration
+            .saturating_add(FINALIZE_WINDOW)
+            .saturating_add(self.execution_delay)
+    }
+
+    /// Vetoes read the voting power at the creation ledger of a queued proposal, so the asset
+    /// has to keep its checkpoints for the whole window. Custom tokens without a retention are
+    /// trusted to keep them.
+    fn check_checkpoint_retention(&self, env: &Env, dao_id: &Bytes) {
+        let core_id = env.storage().instance().get(&CORE).unwrap();
+        let core = CoreContractClient::new(env, &core_id);
+        let asset_id = match core.try_get_dao_asset_id(dao_id) {
+            Ok(Ok(asset_id)) => asset_id,
+            _ => return,
+        };
+        let retention = env.try_invoke_contract::<u32, soroban_sdk::Error>(
+            &asset_id,
+            &Symbol::new(env, "checkpoint_retention"),
+            Vec::new(env),
+        );
+        if let Ok(Ok(retention)) = retention {
+            if retention < self.checkpoint_window() {
+                panic_with_error!(env, VotesError::CheckpointRetentionTooShort)
+            }
+        }
+    }
 }
\ No newline at end of file
@@ -30,6 +30,16 @@ soroban contract deploy \
 )"
 export VOTES_ADDRESS
 
+printf "\nDeploying treasury ...\n"
+TREASURY_ADDRESS="$(
+soroban contract deploy \
+    --wasm wasm/elio_treasury.wasm \
+    --source "${SECRET_KEY}" \
+    --rpc-url "${RPC_URL}" \
+    --network-passphrase "${NETWORK_PASSPHRASE}"
+)"
+export TREASURY_ADDRESS
+
 printf "\nInstalling assets ...\n"
 ASSETS_WASM_HASH="$(
 soroban contract install \
@@ -49,7 +59,8 @@ soroban contract invoke \
     -- \
     init \
     --votes_id "${VOTES_ADDRESS}" \
-    --native_asset_id "${STELLAR_ASSET_ID}"
+    --native_asset_id "${STELLAR_ASSET_ID}" \
+    --protocol_admin "${PUBLIC_KEY}"
 
 printf "\nInitialising votes ...\n"
 soroban contract invoke \
@@ -61,7 +72,28 @@ soroban contract invoke \
     init \
     --core_id "${CORE_ADDRESS}"
 
-for CONTRACT in core votes assets; do
+printf "\nInitialising treasury ...\n"
+soroban contract invoke \
+    --id "${TREASURY_ADDRESS}" \
+    --source "${SECRET_KEY}" \
+    --rpc-url "${RPC_URL}" \
+    --network-passphrase "${NETWORK_PASSPHRASE}" \
+    -- \
+    init \
+    --core_id "${CORE_ADDRESS}"
+
+printf "\nRegistering treasury with core ...\n"
+soroban contract invoke \
+    --id "${CORE_ADDRESS}" \
+    --source "${SECRET_KEY}" \
+    --rpc-url "${RPC_URL}" \
+    --network-passphrase "${NETWORK_PASSPHRASE}" \
+    -- \
+    set_treasury_id \
+    --treasury_id "${TREASURY_ADDRESS}" \
+    --protocol_admin "${PUBLIC_KEY}"
+
+for CONTRACT in core votes assets treasury; do
 	printf "\nBumping contract ${CONTRACT} ...\n"
 	soroban contract bump \
    --source "${SECRET_KEY}" \
@@ -90,6 +122,15 @@ soroban contract bump \
  --durability persistent \
  --ledgers-to-expire 200000
 
+printf "\nSettings instance storage for treasury ...\n"
+soroban contract bump \
+ --source "${SECRET_KEY}" \
+ --rpc-url "${RPC_URL}" \
+ --network-passphrase "${NETWORK_PASSPHRASE}" \
+ --id $TREASURY_ADDRESS \
+ --durability persistent \
+ --ledgers-to-expire 200000
+
 
 if [[ -n "${SERVICE_URL}" ]];
 then
@@ -97,6 +138,7 @@ printf "\nUpdate Service"
 curl -XPATCH -H "Config-Secret: ${CONFIG_SECRET}" -H "Content-type: application/json" -d "{
   \"core_contract_address\": \"${CORE_ADDRESS}\",
   \"votes_contract_address\": \"${VOTES_ADDRESS}\",
+  \"treasury_contract_address\": \"${TREASURY_ADDRESS}\",
   \"assets_wasm_hash\": \"${ASSETS_WASM_HASH}\",
   \"blockchain_url\": \"${RPC_URL}\",
   \"network_passphrase\": \"${NETWORK_PASSPHRASE}\"
@@ -106,4 +148,5 @@ fi
 printf "\nRPC_URL=$RPC_URL"
 printf "\nCORE_CONTRACT_ADDRESS=$CORE_ADDRESS"
 printf "\nVOTES_CONTRACT_ADDRESS=$VOTES_ADDRESS"
+printf "\nTREASURY_CONTRACT_ADDRESS=$TREASURY_ADDRESS"
 printf "\nASSETS_WASM_HASH=$ASSETS_WASM_HASH\n"
@@ -7,7 +7,7 @@ PROFILE="release";
 
 mkdir -p "${DIR}"/wasm/
 
-for CRATE in core votes assets hookpoints; do
+for CRATE in core votes assets hookpoints treasury; do
 	printf "> Compiling ${CRATE} contract...\n"
 	cargo build -p elio-${CRATE} --target wasm32-unknown-unknown --profile "${PROFILE}" &&
 		cp "${DIR}"/target/wasm32-unknown-unknown/"${PROFILE}"/elio_${CRATE}.wasm "${DIR}"/wasm/